@@ -0,0 +1,60 @@
+//! Throughput of `gemm` on square matrices, in GFLOP/s.
+//!
+//! Run with `cargo bench --bench gemm`, adding `--features parallel` to
+//! use every core. Sizes can be given as arguments after `--`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use matrix::{gemm, Matrix};
+
+/// Best time of repeated runs of `f`, taking at least `budget` overall.
+fn best_time(budget: Duration, mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    let mut best = Duration::MAX;
+    while best == Duration::MAX || start.elapsed() < budget {
+        let run = Instant::now();
+        f();
+        best = best.min(run.elapsed());
+    }
+    best
+}
+
+fn bench<K>(name: &str, n: usize, one: K, zero: K)
+where
+    K: matrix::Scalar + From<u16>,
+{
+    let a = Matrix::from_fn(n, n, |i, j| K::from(((i * 7 + j * 3) % 17) as u16));
+    let b = Matrix::from_fn(n, n, |i, j| K::from(((i * 5 + j * 11) % 13) as u16));
+    let mut c = Matrix::zeros(n, n);
+    let time = best_time(Duration::from_secs(2), || {
+        gemm(one, a.view(), b.view(), zero, c.view_mut());
+        black_box(&mut c);
+    });
+    let flops = 2. * (n as f64).powi(3);
+    println!(
+        "{} {:>5}: {:>8.2} ms {:>7.2} GFLOP/s",
+        name,
+        n,
+        time.as_secs_f64() * 1e3,
+        flops / time.as_secs_f64() / 1e9
+    );
+}
+
+fn main() {
+    let sizes: Vec<usize> = std::env::args()
+        .skip(1)
+        .filter_map(|arg| arg.parse().ok())
+        .collect();
+    let sizes = if sizes.is_empty() {
+        vec![128, 256, 512, 1024]
+    } else {
+        sizes
+    };
+    for &n in &sizes {
+        bench::<f64>("f64", n, 1., 0.);
+    }
+    for &n in &sizes {
+        bench::<f32>("f32", n, 1., 0.);
+    }
+}
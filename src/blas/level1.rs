@@ -0,0 +1,184 @@
+use num_traits::{Float, Zero};
+
+use crate::error::{check_dimension, LinalgError};
+use crate::scalar::{Field, Scalar};
+use crate::vector::{VectorView, VectorViewMut};
+
+use super::{unwrap, vector, vector_mut};
+
+/// `y = alpha x + y`. Panics if the sizes differ.
+pub fn axpy<K: Scalar>(alpha: K, x: VectorView<K>, y: VectorViewMut<K>) {
+    unwrap(try_axpy(alpha, x, y));
+}
+
+/// `y = alpha x + y`, or `DimensionMismatch` when the sizes differ.
+pub fn try_axpy<K: Scalar>(
+    alpha: K,
+    x: VectorView<K>,
+    mut y: VectorViewMut<K>,
+) -> Result<(), LinalgError> {
+    check_dimension(y.size(), x.size())?;
+    if !alpha.is_zero() {
+        y.axpy(alpha, x);
+    }
+    Ok(())
+}
+
+/// `cblas_?axpy`.
+pub fn axpy_strided<K: Scalar>(n: usize, alpha: K, x: &[K], incx: usize, y: &mut [K], incy: usize) {
+    axpy(alpha, vector(x, n, incx), vector_mut(y, n, incy));
+}
+
+/// `x = alpha x`.
+pub fn scal<K: Scalar>(alpha: K, mut x: VectorViewMut<K>) {
+    x.scl(alpha);
+}
+
+/// `cblas_?scal`.
+pub fn scal_strided<K: Scalar>(n: usize, alpha: K, x: &mut [K], incx: usize) {
+    scal(alpha, vector_mut(x, n, incx));
+}
+
+/// `x^T y`. Panics if the sizes differ.
+pub fn dot<K: Scalar>(x: VectorView<K>, y: VectorView<K>) -> K {
+    x.dot(y)
+}
+
+pub fn try_dot<K: Scalar>(x: VectorView<K>, y: VectorView<K>) -> Result<K, LinalgError> {
+    x.try_dot(y)
+}
+
+/// `cblas_?dot`, and `cblas_?dotu_sub` for complex elements.
+pub fn dot_strided<K: Scalar>(n: usize, x: &[K], incx: usize, y: &[K], incy: usize) -> K {
+    dot(vector(x, n, incx), vector(y, n, incy))
+}
+
+/// `x^H y`, conjugating `x`. Panics if the sizes differ.
+pub fn dotc<K: Scalar>(x: VectorView<K>, y: VectorView<K>) -> K {
+    x.dotc(y)
+}
+
+pub fn try_dotc<K: Scalar>(x: VectorView<K>, y: VectorView<K>) -> Result<K, LinalgError> {
+    x.try_dotc(y)
+}
+
+/// `cblas_?dotc_sub`.
+pub fn dotc_strided<K: Scalar>(n: usize, x: &[K], incx: usize, y: &[K], incy: usize) -> K {
+    dotc(vector(x, n, incx), vector(y, n, incy))
+}
+
+/// Euclidean norm, scaled against overflow like the reference `?nrm2`.
+pub fn nrm2<K: Field>(x: VectorView<K>) -> K::Real {
+    x.norm()
+}
+
+/// `cblas_?nrm2`.
+pub fn nrm2_strided<K: Field>(n: usize, x: &[K], incx: usize) -> K::Real {
+    nrm2(vector(x, n, incx))
+}
+
+/// `|re| + |im|`, the cheap modulus the reference BLAS uses for complex
+/// elements; the absolute value for real ones.
+fn abs1<K: Field>(z: K) -> K::Real {
+    let re = z.re();
+    // `z - re` is purely imaginary, so its modulus is exactly `|im|`.
+    re.abs() + (z - K::from_real(re)).modulus()
+}
+
+/// Sum of `|re| + |im|` over the elements, like the reference `?asum`;
+/// for real elements the 1-norm. [`VectorView::norm_1`] sums the moduli
+/// instead.
+pub fn asum<K: Field>(x: VectorView<K>) -> K::Real {
+    x.iter().fold(K::Real::zero(), |s, &e| s + abs1(e))
+}
+
+/// `cblas_?asum`.
+pub fn asum_strided<K: Field>(n: usize, x: &[K], incx: usize) -> K::Real {
+    asum(vector(x, n, incx))
+}
+
+/// Zero-based index of the first element of largest `|re| + |im|`, like
+/// the reference `i?amax`, or `None` for an empty vector.
+pub fn iamax<K: Field>(x: VectorView<K>) -> Option<usize> {
+    let mut best: Option<(usize, K::Real)> = None;
+    for (i, &e) in x.iter().enumerate() {
+        let m = abs1(e);
+        if best.is_none_or(|(_, max)| m > max) {
+            best = Some((i, m));
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// `cblas_i?amax`.
+pub fn iamax_strided<K: Field>(n: usize, x: &[K], incx: usize) -> Option<usize> {
+    iamax(vector(x, n, incx))
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::*;
+    use crate::vector::Vector;
+
+    #[test]
+    fn axpy_and_scal() {
+        let x = Vector::from([1., 2., 3.]);
+        let mut y = Vector::from([1., 1., 1.]);
+        axpy(2., x.view(), y.view_mut());
+        assert_eq!(Vector::from([3., 5., 7.]), y);
+        scal(0.5, y.view_mut());
+        assert_eq!(Vector::from([1.5, 2.5, 3.5]), y);
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }),
+            try_axpy(1., Vector::from([1., 2.]).view(), y.view_mut())
+        );
+    }
+
+    #[test]
+    fn strided() {
+        // Every other element of `x`, the first two of `y`.
+        let x = [1f64, 0., 2., 0., 3.];
+        let mut y = [10., 20., 30.];
+        axpy_strided(2, -1., &x, 2, &mut y, 1);
+        assert_eq!([9., 18., 30.], y);
+        scal_strided(2, 2., &mut y, 2);
+        assert_eq!([18., 18., 60.], y);
+        assert_eq!(14., dot_strided(3, &x, 2, &x, 2));
+        assert_eq!(6., asum_strided(3, &x, 2));
+        assert_eq!(Some(2), iamax_strided(3, &x, 2));
+        assert!((nrm2_strided(3, &x, 2) - 14f64.sqrt()).abs() < 1e-15);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension mismatch: expected 5, found 3")]
+    fn strided_short_slice() {
+        scal_strided(3, 2., &mut [1., 2., 3.], 2);
+    }
+
+    #[test]
+    fn complex() {
+        let x = Vector::from([Complex::new(0., 1.), Complex::new(3., 4.)]);
+        let y = Vector::from([Complex::new(1., 0.), Complex::new(0., 1.)]);
+        assert_eq!(Complex::new(-4., 4.), dot(x.view(), y.view()));
+        assert_eq!(Complex::new(4., 2.), dotc(x.view(), y.view()));
+        assert_eq!(8., asum(x.view()));
+        assert_eq!(Some(1), iamax(x.view()));
+
+        // 3 + 4i is ahead by |re| + |im| although 6 has the larger modulus.
+        let x = Vector::from([Complex::new(3., 4.), Complex::new(6., 0.)]);
+        assert_eq!(Some(0), iamax(x.view()));
+        assert_eq!(13., asum_strided(2, &x.e, 1));
+        assert_eq!(11., x.view().norm_1());
+    }
+
+    #[test]
+    fn iamax_ties_and_empty() {
+        assert_eq!(Some(1), iamax(Vector::from([1., -3., 3.]).view()));
+        assert_eq!(None, iamax(Vector::<f64>::from(vec![]).view()));
+    }
+}
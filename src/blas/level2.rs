@@ -0,0 +1,536 @@
+use std::ops::Range;
+
+use crate::error::{check_dimension, check_shape, LinalgError};
+use crate::matrix::{Layout, MatrixView, MatrixViewMut};
+use crate::scalar::{Field, Scalar};
+use crate::vector::{VectorView, VectorViewMut};
+
+use super::{check_square, matrix, matrix_mut, unwrap, vector, vector_mut, Diag, Transpose, Uplo};
+
+/// `y = alpha op(A) x + beta y`. Panics on a size mismatch.
+pub fn gemv<K: Scalar>(
+    trans: Transpose,
+    alpha: K,
+    a: MatrixView<K>,
+    x: VectorView<K>,
+    beta: K,
+    y: VectorViewMut<K>,
+) {
+    unwrap(try_gemv(trans, alpha, a, x, beta, y));
+}
+
+/// `y = alpha op(A) x + beta y`, or `DimensionMismatch` when `x` or `y`
+/// does not match `op(A)`. When `beta` is zero `y` is only written.
+pub fn try_gemv<K: Scalar>(
+    trans: Transpose,
+    alpha: K,
+    a: MatrixView<K>,
+    x: VectorView<K>,
+    beta: K,
+    mut y: VectorViewMut<K>,
+) -> Result<(), LinalgError> {
+    let a = match trans {
+        Transpose::NoTrans => a,
+        _ => a.transpose_view(),
+    };
+    check_dimension(a.cols(), x.size())?;
+    check_dimension(a.rows(), y.size())?;
+    scale(beta, &mut y);
+    if alpha.is_zero() {
+        return Ok(());
+    }
+    for (i, yi) in y.iter_mut().enumerate() {
+        let row = a.row(i);
+        let s = if trans == Transpose::ConjTrans {
+            row.dotc(x)
+        } else {
+            row.dot(x)
+        };
+        *yi += alpha * s;
+    }
+    Ok(())
+}
+
+/// `cblas_?gemv`, where `A` is the stored `m x n` matrix.
+pub fn gemv_strided<K: Scalar>(
+    layout: Layout,
+    trans: Transpose,
+    m: usize,
+    n: usize,
+    alpha: K,
+    a: &[K],
+    lda: usize,
+    x: &[K],
+    incx: usize,
+    beta: K,
+    y: &mut [K],
+    incy: usize,
+) {
+    let (x_len, y_len) = match trans {
+        Transpose::NoTrans => (n, m),
+        _ => (m, n),
+    };
+    gemv(
+        trans,
+        alpha,
+        matrix(layout, a, m, n, lda),
+        vector(x, x_len, incx),
+        beta,
+        vector_mut(y, y_len, incy),
+    );
+}
+
+/// Rank-one update `A = alpha x y^T + A`. Panics on a shape mismatch.
+pub fn ger<K: Scalar>(alpha: K, x: VectorView<K>, y: VectorView<K>, a: MatrixViewMut<K>) {
+    unwrap(try_ger(alpha, x, y, a));
+}
+
+/// Rank-one update `A = alpha x y^T + A`, or `ShapeMismatch` unless `A` is
+/// `x.size() x y.size()`. Conjugate `y` first for the complex `?gerc`.
+pub fn try_ger<K: Scalar>(
+    alpha: K,
+    x: VectorView<K>,
+    y: VectorView<K>,
+    mut a: MatrixViewMut<K>,
+) -> Result<(), LinalgError> {
+    check_shape((x.size(), y.size()), a.shape())?;
+    for (i, &xi) in x.iter().enumerate() {
+        a.reborrow().row(i).axpy(alpha * xi, y);
+    }
+    Ok(())
+}
+
+/// `cblas_?ger`, and `cblas_?geru` for complex elements.
+pub fn ger_strided<K: Scalar>(
+    layout: Layout,
+    m: usize,
+    n: usize,
+    alpha: K,
+    x: &[K],
+    incx: usize,
+    y: &[K],
+    incy: usize,
+    a: &mut [K],
+    lda: usize,
+) {
+    ger(
+        alpha,
+        vector(x, m, incx),
+        vector(y, n, incy),
+        matrix_mut(layout, a, m, n, lda),
+    );
+}
+
+/// Solves `op(A) x = b` in place, where `x` holds `b` on entry and `A` is
+/// triangular. Panics on a size mismatch or a zero on the diagonal.
+pub fn trsv<K: Field>(
+    uplo: Uplo,
+    trans: Transpose,
+    diag: Diag,
+    a: MatrixView<K>,
+    x: VectorViewMut<K>,
+) {
+    unwrap(try_trsv(uplo, trans, diag, a, x));
+}
+
+/// Solves `op(A) x = b` in place, reading only the `uplo` triangle of `A`.
+///
+/// Fails with `NotSquare`, with `DimensionMismatch` when `x` does not match
+/// `A`, or with `Singular` when a diagonal element of a `NonUnit` matrix is
+/// zero, in which case `x` is left untouched. The reference routine does not
+/// check for singularity.
+pub fn try_trsv<K: Field>(
+    uplo: Uplo,
+    trans: Transpose,
+    diag: Diag,
+    a: MatrixView<K>,
+    x: VectorViewMut<K>,
+) -> Result<(), LinalgError> {
+    check_square(a.shape())?;
+    check_dimension(a.rows(), x.size())?;
+    let triangle = Triangle::op(a, uplo, trans, diag);
+    triangle.check_diagonal()?;
+    triangle.solve(x);
+    Ok(())
+}
+
+/// `cblas_?trsv`, with `A` an `n x n` matrix.
+pub fn trsv_strided<K: Field>(
+    layout: Layout,
+    uplo: Uplo,
+    trans: Transpose,
+    diag: Diag,
+    n: usize,
+    a: &[K],
+    lda: usize,
+    x: &mut [K],
+    incx: usize,
+) {
+    trsv(
+        uplo,
+        trans,
+        diag,
+        matrix(layout, a, n, n, lda),
+        vector_mut(x, n, incx),
+    );
+}
+
+/// `y = alpha A x + beta y` for a symmetric `A` of which only the `uplo`
+/// triangle is read. Panics on a size mismatch.
+pub fn symv<K: Scalar>(
+    uplo: Uplo,
+    alpha: K,
+    a: MatrixView<K>,
+    x: VectorView<K>,
+    beta: K,
+    y: VectorViewMut<K>,
+) {
+    unwrap(try_symv(uplo, alpha, a, x, beta, y));
+}
+
+/// `y = alpha A x + beta y` for a symmetric `A`, or `NotSquare` or
+/// `DimensionMismatch`. When `beta` is zero `y` is only written.
+pub fn try_symv<K: Scalar>(
+    uplo: Uplo,
+    alpha: K,
+    a: MatrixView<K>,
+    x: VectorView<K>,
+    beta: K,
+    mut y: VectorViewMut<K>,
+) -> Result<(), LinalgError> {
+    check_square(a.shape())?;
+    check_dimension(a.cols(), x.size())?;
+    check_dimension(a.rows(), y.size())?;
+    scale(beta, &mut y);
+    if alpha.is_zero() {
+        return Ok(());
+    }
+    for (i, yi) in y.iter_mut().enumerate() {
+        let mut s = K::zero();
+        for (j, &xj) in x.iter().enumerate() {
+            let aij = match (uplo, i <= j) {
+                (Uplo::Upper, true) | (Uplo::Lower, false) => a[(i, j)],
+                _ => a[(j, i)],
+            };
+            s += aij * xj;
+        }
+        *yi += alpha * s;
+    }
+    Ok(())
+}
+
+/// `cblas_?symv`, with `A` an `n x n` matrix.
+pub fn symv_strided<K: Scalar>(
+    layout: Layout,
+    uplo: Uplo,
+    n: usize,
+    alpha: K,
+    a: &[K],
+    lda: usize,
+    x: &[K],
+    incx: usize,
+    beta: K,
+    y: &mut [K],
+    incy: usize,
+) {
+    symv(
+        uplo,
+        alpha,
+        matrix(layout, a, n, n, lda),
+        vector(x, n, incx),
+        beta,
+        vector_mut(y, n, incy),
+    );
+}
+
+/// `y = beta y`, without reading `y` when `beta` is zero.
+fn scale<K: Scalar>(beta: K, y: &mut VectorViewMut<K>) {
+    if beta.is_zero() {
+        y.fill(K::zero());
+    } else if !beta.is_one() {
+        y.scl(beta);
+    }
+}
+
+/// A triangular matrix `A`, `A^T`, `conj(A)` or `A^H`, solved against by
+/// [`trsv`] and [`trsm`](super::trsm).
+pub(super) struct Triangle<'a, K> {
+    a: MatrixView<'a, K>,
+    transposed: bool,
+    conj: bool,
+    /// Whether the solved matrix, rather than `A`, is lower triangular.
+    lower: bool,
+    unit: bool,
+}
+
+impl<'a, K: Field> Triangle<'a, K> {
+    pub(super) fn new(
+        a: MatrixView<'a, K>,
+        uplo: Uplo,
+        diag: Diag,
+        transposed: bool,
+        conj: bool,
+    ) -> Self {
+        Triangle {
+            a,
+            transposed,
+            conj,
+            lower: (uplo == Uplo::Lower) != transposed,
+            unit: diag == Diag::Unit,
+        }
+    }
+
+    /// `op(A)` for [`Side::Left`](super::Side::Left).
+    pub(super) fn op(a: MatrixView<'a, K>, uplo: Uplo, trans: Transpose, diag: Diag) -> Self {
+        Triangle::new(
+            a,
+            uplo,
+            diag,
+            trans != Transpose::NoTrans,
+            trans == Transpose::ConjTrans,
+        )
+    }
+
+    /// Fails with `Singular` on a zero diagonal element.
+    pub(super) fn check_diagonal(&self) -> Result<(), LinalgError> {
+        if !self.unit && (0..self.a.rows()).any(|i| self.a[(i, i)].is_zero()) {
+            return Err(LinalgError::Singular);
+        }
+        Ok(())
+    }
+
+    fn at(&self, i: usize, j: usize) -> K {
+        let e = if self.transposed {
+            self.a[(j, i)]
+        } else {
+            self.a[(i, j)]
+        };
+        if self.conj {
+            e.conj()
+        } else {
+            e
+        }
+    }
+
+    /// Overwrites `x` with `M^-1 x`, by forward or back substitution.
+    pub(super) fn solve(&self, mut x: VectorViewMut<K>) {
+        let n = x.size();
+        let step = |x: &mut VectorViewMut<K>, i: usize, range: Range<usize>| {
+            let mut s = x[i];
+            for j in range {
+                s -= self.at(i, j) * x[j];
+            }
+            x[i] = if self.unit { s } else { s / self.at(i, i) };
+        };
+        if self.lower {
+            for i in 0..n {
+                step(&mut x, i, 0..i);
+            }
+        } else {
+            for i in (0..n).rev() {
+                step(&mut x, i, i + 1..n);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::*;
+    use crate::matrix::Matrix;
+    use crate::vector::Vector;
+
+    fn sample() -> Matrix<f64> {
+        Matrix::from([[1., 2., 3.], [4., 5., 6.]])
+    }
+
+    #[test]
+    fn gemv_transposes() {
+        let a = sample();
+        let mut y = Vector::from([1., 1.]);
+        gemv(
+            Transpose::NoTrans,
+            2.,
+            a.view(),
+            Vector::from([1., 0., -1.]).view(),
+            3.,
+            y.view_mut(),
+        );
+        assert_eq!(Vector::from([-1., -1.]), y);
+
+        let mut y = Vector::from([f64::NAN; 3]);
+        gemv(
+            Transpose::Trans,
+            1.,
+            a.view(),
+            Vector::from([1., 1.]).view(),
+            0.,
+            y.view_mut(),
+        );
+        assert_eq!(Vector::from([5., 7., 9.]), y);
+
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }),
+            try_gemv(
+                Transpose::NoTrans,
+                1.,
+                a.view(),
+                Vector::from([1., 1.]).view(),
+                0.,
+                y.view_mut()
+            )
+        );
+    }
+
+    #[test]
+    fn gemv_conj_trans() {
+        let i = Complex::new(0., 1.);
+        let a = Matrix::from([[i, Complex::new(1., 0.)]]);
+        let mut y = Vector::from([Complex::new(0., 0.); 2]);
+        gemv(
+            Transpose::ConjTrans,
+            Complex::new(1., 0.),
+            a.view(),
+            Vector::from([i]).view(),
+            Complex::new(0., 0.),
+            y.view_mut(),
+        );
+        assert_eq!(Vector::from([Complex::new(1., 0.), i]), y);
+    }
+
+    #[test]
+    fn gemv_column_major_slices() {
+        // `sample()` stored by columns, with a leading dimension of 3.
+        let a = [1., 4., 0., 2., 5., 0., 3., 6., 0.];
+        let mut y = [0., -1., 0.];
+        gemv_strided(
+            Layout::ColMajor,
+            Transpose::NoTrans,
+            2,
+            3,
+            1.,
+            &a,
+            3,
+            &[1., 1., 1.],
+            1,
+            1.,
+            &mut y,
+            2,
+        );
+        assert_eq!([6., -1., 15.], y);
+    }
+
+    #[test]
+    fn ger_update() {
+        let mut a = Matrix::zeros(2, 3);
+        ger(
+            2.,
+            Vector::from([1., 2.]).view(),
+            Vector::from([1., 0., -1.]).view(),
+            a.view_mut(),
+        );
+        assert_eq!(Matrix::from([[2., 0., -2.], [4., 0., -4.]]), a);
+
+        let mut data = [0.; 4];
+        ger_strided(
+            Layout::RowMajor,
+            2,
+            2,
+            1.,
+            &[1., 2.],
+            1,
+            &[3., 4.],
+            1,
+            &mut data,
+            2,
+        );
+        assert_eq!([3., 4., 6., 8.], data);
+        assert!(try_ger(1., a.row(0), a.row(1), Matrix::zeros(2, 2).view_mut()).is_err());
+    }
+
+    #[test]
+    fn trsv_all_triangles() {
+        let l = Matrix::from([[2., 0., 0.], [1., 3., 0.], [4., -1., 5.]]);
+        // The opposite triangles hold garbage that must not be read.
+        let mut stored = l.clone();
+        stored[(0, 2)] = 100.;
+        let u = l.transpose();
+        let b = Vector::from([2., 7., 13.]);
+        for (a, uplo, trans) in [
+            (&l, Uplo::Lower, Transpose::NoTrans),
+            (&stored, Uplo::Lower, Transpose::NoTrans),
+            (&u, Uplo::Upper, Transpose::Trans),
+        ] {
+            let mut x = b.clone();
+            trsv(uplo, trans, Diag::NonUnit, a.view(), x.view_mut());
+            assert!((&l.mul_vec(&x) - &b).norm() < 1e-12);
+        }
+        for (a, uplo, trans) in [
+            (&u, Uplo::Upper, Transpose::NoTrans),
+            (&l, Uplo::Lower, Transpose::Trans),
+        ] {
+            let mut x = b.clone();
+            trsv(uplo, trans, Diag::NonUnit, a.view(), x.view_mut());
+            assert!((&u.mul_vec(&x) - &b).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn trsv_unit_and_singular() {
+        let a = Matrix::from([[0., 0.], [2., 0.]]);
+        let mut x = [1., 3.];
+        trsv_strided(
+            Layout::RowMajor,
+            Uplo::Lower,
+            Transpose::NoTrans,
+            Diag::Unit,
+            2,
+            a.as_slice(),
+            2,
+            &mut x,
+            1,
+        );
+        assert_eq!([1., 1.], x);
+        assert_eq!(
+            Err(LinalgError::Singular),
+            try_trsv(
+                Uplo::Lower,
+                Transpose::NoTrans,
+                Diag::NonUnit,
+                a.view(),
+                Vector::from([1., 3.]).view_mut()
+            )
+        );
+    }
+
+    #[test]
+    fn symv_reads_one_triangle() {
+        let full = Matrix::from([[1., 2., 3.], [2., 4., 5.], [3., 5., 6.]]);
+        let mut upper = full.clone();
+        upper[(1, 0)] = f64::NAN;
+        upper[(2, 0)] = f64::NAN;
+        upper[(2, 1)] = f64::NAN;
+        let lower = upper.transpose();
+        let x = Vector::from([1., -1., 2.]);
+        let expected = full.mul_vec(&x) * 2. + Vector::from([1., 1., 1.]);
+        for (a, uplo) in [(&upper, Uplo::Upper), (&lower, Uplo::Lower)] {
+            let mut y = Vector::from([1., 1., 1.]);
+            symv(uplo, 2., a.view(), x.view(), 1., y.view_mut());
+            assert_eq!(expected, y);
+        }
+        assert!(try_symv(
+            Uplo::Upper,
+            1.,
+            sample().view(),
+            x.view(),
+            0.,
+            x.clone().view_mut()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,429 @@
+use crate::error::{check_dimension, LinalgError};
+use crate::matrix::{try_gemm_with, GemmOptions, Layout, MatrixView, MatrixViewMut};
+use crate::scalar::{Field, Scalar};
+
+use super::level2::Triangle;
+use super::{check_square, matrix, matrix_mut, unwrap, Diag, Side, Transpose, Uplo};
+
+/// `C = alpha op(A) op(B) + beta C`. Panics on a shape mismatch.
+pub fn gemm<K: Scalar>(
+    trans_a: Transpose,
+    trans_b: Transpose,
+    alpha: K,
+    a: MatrixView<K>,
+    b: MatrixView<K>,
+    beta: K,
+    c: MatrixViewMut<K>,
+) {
+    unwrap(try_gemm(trans_a, trans_b, alpha, a, b, beta, c));
+}
+
+/// [`try_gemm_with`](crate::matrix::try_gemm_with) with the default
+/// blocking.
+pub fn try_gemm<K: Scalar>(
+    trans_a: Transpose,
+    trans_b: Transpose,
+    alpha: K,
+    a: MatrixView<K>,
+    b: MatrixView<K>,
+    beta: K,
+    c: MatrixViewMut<K>,
+) -> Result<(), LinalgError> {
+    let options = GemmOptions::default().trans_a(trans_a).trans_b(trans_b);
+    try_gemm_with(alpha, a, b, beta, c, &options)
+}
+
+/// `cblas_?gemm`, where `op(A)` is `m x k` and `op(B)` is `k x n`.
+pub fn gemm_strided<K: Scalar>(
+    layout: Layout,
+    trans_a: Transpose,
+    trans_b: Transpose,
+    m: usize,
+    n: usize,
+    k: usize,
+    alpha: K,
+    a: &[K],
+    lda: usize,
+    b: &[K],
+    ldb: usize,
+    beta: K,
+    c: &mut [K],
+    ldc: usize,
+) {
+    let (a_rows, a_cols) = stored_shape(trans_a, m, k);
+    let (b_rows, b_cols) = stored_shape(trans_b, k, n);
+    gemm(
+        trans_a,
+        trans_b,
+        alpha,
+        matrix(layout, a, a_rows, a_cols, lda),
+        matrix(layout, b, b_rows, b_cols, ldb),
+        beta,
+        matrix_mut(layout, c, m, n, ldc),
+    );
+}
+
+/// Solves `op(A) X = alpha B` or `X op(A) = alpha B`, depending on `side`,
+/// for a triangular `A`, overwriting `B` with `X`. Panics on a shape
+/// mismatch or a zero on the diagonal.
+pub fn trsm<K: Field>(
+    side: Side,
+    uplo: Uplo,
+    trans: Transpose,
+    diag: Diag,
+    alpha: K,
+    a: MatrixView<K>,
+    b: MatrixViewMut<K>,
+) {
+    unwrap(try_trsm(side, uplo, trans, diag, alpha, a, b));
+}
+
+/// Solves `op(A) X = alpha B` or `X op(A) = alpha B` in place, reading only
+/// the `uplo` triangle of `A`.
+///
+/// Fails with `NotSquare`, with `DimensionMismatch` when `A` does not match
+/// the rows (`Left`) or columns (`Right`) of `B`, or with `Singular` as
+/// [`try_trsv`](super::try_trsv) does, leaving `B` untouched.
+pub fn try_trsm<K: Field>(
+    side: Side,
+    uplo: Uplo,
+    trans: Transpose,
+    diag: Diag,
+    alpha: K,
+    a: MatrixView<K>,
+    mut b: MatrixViewMut<K>,
+) -> Result<(), LinalgError> {
+    check_square(a.shape())?;
+    let triangle = match side {
+        Side::Left => {
+            check_dimension(a.rows(), b.rows())?;
+            Triangle::op(a, uplo, trans, diag)
+        }
+        Side::Right => {
+            check_dimension(a.rows(), b.cols())?;
+            // Each row solves `x op(A) = b`, that is `op(A)^T x^T = b^T`.
+            Triangle::new(
+                a,
+                uplo,
+                diag,
+                trans == Transpose::NoTrans,
+                trans == Transpose::ConjTrans,
+            )
+        }
+    };
+    triangle.check_diagonal()?;
+    if alpha.is_zero() {
+        b.fill(K::zero());
+        return Ok(());
+    } else if !alpha.is_one() {
+        b.scl(alpha);
+    }
+    match side {
+        Side::Left => (0..b.cols()).for_each(|j| triangle.solve(b.reborrow().column(j))),
+        Side::Right => (0..b.rows()).for_each(|i| triangle.solve(b.reborrow().row(i))),
+    }
+    Ok(())
+}
+
+/// `cblas_?trsm`, where `B` is `m x n`.
+pub fn trsm_strided<K: Field>(
+    layout: Layout,
+    side: Side,
+    uplo: Uplo,
+    trans: Transpose,
+    diag: Diag,
+    m: usize,
+    n: usize,
+    alpha: K,
+    a: &[K],
+    lda: usize,
+    b: &mut [K],
+    ldb: usize,
+) {
+    let order = match side {
+        Side::Left => m,
+        Side::Right => n,
+    };
+    trsm(
+        side,
+        uplo,
+        trans,
+        diag,
+        alpha,
+        matrix(layout, a, order, order, lda),
+        matrix_mut(layout, b, m, n, ldb),
+    );
+}
+
+/// Symmetric rank-k update `C = alpha op(A) op(A)^T + beta C`, writing only
+/// the `uplo` triangle of `C`. Panics on a shape mismatch.
+pub fn syrk<K: Scalar>(
+    uplo: Uplo,
+    trans: Transpose,
+    alpha: K,
+    a: MatrixView<K>,
+    beta: K,
+    c: MatrixViewMut<K>,
+) {
+    unwrap(try_syrk(uplo, trans, alpha, a, beta, c));
+}
+
+/// Symmetric rank-k update `C = alpha op(A) op(A)^T + beta C`, or
+/// `NotSquare` or `DimensionMismatch`.
+///
+/// `op(A)` is `A^T` for both `Trans` and `ConjTrans`, as in the real
+/// reference routines; no conjugation happens. When `beta` is zero `C` is
+/// only written.
+pub fn try_syrk<K: Scalar>(
+    uplo: Uplo,
+    trans: Transpose,
+    alpha: K,
+    a: MatrixView<K>,
+    beta: K,
+    mut c: MatrixViewMut<K>,
+) -> Result<(), LinalgError> {
+    let a = match trans {
+        Transpose::NoTrans => a,
+        _ => a.transpose_view(),
+    };
+    check_square(c.shape())?;
+    check_dimension(c.rows(), a.rows())?;
+    let n = c.rows();
+    for i in 0..n {
+        let columns = match uplo {
+            Uplo::Upper => i..n,
+            Uplo::Lower => 0..i + 1,
+        };
+        for j in columns {
+            let s = if alpha.is_zero() {
+                K::zero()
+            } else {
+                alpha * a.row(i).dot(a.row(j))
+            };
+            c[(i, j)] = if beta.is_zero() {
+                s
+            } else {
+                beta * c[(i, j)] + s
+            };
+        }
+    }
+    Ok(())
+}
+
+/// `cblas_?syrk`, where `C` is `n x n` and `op(A)` is `n x k`.
+pub fn syrk_strided<K: Scalar>(
+    layout: Layout,
+    uplo: Uplo,
+    trans: Transpose,
+    n: usize,
+    k: usize,
+    alpha: K,
+    a: &[K],
+    lda: usize,
+    beta: K,
+    c: &mut [K],
+    ldc: usize,
+) {
+    let (a_rows, a_cols) = stored_shape(trans, n, k);
+    syrk(
+        uplo,
+        trans,
+        alpha,
+        matrix(layout, a, a_rows, a_cols, lda),
+        beta,
+        matrix_mut(layout, c, n, n, ldc),
+    );
+}
+
+/// Shape of the stored matrix whose `op` is `rows x cols`.
+fn stored_shape(trans: Transpose, rows: usize, cols: usize) -> (usize, usize) {
+    match trans {
+        Transpose::NoTrans => (rows, cols),
+        _ => (cols, rows),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::*;
+    use crate::matrix::Matrix;
+
+    fn lower() -> Matrix<f64> {
+        Matrix::from([[2., 0., 0.], [1., 3., 0.], [4., -1., 5.]])
+    }
+
+    fn rhs() -> Matrix<f64> {
+        Matrix::from([[1., 2.], [3., 4.], [5., 6.]])
+    }
+
+    fn close<K: Field<Real = f64>>(a: &Matrix<K>, b: &Matrix<K>) -> bool {
+        (a - b).as_slice().iter().all(|e| e.modulus() < 1e-12)
+    }
+
+    #[test]
+    fn gemm_column_major_slices() {
+        // op(A) = A^T with A = [[1, 2], [3, 4]] stored by columns.
+        let a = [1., 3., 2., 4.];
+        let b = [1., 0., 0., 1.];
+        let mut c = [1.; 4];
+        gemm_strided(
+            Layout::ColMajor,
+            Transpose::Trans,
+            Transpose::NoTrans,
+            2,
+            2,
+            2,
+            1.,
+            &a,
+            2,
+            &b,
+            2,
+            1.,
+            &mut c,
+            2,
+        );
+        // C = A^T + 1, stored by columns.
+        assert_eq!([2., 3., 4., 5.], c);
+    }
+
+    #[test]
+    fn trsm_left_and_right() {
+        let l = lower();
+        let b = rhs();
+        for (a, uplo, trans) in [
+            (l.clone(), Uplo::Lower, Transpose::NoTrans),
+            (l.transpose(), Uplo::Upper, Transpose::Trans),
+        ] {
+            let mut x = b.clone();
+            trsm(
+                Side::Left,
+                uplo,
+                trans,
+                Diag::NonUnit,
+                2.,
+                a.view(),
+                x.view_mut(),
+            );
+            assert!(close(&l.mul_mat(&x), &(&b * 2.)));
+        }
+
+        let bt = b.transpose();
+        for (a, uplo, trans) in [
+            (l.clone(), Uplo::Lower, Transpose::NoTrans),
+            (l.transpose(), Uplo::Upper, Transpose::Trans),
+        ] {
+            let mut x = bt.clone();
+            trsm(
+                Side::Right,
+                uplo,
+                trans,
+                Diag::NonUnit,
+                1.,
+                a.view(),
+                x.view_mut(),
+            );
+            assert!(close(&x.mul_mat(&l), &bt));
+        }
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }),
+            try_trsm(
+                Side::Right,
+                Uplo::Lower,
+                Transpose::NoTrans,
+                Diag::NonUnit,
+                1.,
+                l.view(),
+                rhs().view_mut()
+            )
+        );
+    }
+
+    #[test]
+    fn trsm_right_conj_trans() {
+        let i = Complex::new(0., 1.);
+        let one = Complex::new(1., 0.);
+        // X U^H = B for the upper triangular U = [[1, i], [0, 2]].
+        let u = Matrix::from([[one, i], [Complex::new(0., 0.), one * 2.]]);
+        let b = Matrix::from([[one, i]]);
+        let mut x = b.clone();
+        trsm(
+            Side::Right,
+            Uplo::Upper,
+            Transpose::ConjTrans,
+            Diag::NonUnit,
+            one,
+            u.view(),
+            x.view_mut(),
+        );
+        assert!(close(&x.mul_mat(&u.conjugate_transpose()), &b));
+    }
+
+    #[test]
+    fn trsm_singular_leaves_b() {
+        let mut a = lower();
+        a[(1, 1)] = 0.;
+        let mut b = rhs();
+        assert_eq!(
+            Err(LinalgError::Singular),
+            try_trsm(
+                Side::Left,
+                Uplo::Lower,
+                Transpose::NoTrans,
+                Diag::NonUnit,
+                2.,
+                a.view(),
+                b.view_mut()
+            )
+        );
+        assert_eq!(rhs(), b);
+    }
+
+    #[test]
+    fn syrk_triangles() {
+        let a = rhs();
+        let full = a.mul_mat(&a.transpose());
+        let mut upper = Matrix::from_fn(3, 3, |_, _| f64::NAN);
+        syrk(
+            Uplo::Upper,
+            Transpose::NoTrans,
+            1.,
+            a.view(),
+            0.,
+            upper.view_mut(),
+        );
+        let mut lower = Matrix::from_fn(3, 3, |_, _| 1.);
+        let at = a.transpose();
+        syrk_strided(
+            Layout::RowMajor,
+            Uplo::Lower,
+            Transpose::Trans,
+            3,
+            2,
+            2.,
+            at.as_slice(),
+            3,
+            -1.,
+            lower.as_mut_slice(),
+            3,
+        );
+        for i in 0..3 {
+            for j in 0..3 {
+                if i <= j {
+                    assert_eq!(full[(i, j)], upper[(i, j)]);
+                } else {
+                    assert!(upper[(i, j)].is_nan());
+                }
+                if i >= j {
+                    assert_eq!(2. * full[(i, j)] - 1., lower[(i, j)]);
+                } else {
+                    assert_eq!(1., lower[(i, j)]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! The BLAS routines under their reference names, for code ported from
+//! Fortran or C.
+//!
+//! Every routine takes its operands as views, with a panicking form and a
+//! `try_*` form, plus a `*_strided` form over plain slices whose arguments
+//! follow the CBLAS prototype: sizes, increments and leading dimensions,
+//! with a [`Layout`] for the matrices. The differences from the reference
+//! are that indices are zero-based, increments cannot be negative, and the
+//! element type is generic instead of being part of the name (`axpy` covers
+//! `saxpy`, `daxpy`, `caxpy` and `zaxpy`).
+
+// The `*_strided` signatures mirror CBLAS argument for argument.
+#![allow(clippy::too_many_arguments)]
+
+use crate::error::LinalgError;
+use crate::matrix::{Layout, MatrixView, MatrixViewMut};
+use crate::vector::{VectorView, VectorViewMut};
+
+pub use crate::matrix::Transpose;
+
+mod level1;
+mod level2;
+mod level3;
+
+pub use level1::{
+    asum, asum_strided, axpy, axpy_strided, dot, dot_strided, dotc, dotc_strided, iamax,
+    iamax_strided, nrm2, nrm2_strided, scal, scal_strided, try_axpy, try_dot, try_dotc,
+};
+pub use level2::{
+    gemv, gemv_strided, ger, ger_strided, symv, symv_strided, trsv, trsv_strided, try_gemv,
+    try_ger, try_symv, try_trsv,
+};
+pub use level3::{
+    gemm, gemm_strided, syrk, syrk_strided, trsm, trsm_strided, try_gemm, try_syrk, try_trsm,
+};
+
+/// Which triangle of a matrix a routine reads or writes, the BLAS `UPLO`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Uplo {
+    Upper,
+    Lower,
+}
+
+/// Whether a triangular matrix has an implicit unit diagonal, the BLAS
+/// `DIAG`. With `Unit` the stored diagonal is never read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Diag {
+    NonUnit,
+    Unit,
+}
+
+/// The side of the unknown a triangular matrix multiplies, the BLAS `SIDE`:
+/// `op(A) X` for `Left`, `X op(A)` for `Right`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+fn unwrap<T>(result: Result<T, LinalgError>) -> T {
+    result.unwrap_or_else(|e| panic!("{}", e))
+}
+
+fn strides(layout: Layout, ld: usize) -> (usize, usize) {
+    match layout {
+        Layout::RowMajor => (ld, 1),
+        Layout::ColMajor => (1, ld),
+    }
+}
+
+// Slice adapters of the `*_strided` routines, which panic when a slice is
+// too short for its size and increment.
+
+fn vector<K>(x: &[K], n: usize, inc: usize) -> VectorView<'_, K> {
+    unwrap(VectorView::from_slice(x, n, inc))
+}
+
+fn vector_mut<K>(x: &mut [K], n: usize, inc: usize) -> VectorViewMut<'_, K> {
+    unwrap(VectorViewMut::from_slice(x, n, inc))
+}
+
+fn matrix<K>(layout: Layout, a: &[K], rows: usize, cols: usize, ld: usize) -> MatrixView<'_, K> {
+    unwrap(MatrixView::from_slice(a, rows, cols, strides(layout, ld)))
+}
+
+fn matrix_mut<K>(
+    layout: Layout,
+    a: &mut [K],
+    rows: usize,
+    cols: usize,
+    ld: usize,
+) -> MatrixViewMut<'_, K> {
+    unwrap(MatrixViewMut::from_slice(
+        a,
+        rows,
+        cols,
+        strides(layout, ld),
+    ))
+}
+
+fn check_square(shape: (usize, usize)) -> Result<(), LinalgError> {
+    if shape.0 != shape.1 {
+        return Err(LinalgError::NotSquare {
+            rows: shape.0,
+            cols: shape.1,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use num_traits::{Float, Zero};
+
+use crate::error::{check_dimension, LinalgError};
+use crate::matrix::Matrix;
+use crate::scalar::Field;
+use crate::vector::Vector;
+
+/// Cholesky factorization `A = L L^H` of a Hermitian positive definite
+/// matrix, from [`Matrix::cholesky`].
+#[derive(Clone, Debug)]
+pub struct Cholesky<K> {
+    l: Matrix<K>,
+}
+
+impl<K: Field> Matrix<K> {
+    /// Factors a Hermitian (real symmetric) positive definite matrix. Only
+    /// the lower triangle is read.
+    ///
+    /// Fails with `NotSquare`, or with `NotPositiveDefinite` naming the
+    /// first column whose pivot is not positive.
+    pub fn cholesky(&self) -> Result<Cholesky<K>, LinalgError> {
+        self.check_square()?;
+        let n = self.rows();
+        let mut l: Matrix<K> = Matrix::zeros(n, n);
+        for j in 0..n {
+            let d = (0..j).fold(self[(j, j)].re(), |d, k| d - l[(j, k)].modulus_squared());
+            if d.is_nan() || d <= K::Real::zero() {
+                return Err(LinalgError::NotPositiveDefinite { pivot: j });
+            }
+            let d = d.sqrt();
+            l[(j, j)] = K::from_real(d);
+            for i in j + 1..n {
+                let s = (0..j).fold(self[(i, j)], |s, k| s - l[(i, k)] * l[(j, k)].conj());
+                l[(i, j)] = s / K::from_real(d);
+            }
+        }
+        Ok(Cholesky { l })
+    }
+}
+
+impl<K: Field> Cholesky<K> {
+    /// The lower triangular factor, with a real positive diagonal.
+    pub fn l(&self) -> &Matrix<K> {
+        &self.l
+    }
+
+    /// Product of the squared diagonal of `L`.
+    pub fn determinant(&self) -> K {
+        (0..self.l.rows()).fold(K::one(), |acc, i| acc * self.l[(i, i)] * self.l[(i, i)])
+    }
+
+    /// Solves `A x = b`, failing with `DimensionMismatch`.
+    pub fn solve(&self, b: &Vector<K>) -> Result<Vector<K>, LinalgError> {
+        check_dimension(self.l.rows(), b.size())?;
+        let mut x = b.e.clone();
+        self.substitute(&mut x);
+        Ok(Vector::from(x))
+    }
+
+    /// Solves `A X = B` column by column, failing with `DimensionMismatch`
+    /// when `B` does not have `n` rows.
+    pub fn solve_matrix(&self, b: &Matrix<K>) -> Result<Matrix<K>, LinalgError> {
+        check_dimension(self.l.rows(), b.rows())?;
+        let mut x = Matrix::zeros(b.rows(), b.cols());
+        let mut col = vec![K::zero(); b.rows()];
+        for j in 0..b.cols() {
+            for (i, c) in col.iter_mut().enumerate() {
+                *c = b[(i, j)];
+            }
+            self.substitute(&mut col);
+            for (i, c) in col.iter().enumerate() {
+                x[(i, j)] = *c;
+            }
+        }
+        Ok(x)
+    }
+
+    /// Inverse of the factored matrix.
+    pub fn inverse(&self) -> Matrix<K> {
+        let n = self.l.rows();
+        self.solve_matrix(&Matrix::identity(n))
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Overwrites `x` with `L^-H L^-1 x`.
+    fn substitute(&self, x: &mut [K]) {
+        let n = self.l.rows();
+        for i in 0..n {
+            for k in 0..i {
+                let xk = x[k];
+                x[i] -= self.l[(i, k)] * xk;
+            }
+            x[i] /= self.l[(i, i)];
+        }
+        for i in (0..n).rev() {
+            for k in i + 1..n {
+                let xk = x[k];
+                x[i] -= self.l[(k, i)].conj() * xk;
+            }
+            x[i] /= self.l[(i, i)];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::super::assert_close;
+    use super::*;
+
+    #[test]
+    fn cholesky_factors() {
+        let a = Matrix::from([[4., 12., -16.], [12., 37., -43.], [-16., -43., 98.]]);
+        let chol = a.cholesky().unwrap();
+        assert_close(
+            &Matrix::from([[2., 0., 0.], [6., 1., 0.], [-8., 5., 3.]]),
+            chol.l(),
+            1e-12,
+        );
+        assert!((chol.determinant() - 36.).abs() < 1e-9);
+
+        let b = Vector::from([1., 2., 3.]);
+        let x = chol.solve(&b).unwrap();
+        assert_close(
+            &Matrix::from(vec![b.e]),
+            &Matrix::from(vec![(&a * &x).e]),
+            1e-9,
+        );
+        assert_close(&Matrix::identity(3), &(&a * &chol.inverse()), 1e-9);
+        let x = chol.solve_matrix(&Matrix::identity(3)).unwrap();
+        assert_close(&chol.inverse(), &x, 0.);
+    }
+
+    #[test]
+    fn cholesky_errors() {
+        let indefinite = Matrix::from([[1., 2., 0.], [2., 1., 0.], [0., 0., 1.]]);
+        assert_eq!(
+            LinalgError::NotPositiveDefinite { pivot: 1 },
+            indefinite.cholesky().unwrap_err()
+        );
+        let semidefinite = Matrix::from([[1., 1.], [1., 1.]]);
+        assert_eq!(
+            LinalgError::NotPositiveDefinite { pivot: 1 },
+            semidefinite.cholesky().unwrap_err()
+        );
+        assert_eq!(
+            LinalgError::NotPositiveDefinite { pivot: 0 },
+            Matrix::from([[-1.]]).cholesky().unwrap_err()
+        );
+        assert!(Matrix::from([[1., 2.]]).cholesky().is_err());
+        let chol = Matrix::from([[1.]]).cholesky().unwrap();
+        assert!(chol.solve(&Vector::from([1., 2.])).is_err());
+    }
+
+    #[test]
+    fn cholesky_hermitian() {
+        let a = Matrix::from([
+            [Complex::new(2., 0.), Complex::new(0., -1.)],
+            [Complex::new(0., 1.), Complex::new(2., 0.)],
+        ]);
+        let chol = a.cholesky().unwrap();
+        let llh = chol.l() * &chol.l().conjugate_transpose();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((llh[(i, j)] - a[(i, j)]).norm() < 1e-12);
+            }
+        }
+        // 2 * 2 - (-i)(i) = 3
+        assert!((chol.determinant() - Complex::new(3., 0.)).norm() < 1e-12);
+    }
+}
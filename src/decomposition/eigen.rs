@@ -0,0 +1,261 @@
+use num_complex::Complex;
+
+use crate::error::LinalgError;
+use crate::matrix::Matrix;
+use crate::scalar::RealField;
+use crate::vector;
+
+/// Eigenvalues and right eigenvectors of a general real matrix, from
+/// [`Matrix::eigen`].
+#[derive(Clone, Debug)]
+pub struct Eigen<K> {
+    eigenvalues: Vec<Complex<K>>,
+    eigenvectors: Matrix<Complex<K>>,
+}
+
+impl<K: RealField> Matrix<K> {
+    /// Eigenvalues and right eigenvectors `A v = w v`, by back substitution
+    /// on the real Schur form.
+    ///
+    /// Fails like [`Matrix::schur`].
+    pub fn eigen(&self) -> Result<Eigen<K>, LinalgError> {
+        let schur = self.schur()?;
+        let w = schur.eigenvalues();
+        let mut t = schur.t().clone();
+        back_substitute(&mut t, w);
+        // The columns of Z T' are the eigenvectors, packed like T: a complex
+        // pair keeps its real part in the first column, its imaginary part
+        // in the second.
+        let x = schur.z() * &t;
+        let n = self.rows();
+        let mut v = Matrix::zeros(n, n);
+        let mut j = 0;
+        while j < n {
+            if w[j].im.is_zero() {
+                for i in 0..n {
+                    v[(i, j)] = Complex::new(x[(i, j)], K::zero());
+                }
+                j += 1;
+            } else {
+                for i in 0..n {
+                    let z = Complex::new(x[(i, j)], x[(i, j + 1)]);
+                    v[(i, j)] = z;
+                    v[(i, j + 1)] = z.conj();
+                }
+                j += 2;
+            }
+        }
+        for j in 0..n {
+            let column: Vec<_> = (0..n).map(|i| v[(i, j)]).collect();
+            let norm = vector::norm_2(&column);
+            if !norm.is_zero() {
+                for i in 0..n {
+                    v[(i, j)] /= Complex::new(norm, K::zero());
+                }
+            }
+        }
+        Ok(Eigen {
+            eigenvalues: w.to_vec(),
+            eigenvectors: v,
+        })
+    }
+}
+
+impl<K: RealField> Eigen<K> {
+    /// Eigenvalues, in the order of [`Matrix::eigenvalues`].
+    pub fn eigenvalues(&self) -> &[Complex<K>] {
+        &self.eigenvalues
+    }
+
+    /// Unit eigenvectors as columns, in the order of
+    /// [`Eigen::eigenvalues`]. Eigenvectors of a complex conjugate pair are
+    /// conjugate too.
+    pub fn eigenvectors(&self) -> &Matrix<Complex<K>> {
+        &self.eigenvectors
+    }
+}
+
+/// Overwrites the quasi upper triangular `t` with the eigenvectors of
+/// `T`, by back substitution from the bottom right.
+fn back_substitute<K: RealField>(t: &mut Matrix<K>, w: &[Complex<K>]) {
+    let nn = t.rows();
+    let zero = K::zero();
+    let eps = K::epsilon();
+    let mut norm = zero;
+    for i in 0..nn {
+        for j in i.saturating_sub(1)..nn {
+            norm += t[(i, j)].abs();
+        }
+    }
+    if norm.is_zero() {
+        // Every vector is an eigenvector of the zero matrix.
+        *t = Matrix::identity(nn);
+        return;
+    }
+    let d: Vec<K> = w.iter().map(|w| w.re).collect();
+    let e: Vec<K> = w.iter().map(|w| w.im).collect();
+    let (mut z, mut r, mut s) = (zero, zero, zero);
+    let cdiv = |a: K, b: K, c: K, d: K| Complex::new(a, b) / Complex::new(c, d);
+
+    for n in (0..nn).rev() {
+        let (p, q) = (d[n], e[n]);
+        if q.is_zero() {
+            // Real vector.
+            let mut l = n;
+            t[(n, n)] = K::one();
+            for i in (0..n).rev() {
+                let w = t[(i, i)] - p;
+                r = (l..=n).fold(zero, |r, j| r + t[(i, j)] * t[(j, n)]);
+                if e[i] < zero {
+                    z = w;
+                    s = r;
+                    continue;
+                }
+                l = i;
+                if e[i].is_zero() {
+                    t[(i, n)] = if w.is_zero() {
+                        -r / (eps * norm)
+                    } else {
+                        -r / w
+                    };
+                } else {
+                    let (x, y) = (t[(i, i + 1)], t[(i + 1, i)]);
+                    let q = (d[i] - p) * (d[i] - p) + e[i] * e[i];
+                    let v = (x * s - z * r) / q;
+                    t[(i, n)] = v;
+                    t[(i + 1, n)] = if x.abs() > z.abs() {
+                        (-r - w * v) / x
+                    } else {
+                        (-s - y * v) / z
+                    };
+                }
+                // Overflow control.
+                let m = t[(i, n)].abs();
+                if eps * m * m > K::one() {
+                    for j in i..=n {
+                        t[(j, n)] /= m;
+                    }
+                }
+            }
+        } else if q < zero {
+            // Complex vector, in columns n - 1 (real) and n (imaginary).
+            let mut l = n - 1;
+            let c = if t[(n, n - 1)].abs() > t[(n - 1, n)].abs() {
+                Complex::new(q / t[(n, n - 1)], -(t[(n, n)] - p) / t[(n, n - 1)])
+            } else {
+                cdiv(zero, -t[(n - 1, n)], t[(n - 1, n - 1)] - p, q)
+            };
+            t[(n - 1, n - 1)] = c.re;
+            t[(n - 1, n)] = c.im;
+            t[(n, n - 1)] = zero;
+            t[(n, n)] = K::one();
+            for i in (0..n - 1).rev() {
+                let (ra, sa) = (l..=n).fold((zero, zero), |(ra, sa), j| {
+                    (ra + t[(i, j)] * t[(j, n - 1)], sa + t[(i, j)] * t[(j, n)])
+                });
+                let w = t[(i, i)] - p;
+                if e[i] < zero {
+                    z = w;
+                    r = ra;
+                    s = sa;
+                    continue;
+                }
+                l = i;
+                if e[i].is_zero() {
+                    let c = cdiv(-ra, -sa, w, q);
+                    t[(i, n - 1)] = c.re;
+                    t[(i, n)] = c.im;
+                } else {
+                    let (x, y) = (t[(i, i + 1)], t[(i + 1, i)]);
+                    let mut vr = (d[i] - p) * (d[i] - p) + e[i] * e[i] - q * q;
+                    let vi = (d[i] - p) * (q + q);
+                    if vr.is_zero() && vi.is_zero() {
+                        vr = eps * norm * (w.abs() + q.abs() + x.abs() + y.abs() + z.abs());
+                    }
+                    let c = cdiv(x * r - z * ra + q * sa, x * s - z * sa - q * ra, vr, vi);
+                    t[(i, n - 1)] = c.re;
+                    t[(i, n)] = c.im;
+                    if x.abs() > z.abs() + q.abs() {
+                        t[(i + 1, n - 1)] = (-ra - w * c.re + q * c.im) / x;
+                        t[(i + 1, n)] = (-sa - w * c.im - q * c.re) / x;
+                    } else {
+                        let c = cdiv(-r - y * c.re, -s - y * c.im, z, q);
+                        t[(i + 1, n - 1)] = c.re;
+                        t[(i + 1, n)] = c.im;
+                    }
+                }
+                // Overflow control.
+                let m = t[(i, n - 1)].abs().max(t[(i, n)].abs());
+                if eps * m * m > K::one() {
+                    for j in i..=n {
+                        t[(j, n - 1)] /= m;
+                        t[(j, n)] /= m;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(a: &Matrix<f64>) {
+        let eigen = a.eigen().unwrap();
+        let n = a.rows();
+        let a = Matrix::from_fn(n, n, |i, j| Complex::new(a[(i, j)], 0.));
+        let v = eigen.eigenvectors();
+        let av = &a * v;
+        for j in 0..n {
+            let w = eigen.eigenvalues()[j];
+            let norm: f64 = (0..n).map(|i| v[(i, j)].norm_sqr()).sum();
+            assert!((norm - 1.).abs() < 1e-12);
+            for i in 0..n {
+                let err = (av[(i, j)] - v[(i, j)] * w).norm();
+                assert!(err < 1e-10, "column {}: residual {}", j, err);
+            }
+        }
+    }
+
+    #[test]
+    fn real_eigenvectors() {
+        check(&Matrix::from([[2., 0., 0.], [1., 3., 0.], [4., 5., 6.]]));
+        check(&Matrix::from([[1., 2.], [3., 4.]]));
+        check(&Matrix::from([
+            [4., 1., 2., 3.],
+            [0., 3., 1., 1.],
+            [2., 1., 5., 0.],
+            [1., 0., 1., 2.],
+        ]));
+        // Defective: both eigenvectors come out parallel.
+        check(&Matrix::from([[1., 1.], [0., 1.]]));
+    }
+
+    #[test]
+    fn complex_eigenvectors() {
+        let a = Matrix::from([[0., -1.], [1., 0.]]);
+        check(&a);
+        let eigen = a.eigen().unwrap();
+        let v = eigen.eigenvectors();
+        for i in 0..2 {
+            assert_eq!(v[(i, 0)].conj(), v[(i, 1)]);
+        }
+
+        check(&Matrix::from([[0., 0., 1.], [1., 0., 0.], [0., 1., 0.]]));
+        check(&Matrix::from([
+            [1., 2., 0., 1.],
+            [-2., 1., 3., 0.],
+            [0., 0., 3., 1.],
+            [1., 0., -1., 3.],
+        ]));
+    }
+
+    #[test]
+    fn eigen_edge_cases() {
+        assert!(Matrix::from([[1., 2.]]).eigen().is_err());
+        check(&Matrix::zeros(3, 3));
+        let empty = Matrix::<f64>::zeros(0, 0).eigen().unwrap();
+        assert!(empty.eigenvalues().is_empty());
+    }
+}
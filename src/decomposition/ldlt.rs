@@ -0,0 +1,357 @@
+use num_traits::NumCast;
+
+use crate::error::{check_dimension, LinalgError};
+use crate::matrix::{Layout, Matrix};
+use crate::scalar::RealField;
+use crate::vector::Vector;
+
+use super::max_modulus;
+
+/// Factorization `P A P^T = L D L^T` of a real symmetric matrix, from
+/// [`Matrix::ldlt`].
+///
+/// `L` is unit lower triangular and `D` block diagonal with 1x1 and 2x2
+/// blocks, so indefinite matrices factor without breaking symmetry.
+#[derive(Clone, Debug)]
+pub struct Ldlt<K> {
+    l: Matrix<K>,
+    /// Diagonal of `D`.
+    d: Vec<K>,
+    /// Subdiagonal of `D`, nonzero only inside 2x2 blocks.
+    e: Vec<K>,
+    /// Size of each diagonal block of `D`, from top to bottom.
+    blocks: Vec<usize>,
+    perm: Vec<usize>,
+    /// Largest element of the factored matrix in magnitude.
+    scale: K,
+}
+
+impl<K: RealField> Matrix<K> {
+    /// Factors a symmetric matrix with Bunch-Kaufman diagonal pivoting.
+    /// The whole matrix is read and assumed symmetric.
+    ///
+    /// Fails with `NotSquare`. Singular matrices are factored too; see
+    /// [`Ldlt::is_singular`].
+    pub fn ldlt(&self) -> Result<Ldlt<K>, LinalgError> {
+        self.check_square()?;
+        let n = self.rows();
+        let alpha = (K::one() + <K as NumCast>::from(17).unwrap().sqrt())
+            / <K as NumCast>::from(8).unwrap();
+        let mut a = self.to_layout(Layout::RowMajor);
+        let mut l = Matrix::identity(n);
+        let (mut d, mut e) = (vec![K::zero(); n], vec![K::zero(); n]);
+        let mut blocks = Vec::new();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut k = 0;
+        while k < n {
+            let absakk = a[(k, k)].abs();
+            let (imax, colmax) = (k + 1..n)
+                .map(|i| (i, a[(i, k)].abs()))
+                .fold((k, K::zero()), |m, c| if c.1 > m.1 { c } else { m });
+            let (kp, kstep) = if absakk.max(colmax).is_zero() || absakk >= alpha * colmax {
+                (k, 1)
+            } else {
+                let rowmax = (k..n)
+                    .filter(|&j| j != imax)
+                    .fold(K::zero(), |m, j| m.max(a[(imax, j)].abs()));
+                if absakk * rowmax >= alpha * colmax * colmax {
+                    (k, 1)
+                } else if a[(imax, imax)].abs() >= alpha * rowmax {
+                    (imax, 1)
+                } else {
+                    (imax, 2)
+                }
+            };
+
+            let kk = k + kstep - 1;
+            if kp != kk {
+                a.swap_rows(kk, kp);
+                for i in 0..n {
+                    let t = a[(i, kk)];
+                    a[(i, kk)] = a[(i, kp)];
+                    a[(i, kp)] = t;
+                }
+                for j in 0..k {
+                    let t = l[(kk, j)];
+                    l[(kk, j)] = l[(kp, j)];
+                    l[(kp, j)] = t;
+                }
+                perm.swap(kk, kp);
+            }
+
+            if kstep == 1 {
+                let dk = a[(k, k)];
+                d[k] = dk;
+                if !dk.is_zero() {
+                    for i in k + 1..n {
+                        l[(i, k)] = a[(i, k)] / dk;
+                    }
+                    for i in k + 1..n {
+                        for j in k + 1..n {
+                            let update = l[(i, k)] * a[(j, k)];
+                            a[(i, j)] -= update;
+                        }
+                    }
+                }
+            } else {
+                let (a11, a21, a22) = (a[(k, k)], a[(k + 1, k)], a[(k + 1, k + 1)]);
+                let det = a11 * a22 - a21 * a21;
+                d[k] = a11;
+                d[k + 1] = a22;
+                e[k] = a21;
+                for i in k + 2..n {
+                    let (u, v) = (a[(i, k)], a[(i, k + 1)]);
+                    l[(i, k)] = (u * a22 - v * a21) / det;
+                    l[(i, k + 1)] = (v * a11 - u * a21) / det;
+                }
+                for i in k + 2..n {
+                    for j in k + 2..n {
+                        let update = l[(i, k)] * a[(j, k)] + l[(i, k + 1)] * a[(j, k + 1)];
+                        a[(i, j)] -= update;
+                    }
+                }
+            }
+            blocks.push(kstep);
+            k += kstep;
+        }
+        Ok(Ldlt {
+            scale: max_modulus(self),
+            l,
+            d,
+            e,
+            blocks,
+            perm,
+        })
+    }
+}
+
+impl<K: RealField> Ldlt<K> {
+    fn size(&self) -> usize {
+        self.l.rows()
+    }
+
+    /// The unit lower triangular factor `L`.
+    pub fn l(&self) -> &Matrix<K> {
+        &self.l
+    }
+
+    /// The block diagonal factor `D`.
+    pub fn d(&self) -> Matrix<K> {
+        let n = self.size();
+        Matrix::from_fn(n, n, |i, j| match i.abs_diff(j) {
+            0 => self.d[i],
+            1 => self.e[i.min(j)],
+            _ => K::zero(),
+        })
+    }
+
+    /// Row `i` of `P A P^T` is row `permutation()[i]` of `A`, and likewise
+    /// for columns.
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Determinants of the diagonal blocks of `D`, with their starting
+    /// indices.
+    fn block_dets(&self) -> impl Iterator<Item = (usize, usize, K)> + '_ {
+        self.blocks.iter().scan(0, |k, &size| {
+            let start = *k;
+            *k += size;
+            let det = if size == 1 {
+                self.d[start]
+            } else {
+                self.d[start] * self.d[start + 1] - self.e[start] * self.e[start]
+            };
+            Some((start, size, det))
+        })
+    }
+
+    /// True when a diagonal block of `D` has a determinant that is exactly
+    /// zero or not finite, which is when [`Ldlt::solve`] and
+    /// [`Ldlt::inverse`] fail. Badly scaled but regular matrices such as
+    /// `diag(1e20, 1)` are not singular.
+    pub fn is_singular(&self) -> bool {
+        self.block_dets()
+            .any(|(_, _, det)| det.is_zero() || !det.is_finite())
+    }
+
+    /// True when a 1x1 block of `D` is at most `tol` times the largest
+    /// element of the factored matrix in magnitude, or a 2x2 block has a
+    /// determinant at most the square of that, or a determinant is not
+    /// finite. `n * epsilon` is the usual choice of `tol`.
+    pub fn is_singular_with(&self, tol: K) -> bool {
+        let cutoff = tol * self.scale;
+        self.block_dets().any(|(_, size, det)| {
+            let cutoff = if size == 1 { cutoff } else { cutoff * cutoff };
+            det.abs() <= cutoff || !det.is_finite()
+        })
+    }
+
+    /// Product of the determinants of the blocks of `D`.
+    pub fn determinant(&self) -> K {
+        self.block_dets()
+            .fold(K::one(), |acc, (_, _, det)| acc * det)
+    }
+
+    /// Solves `A x = b`, failing with `DimensionMismatch` or `Singular`.
+    pub fn solve(&self, b: &Vector<K>) -> Result<Vector<K>, LinalgError> {
+        check_dimension(self.size(), b.size())?;
+        if self.is_singular() {
+            return Err(LinalgError::Singular);
+        }
+        let mut y: Vec<K> = self.perm.iter().map(|&p| b[p]).collect();
+        self.substitute(&mut y);
+        let mut x = vec![K::zero(); self.size()];
+        for (i, &p) in self.perm.iter().enumerate() {
+            x[p] = y[i];
+        }
+        Ok(Vector::from(x))
+    }
+
+    /// Solves `A X = B` column by column, failing with `DimensionMismatch`
+    /// when `B` does not have `n` rows or with `Singular`.
+    pub fn solve_matrix(&self, b: &Matrix<K>) -> Result<Matrix<K>, LinalgError> {
+        check_dimension(self.size(), b.rows())?;
+        if self.is_singular() {
+            return Err(LinalgError::Singular);
+        }
+        let mut x = Matrix::zeros(b.rows(), b.cols());
+        let mut col = vec![K::zero(); self.size()];
+        for j in 0..b.cols() {
+            for (c, &p) in col.iter_mut().zip(&self.perm) {
+                *c = b[(p, j)];
+            }
+            self.substitute(&mut col);
+            for (c, &p) in col.iter().zip(&self.perm) {
+                x[(p, j)] = *c;
+            }
+        }
+        Ok(x)
+    }
+
+    /// Inverse of the factored matrix, failing with `Singular`.
+    pub fn inverse(&self) -> Result<Matrix<K>, LinalgError> {
+        self.solve_matrix(&Matrix::identity(self.size()))
+    }
+
+    /// Overwrites the permuted right-hand side `y` with
+    /// `L^-T D^-1 L^-1 y`.
+    fn substitute(&self, y: &mut [K]) {
+        let n = self.size();
+        for i in 0..n {
+            for k in 0..i {
+                let yk = y[k];
+                y[i] -= self.l[(i, k)] * yk;
+            }
+        }
+        for (k, size, det) in self.block_dets() {
+            if size == 1 {
+                y[k] /= det;
+            } else {
+                let (u, v) = (y[k], y[k + 1]);
+                y[k] = (u * self.d[k + 1] - v * self.e[k]) / det;
+                y[k + 1] = (v * self.d[k] - u * self.e[k]) / det;
+            }
+        }
+        for i in (0..n).rev() {
+            for k in i + 1..n {
+                let yk = y[k];
+                y[i] -= self.l[(k, i)] * yk;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::assert_close;
+    use super::*;
+
+    fn check(a: &Matrix<f64>, ldlt: &Ldlt<f64>) {
+        let p = ldlt.permutation();
+        let pap = Matrix::from_fn(a.rows(), a.cols(), |i, j| a[(p[i], p[j])]);
+        let ldl = &(ldlt.l() * &ldlt.d()) * &ldlt.l().transpose();
+        assert_close(&pap, &ldl, 1e-12);
+    }
+
+    #[test]
+    fn ldlt_definite() {
+        let a = Matrix::from([[4., 12., -16.], [12., 37., -43.], [-16., -43., 98.]]);
+        let ldlt = a.ldlt().unwrap();
+        check(&a, &ldlt);
+        assert!((ldlt.determinant() - 36.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ldlt_indefinite() {
+        // Zero diagonal forces a 2x2 pivot.
+        let a = Matrix::from([[0., 1., 2.], [1., 0., 3.], [2., 3., 0.]]);
+        let ldlt = a.ldlt().unwrap();
+        check(&a, &ldlt);
+        assert!(ldlt.blocks.contains(&2));
+        assert!((ldlt.determinant() - a.determinant()).abs() < 1e-12);
+        assert!(!ldlt.is_singular());
+
+        let b = Vector::from([1., 2., 3.]);
+        let x = ldlt.solve(&b).unwrap();
+        for (e, x) in b.e.iter().zip(&(&a * &x).e) {
+            assert!((e - x).abs() < 1e-12);
+        }
+        assert_close(
+            &Matrix::identity(3),
+            &(&a * &ldlt.inverse().unwrap()),
+            1e-12,
+        );
+
+        let a = Matrix::from([
+            [1., 2., 3., 4.],
+            [2., -3., 0., 1.],
+            [3., 0., 0., -2.],
+            [4., 1., -2., 5.],
+        ]);
+        let ldlt = a.ldlt().unwrap();
+        check(&a, &ldlt);
+        let x = ldlt.solve_matrix(&Matrix::identity(4)).unwrap();
+        assert_close(&Matrix::identity(4), &(&a * &x), 1e-12);
+    }
+
+    #[test]
+    fn ldlt_singular() {
+        let a = Matrix::from([[1., 1.], [1., 1.]]);
+        let ldlt = a.ldlt().unwrap();
+        check(&a, &ldlt);
+        assert!(ldlt.is_singular());
+        assert_eq!(0., ldlt.determinant());
+        assert_eq!(
+            Err(LinalgError::Singular),
+            ldlt.solve(&Vector::from([1., 1.]))
+        );
+        assert!(Matrix::from([[1., 2.]]).ldlt().is_err());
+        assert!(a.ldlt().unwrap().solve(&Vector::from([1.])).is_err());
+        assert!(ldlt.is_singular_with(0.));
+    }
+
+    #[test]
+    fn ldlt_badly_scaled() {
+        let a = Matrix::from([[1e20, 0.], [0., 1.]]);
+        let ldlt = a.ldlt().unwrap();
+        check(&a, &ldlt);
+        assert!(!ldlt.is_singular());
+        assert_eq!(
+            Vector::from([1., 2.]),
+            ldlt.solve(&Vector::from([1e20, 2.])).unwrap()
+        );
+        assert!(ldlt.is_singular_with(2. * f64::EPSILON));
+        assert!(!ldlt.is_singular_with(1e-21));
+
+        let b = Matrix::from([[1e-20, 1.], [1., 1e-20]]);
+        let ldlt = b.ldlt().unwrap();
+        assert_eq!(vec![2], ldlt.blocks);
+        assert!(!ldlt.is_singular());
+        assert!(!ldlt.is_singular_with(2. * f64::EPSILON));
+
+        let nan = Matrix::from([[1., 0.], [0., f64::NAN]]).ldlt().unwrap();
+        assert!(nan.is_singular());
+        assert_eq!(Err(LinalgError::Singular), nan.inverse());
+    }
+}
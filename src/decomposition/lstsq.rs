@@ -0,0 +1,288 @@
+use num_traits::{Float, Zero};
+
+use crate::error::{check_dimension, LinalgError};
+use crate::matrix::Matrix;
+use crate::scalar::Field;
+use crate::vector::{self, Vector};
+
+/// Weighting and regularization for [`Matrix::lstsq_with`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LstsqOptions<R> {
+    /// Singular values up to this threshold are treated as zero; see
+    /// [`crate::Svd::rank`] for the default.
+    pub tol: Option<R>,
+    /// Non-negative weight of each equation, one per row.
+    pub weights: Option<Vec<R>>,
+    /// Non-negative Tikhonov (ridge) parameter `λ`.
+    pub ridge: R,
+}
+
+impl<R: Zero> Default for LstsqOptions<R> {
+    fn default() -> Self {
+        LstsqOptions {
+            tol: None,
+            weights: None,
+            ridge: R::zero(),
+        }
+    }
+}
+
+impl<R> LstsqOptions<R> {
+    /// Sets the rank threshold.
+    pub fn tol(mut self, tol: R) -> Self {
+        self.tol = Some(tol);
+        self
+    }
+
+    /// Sets the row weights.
+    pub fn weights(mut self, weights: Vec<R>) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    /// Sets the ridge parameter.
+    pub fn ridge(mut self, ridge: R) -> Self {
+        self.ridge = ridge;
+        self
+    }
+}
+
+/// Least-squares solution of `A x = b`, from [`Matrix::lstsq`].
+#[derive(Clone, Debug)]
+pub struct Lstsq<K: Field> {
+    solution: Vector<K>,
+    residual: K::Real,
+    rank: usize,
+    singular_values: Vec<K::Real>,
+}
+
+impl<K: Field> Matrix<K> {
+    /// Minimum-norm least-squares solution of `A x = b` by SVD, for any
+    /// shape and rank: among the `x` minimizing `|b - A x|`, the one of
+    /// least norm.
+    ///
+    /// Fails with `DimensionMismatch` unless `b` has one element per row,
+    /// or like [`Matrix::svd`].
+    pub fn lstsq(&self, b: &Vector<K>) -> Result<Lstsq<K>, LinalgError> {
+        self.lstsq_with(b, &LstsqOptions::default())
+    }
+
+    /// Minimizes `Σ w_i |b_i - (A x)_i|^2 + λ |x|^2` by SVD of `W^½ A`,
+    /// dropping singular values under the threshold. Weights default to
+    /// one and `λ` to zero, as in [`Matrix::lstsq`].
+    ///
+    /// Fails like [`Matrix::lstsq`], with `DimensionMismatch` unless there
+    /// is one weight per row, or with `InvalidArgument` if a weight or `λ`
+    /// is negative or NaN.
+    pub fn lstsq_with(
+        &self,
+        b: &Vector<K>,
+        options: &LstsqOptions<K::Real>,
+    ) -> Result<Lstsq<K>, LinalgError> {
+        let (m, n) = self.shape();
+        check_dimension(m, b.size())?;
+        let negative = |x: K::Real| x.is_nan() || x < K::Real::zero();
+        let scale: Vec<K> = match &options.weights {
+            Some(w) => {
+                check_dimension(m, w.len())?;
+                if w.iter().any(|&w| negative(w)) {
+                    return Err(LinalgError::InvalidArgument(
+                        "least-squares weights must be non-negative",
+                    ));
+                }
+                w.iter().map(|&w| K::from_real(w.sqrt())).collect()
+            }
+            None => vec![K::one(); m],
+        };
+        let ridge = options.ridge;
+        if negative(ridge) {
+            return Err(LinalgError::InvalidArgument(
+                "the ridge parameter must be non-negative",
+            ));
+        }
+
+        let a = Matrix::from_fn(m, n, |i, j| scale[i] * self[(i, j)]);
+        let b: Vec<K> = (0..m).map(|i| scale[i] * b[i]).collect();
+        let svd = a.thin_svd()?;
+        let rank = svd.rank(options.tol);
+        let (u, s, vt) = (svd.u(), svd.singular_values(), svd.vt());
+        // x = Σ σ_k / (σ_k^2 + λ) (u_k^H b) v_k
+        let mut x = vec![K::zero(); n];
+        for k in 0..rank {
+            let c = (0..m).fold(K::zero(), |acc, i| acc + u[(i, k)].conj() * b[i]);
+            let c = c * K::from_real(s[k] / (s[k] * s[k] + ridge));
+            for (j, x) in x.iter_mut().enumerate() {
+                *x += c * vt[(k, j)].conj();
+            }
+        }
+        let r: Vec<K> = (0..m)
+            .map(|i| (0..n).fold(b[i], |r, j| r - a[(i, j)] * x[j]))
+            .collect();
+        Ok(Lstsq {
+            solution: Vector::from(x),
+            residual: vector::norm_2(&r),
+            rank,
+            singular_values: s.to_vec(),
+        })
+    }
+}
+
+impl<K: Field> Lstsq<K> {
+    /// The solution `x`.
+    pub fn solution(&self) -> &Vector<K> {
+        &self.solution
+    }
+
+    /// Takes the solution `x`.
+    pub fn into_solution(self) -> Vector<K> {
+        self.solution
+    }
+
+    /// Norm of the (weighted) residual `W^½ (b - A x)`.
+    pub fn residual_norm(&self) -> K::Real {
+        self.residual
+    }
+
+    /// Number of singular values above the threshold.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Singular values of `W^½ A`, largest first.
+    pub fn singular_values(&self) -> &[K::Real] {
+        &self.singular_values
+    }
+
+    /// True when the system has full column rank, so that the solution is
+    /// the unique minimizer.
+    pub fn is_unique(&self) -> bool {
+        self.rank == self.solution.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vector(expected: &[f64], actual: &Vector<f64>, tol: f64) {
+        assert_eq!(expected.len(), actual.size());
+        for (e, a) in expected.iter().zip(&actual.e) {
+            assert!(
+                (e - a).abs() <= tol,
+                "expected {:?}, found {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn overdetermined() {
+        let a = Matrix::from([[1., 1.], [1., 2.], [1., 3.], [1., 4.]]);
+        let b = Vector::from([6., 5., 7., 10.]);
+        let fit = a.lstsq(&b).unwrap();
+        assert_vector(&[3.5, 1.4], fit.solution(), 1e-12);
+        // Residuals 1.1, -1.3, -0.7, 0.9.
+        assert!((fit.residual_norm() - 4.2f64.sqrt()).abs() < 1e-12);
+        assert_eq!(2, fit.rank());
+        assert!(fit.is_unique());
+        assert_eq!(2, fit.singular_values().len());
+        assert_vector(&[3.5, 1.4], &a.solve(&b).unwrap(), 1e-12);
+    }
+
+    #[test]
+    fn minimum_norm() {
+        // Rank deficient: x + y = 2 three times over.
+        let a = Matrix::from([[1., 1.], [1., 1.], [1., 1.]]);
+        let fit = a.lstsq(&Vector::from([2., 2., 2.])).unwrap();
+        assert_vector(&[1., 1.], fit.solution(), 1e-12);
+        assert_eq!(1, fit.rank());
+        assert!(!fit.is_unique());
+        assert!(fit.residual_norm() < 1e-12);
+
+        // Underdetermined.
+        let a = Matrix::from([[1., 1., 1.], [1., -1., 0.]]);
+        let fit = a.lstsq(&Vector::from([3., 0.])).unwrap();
+        assert_vector(&[1., 1., 1.], fit.solution(), 1e-12);
+        assert_eq!(2, fit.rank());
+
+        let zero = Matrix::<f64>::zeros(2, 2);
+        let fit = zero.lstsq(&Vector::from([3., 4.])).unwrap();
+        assert_vector(&[0., 0.], fit.solution(), 0.);
+        assert_eq!(5., fit.residual_norm());
+    }
+
+    #[test]
+    fn weighted() {
+        let a = Matrix::from([[1., 1.], [1., 2.], [1., 3.], [1., 4.]]);
+        // The outlier in the last row carries no weight.
+        let b = Vector::from([3., 5., 7., 100.]);
+        let options = LstsqOptions::default().weights(vec![1., 1., 1., 0.]);
+        let fit = a.lstsq_with(&b, &options).unwrap();
+        assert_vector(&[1., 2.], fit.solution(), 1e-12);
+        assert!(fit.residual_norm() < 1e-12);
+
+        // Integer weights count equations that many times.
+        let options = LstsqOptions::default().weights(vec![2., 1., 1., 1.]);
+        let fit = a.lstsq_with(&b, &options).unwrap();
+        let repeated = Matrix::from([[1., 1.], [1., 1.], [1., 2.], [1., 3.], [1., 4.]]);
+        let expected = repeated
+            .lstsq(&Vector::from([3., 3., 5., 7., 100.]))
+            .unwrap();
+        assert_vector(&expected.solution().e, fit.solution(), 1e-10);
+    }
+
+    #[test]
+    fn ridge() {
+        let a = Matrix::<f64>::identity(2);
+        let options = LstsqOptions::default().ridge(1.);
+        let fit = a.lstsq_with(&Vector::from([2., 4.]), &options).unwrap();
+        assert_vector(&[1., 2.], fit.solution(), 1e-12);
+
+        // Against the normal equations (A^T A + λ I) x = A^T b.
+        let a = Matrix::from([[1., 2.], [3., 4.], [5., 7.]]);
+        let b = Vector::from([1., 0., 2.]);
+        let lambda = 0.5;
+        let mut normal = &a.transpose() * &a;
+        for i in 0..2 {
+            normal[(i, i)] += lambda;
+        }
+        let expected = normal.solve(&(&a.transpose() * &b)).unwrap();
+        let fit = a
+            .lstsq_with(&b, &LstsqOptions::default().ridge(lambda))
+            .unwrap();
+        assert_vector(&expected.e, fit.solution(), 1e-12);
+    }
+
+    #[test]
+    fn lstsq_errors() {
+        let a = Matrix::from([[1., 2.], [3., 4.]]);
+        assert!(a.lstsq(&Vector::from([1.])).is_err());
+        let options = LstsqOptions::default().weights(vec![1.]);
+        assert_eq!(
+            LinalgError::DimensionMismatch {
+                expected: 2,
+                found: 1
+            },
+            a.lstsq_with(&Vector::from([1., 2.]), &options).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn invalid_options() {
+        let a = Matrix::from([[1.], [1.]]);
+        let b = Vector::from([1., 2.]);
+        for w in [-1., f64::NAN] {
+            let options = LstsqOptions::default().weights(vec![1., w]);
+            assert_eq!(
+                LinalgError::InvalidArgument("least-squares weights must be non-negative"),
+                a.lstsq_with(&b, &options).unwrap_err()
+            );
+            let options = LstsqOptions::default().ridge(w);
+            assert_eq!(
+                LinalgError::InvalidArgument("the ridge parameter must be non-negative"),
+                a.lstsq_with(&b, &options).unwrap_err()
+            );
+        }
+    }
+}
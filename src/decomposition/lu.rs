@@ -0,0 +1,276 @@
+use std::cmp::Ordering;
+
+use num_traits::{Float, Zero};
+
+use crate::error::{check_dimension, LinalgError};
+use crate::matrix::{Layout, Matrix};
+use crate::scalar::Field;
+use crate::vector::Vector;
+
+use super::max_modulus;
+
+/// LU factorization `P A = L U` with partial pivoting, from [`Matrix::lu`].
+///
+/// `L` is unit lower triangular and `U` upper triangular; both are packed
+/// into a single matrix, the unit diagonal of `L` being implicit.
+#[derive(Clone, Debug)]
+pub struct Lu<K: Field> {
+    lu: Matrix<K>,
+    perm: Vec<usize>,
+    swaps: usize,
+    /// Largest element of the factored matrix in magnitude.
+    scale: K::Real,
+}
+
+impl<K: Field> Matrix<K> {
+    /// Factors a square matrix with Gaussian elimination and partial
+    /// pivoting. Singular matrices are factored too; see
+    /// [`Lu::is_singular`].
+    pub fn lu(&self) -> Result<Lu<K>, LinalgError> {
+        self.check_square()?;
+        let n = self.rows();
+        let mut lu = self.to_layout(Layout::RowMajor);
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut swaps = 0;
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&x, &y| {
+                    let (x, y) = (lu[(x, k)].modulus(), lu[(y, k)].modulus());
+                    x.partial_cmp(&y).unwrap_or(Ordering::Equal)
+                })
+                .unwrap();
+            if p != k {
+                lu.swap_rows(p, k);
+                perm.swap(p, k);
+                swaps += 1;
+            }
+            let piv = lu[(k, k)];
+            if piv.is_zero() {
+                continue;
+            }
+            for i in k + 1..n {
+                let l = lu[(i, k)] / piv;
+                lu[(i, k)] = l;
+                for j in k + 1..n {
+                    let u = lu[(k, j)];
+                    lu[(i, j)] -= l * u;
+                }
+            }
+        }
+        Ok(Lu {
+            scale: max_modulus(self),
+            lu,
+            perm,
+            swaps,
+        })
+    }
+}
+
+impl<K: Field> Lu<K> {
+    fn size(&self) -> usize {
+        self.lu.rows()
+    }
+
+    /// The unit lower triangular factor `L`.
+    pub fn l(&self) -> Matrix<K> {
+        Matrix::from_fn(self.size(), self.size(), |i, j| match i.cmp(&j) {
+            Ordering::Greater => self.lu[(i, j)],
+            Ordering::Equal => K::one(),
+            Ordering::Less => K::zero(),
+        })
+    }
+
+    /// The upper triangular factor `U`.
+    pub fn u(&self) -> Matrix<K> {
+        Matrix::from_fn(self.size(), self.size(), |i, j| {
+            if i <= j {
+                self.lu[(i, j)]
+            } else {
+                K::zero()
+            }
+        })
+    }
+
+    /// Row `i` of `P A` is row `permutation()[i]` of `A`.
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// True when a pivot is exactly zero or not finite, which is when
+    /// [`Lu::solve`] and [`Lu::inverse`] fail. Badly scaled but regular
+    /// matrices such as `diag(1e20, 1)` are not singular.
+    pub fn is_singular(&self) -> bool {
+        (0..self.size()).any(|i| {
+            let p = self.lu[(i, i)].modulus();
+            p.is_zero() || !p.is_finite()
+        })
+    }
+
+    /// True when a pivot is at most `tol` times the largest element of the
+    /// factored matrix in magnitude, or is not finite. `n * epsilon` is the
+    /// usual choice of `tol` for a numerical singularity test.
+    pub fn is_singular_with(&self, tol: K::Real) -> bool {
+        let cutoff = tol * self.scale;
+        (0..self.size()).any(|i| {
+            let p = self.lu[(i, i)].modulus();
+            p <= cutoff || !p.is_finite()
+        })
+    }
+
+    /// Product of the pivots, negated for an odd permutation.
+    pub fn determinant(&self) -> K {
+        let det = (0..self.size()).fold(K::one(), |acc, i| acc * self.lu[(i, i)]);
+        if self.swaps.is_multiple_of(2) {
+            det
+        } else {
+            -det
+        }
+    }
+
+    /// Solves `A x = b`, failing with `DimensionMismatch` or `Singular`.
+    pub fn solve(&self, b: &Vector<K>) -> Result<Vector<K>, LinalgError> {
+        check_dimension(self.size(), b.size())?;
+        if self.is_singular() {
+            return Err(LinalgError::Singular);
+        }
+        let mut x: Vec<K> = self.perm.iter().map(|&p| b[p]).collect();
+        self.substitute(&mut x);
+        Ok(Vector::from(x))
+    }
+
+    /// Solves `A X = B` column by column, failing with `DimensionMismatch`
+    /// when `B` does not have `n` rows or with `Singular`.
+    pub fn solve_matrix(&self, b: &Matrix<K>) -> Result<Matrix<K>, LinalgError> {
+        check_dimension(self.size(), b.rows())?;
+        if self.is_singular() {
+            return Err(LinalgError::Singular);
+        }
+        let mut x = Matrix::zeros(b.rows(), b.cols());
+        let mut col = vec![K::zero(); self.size()];
+        for j in 0..b.cols() {
+            for (c, &p) in col.iter_mut().zip(&self.perm) {
+                *c = b[(p, j)];
+            }
+            self.substitute(&mut col);
+            for (i, c) in col.iter().enumerate() {
+                x[(i, j)] = *c;
+            }
+        }
+        Ok(x)
+    }
+
+    /// Inverse of the factored matrix, failing with `Singular`.
+    pub fn inverse(&self) -> Result<Matrix<K>, LinalgError> {
+        self.solve_matrix(&Matrix::identity(self.size()))
+    }
+
+    /// Overwrites the permuted right-hand side `x` with `U^-1 L^-1 x`.
+    fn substitute(&self, x: &mut [K]) {
+        let n = self.size();
+        for i in 0..n {
+            for k in 0..i {
+                let xk = x[k];
+                x[i] -= self.lu[(i, k)] * xk;
+            }
+        }
+        for i in (0..n).rev() {
+            for k in i + 1..n {
+                let xk = x[k];
+                x[i] -= self.lu[(i, k)] * xk;
+            }
+            x[i] /= self.lu[(i, i)];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::super::assert_close;
+    use super::*;
+
+    #[test]
+    fn lu_factors() {
+        let a = Matrix::from([[1., 2., 0.], [3., 4., 4.], [5., 6., 3.]]);
+        let lu = a.lu().unwrap();
+        assert_eq!(&[2, 0, 1], lu.permutation());
+        let pa = Matrix::from_fn(3, 3, |i, j| a[(lu.permutation()[i], j)]);
+        assert_close(&pa, &(&lu.l() * &lu.u()), 1e-12);
+        assert!((lu.determinant() - a.determinant()).abs() < 1e-12);
+        assert!(!lu.is_singular());
+    }
+
+    #[test]
+    fn lu_solves() {
+        let a = Matrix::from([[4., -2., 1.], [-2., 4., -2.], [1., -2., 4.]]);
+        let lu = a.lu().unwrap();
+        let x = lu.solve(&Vector::from([11., -16., 17.])).unwrap();
+        for (e, x) in [1f64, -2., 3.].iter().zip(&x.e) {
+            assert!((e - x).abs() < 1e-12);
+        }
+
+        let b = Matrix::from([[11., 4.], [-16., 0.], [17., 3.]]);
+        let x = lu.solve_matrix(&b).unwrap();
+        assert_close(&b, &(&a * &x), 1e-12);
+        assert_close(&Matrix::identity(3), &(&a * &lu.inverse().unwrap()), 1e-12);
+
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }),
+            lu.solve(&Vector::from([1., 2.]))
+        );
+    }
+
+    #[test]
+    fn lu_singular() {
+        let lu = Matrix::from([[1., 2.], [2., 4.]]).lu().unwrap();
+        assert!(lu.is_singular());
+        assert_eq!(0., lu.determinant());
+        assert_eq!(
+            Err(LinalgError::Singular),
+            lu.solve(&Vector::from([1., 2.]))
+        );
+        assert_eq!(Err(LinalgError::Singular), lu.inverse());
+        assert!(lu.is_singular_with(0.));
+
+        let lu = Matrix::<f64>::zeros(0, 0).lu().unwrap();
+        assert_eq!(1., lu.determinant());
+        assert_eq!(
+            Err(LinalgError::NotSquare { rows: 1, cols: 2 }),
+            Matrix::from([[1., 2.]]).lu().map(|_| ())
+        );
+    }
+
+    #[test]
+    fn lu_badly_scaled() {
+        let a = Matrix::from([[1e20, 0.], [0., 1.]]);
+        let lu = a.lu().unwrap();
+        assert!(!lu.is_singular());
+        assert_eq!(
+            Vector::from([1., 2.]),
+            lu.solve(&Vector::from([1e20, 2.])).unwrap()
+        );
+        assert_eq!(Matrix::from([[1e-20, 0.], [0., 1.]]), a.inverse().unwrap());
+        assert!(lu.is_singular_with(2. * f64::EPSILON));
+        assert!(!lu.is_singular_with(1e-21));
+
+        let nan = Matrix::from([[1., 0.], [0., f64::NAN]]).lu().unwrap();
+        assert!(nan.is_singular());
+        assert_eq!(Err(LinalgError::Singular), nan.inverse());
+    }
+
+    #[test]
+    fn lu_complex() {
+        let i = Complex::new(0., 1.);
+        let one = Complex::new(1., 0.);
+        let a = Matrix::from([[one, i], [i, one]]);
+        let lu = a.lu().unwrap();
+        // 1 - i^2 = 2
+        assert!((lu.determinant() - Complex::new(2., 0.)).norm() < 1e-12);
+        let x = lu.solve(&Vector::from([one + i, one + i])).unwrap();
+        assert!((x[0] - one).norm() < 1e-12 && (x[1] - one).norm() < 1e-12);
+    }
+}
@@ -0,0 +1,56 @@
+//! Matrix factorizations.
+//!
+//! Each factorization is computed once by a method on [`Matrix`] and kept in
+//! its own type, so that solving against many right-hand sides, or asking
+//! for the determinant and the inverse, reuses the same factors.
+
+use num_traits::{Float, Zero};
+
+use crate::matrix::Matrix;
+use crate::scalar::Field;
+
+mod cholesky;
+mod eigen;
+mod ldlt;
+mod lstsq;
+mod lu;
+mod qr;
+mod schur;
+mod solve;
+mod svd;
+mod symmetric_eigen;
+
+pub use cholesky::Cholesky;
+pub use eigen::Eigen;
+pub use ldlt::Ldlt;
+pub use lstsq::{Lstsq, LstsqOptions};
+pub use lu::Lu;
+pub use qr::Qr;
+pub use schur::Schur;
+pub use solve::{SolveMethod, SolveOptions};
+pub use svd::Svd;
+pub use symmetric_eigen::SymmetricEigen;
+
+/// Largest element of `a` in magnitude.
+fn max_modulus<K: Field>(a: &Matrix<K>) -> K::Real {
+    a.as_slice()
+        .iter()
+        .fold(K::Real::zero(), |m, e| m.max(e.modulus()))
+}
+
+#[cfg(test)]
+pub(crate) fn assert_close(expected: &Matrix<f64>, actual: &Matrix<f64>, tol: f64) {
+    assert_eq!(expected.shape(), actual.shape());
+    for i in 0..expected.rows() {
+        for j in 0..expected.cols() {
+            assert!(
+                (actual[(i, j)] - expected[(i, j)]).abs() <= tol,
+                "({}, {}): expected {}, found {}",
+                i,
+                j,
+                expected[(i, j)],
+                actual[(i, j)]
+            );
+        }
+    }
+}
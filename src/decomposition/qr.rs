@@ -0,0 +1,326 @@
+use std::cmp::Ordering;
+
+use num_traits::{Float, NumCast, Zero};
+
+use crate::error::{check_dimension, LinalgError};
+use crate::matrix::{Layout, Matrix};
+use crate::scalar::Field;
+use crate::vector::{self, Vector};
+
+/// QR factorization `A P = Q R` of an `m x n` matrix, from [`Matrix::qr`],
+/// [`Matrix::qr_pivoted`] or [`Matrix::qr_givens`].
+///
+/// `Q` is `m x m` unitary and `R` is `m x n` upper triangular. `P` permutes
+/// the columns and is the identity unless the factorization was pivoted.
+#[derive(Clone, Debug)]
+pub struct Qr<K> {
+    q: Matrix<K>,
+    r: Matrix<K>,
+    perm: Vec<usize>,
+}
+
+impl<K: Field> Matrix<K> {
+    /// QR factorization by Householder reflections.
+    pub fn qr(&self) -> Qr<K> {
+        Qr::householder(self, false)
+    }
+
+    /// Householder QR with column pivoting: at each step the remaining
+    /// column of largest norm is moved to the front, so the diagonal of `R`
+    /// is non-increasing in modulus and reveals the numerical rank.
+    pub fn qr_pivoted(&self) -> Qr<K> {
+        Qr::householder(self, true)
+    }
+
+    /// QR factorization by Givens rotations, one subdiagonal element at a
+    /// time. Rows can then be appended with [`Qr::push_row`].
+    pub fn qr_givens(&self) -> Qr<K> {
+        let (m, n) = self.shape();
+        let mut qr = Qr {
+            q: Matrix::identity(m),
+            r: self.to_layout(Layout::RowMajor),
+            perm: (0..n).collect(),
+        };
+        for j in 0..n.min(m) {
+            for i in j + 1..m {
+                qr.rotate(j, i);
+            }
+        }
+        qr
+    }
+}
+
+impl<K: Field> Qr<K> {
+    fn householder(a: &Matrix<K>, pivoting: bool) -> Self {
+        let (m, n) = a.shape();
+        let mut q = Matrix::identity(m);
+        let mut r = a.to_layout(Layout::RowMajor);
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut x = Vec::with_capacity(m);
+        for k in 0..n.min(m) {
+            if pivoting {
+                let p = (k..n)
+                    .map(|j| (j, column_norm(&r, k, j)))
+                    .max_by(|x, y| x.1.partial_cmp(&y.1).unwrap_or(Ordering::Equal))
+                    .map_or(k, |(j, _)| j);
+                if p != k {
+                    for i in 0..m {
+                        let t = r[(i, k)];
+                        r[(i, k)] = r[(i, p)];
+                        r[(i, p)] = t;
+                    }
+                    perm.swap(k, p);
+                }
+            }
+
+            x.clear();
+            x.extend((k..m).map(|i| r[(i, k)]));
+            let norm = vector::norm_2(&x);
+            if norm.is_zero() {
+                continue;
+            }
+            // v = x - alpha e1 with alpha = -phase(x0) * |x|, which avoids
+            // cancellation in v0.
+            let alpha = -phase(x[0]) * K::from_real(norm);
+            x[0] -= alpha;
+            let vv = vector::norm_2(&x).powi(2);
+            let two = K::from_real(<K::Real as NumCast>::from(2).unwrap() / vv);
+
+            // R <- H R, H = I - 2 v v^H / (v^H v)
+            for j in k + 1..n {
+                let s = (k..m).fold(K::zero(), |acc, i| acc + x[i - k].conj() * r[(i, j)]);
+                let s = s * two;
+                for i in k..m {
+                    r[(i, j)] -= x[i - k] * s;
+                }
+            }
+            r[(k, k)] = alpha;
+            for i in k + 1..m {
+                r[(i, k)] = K::zero();
+            }
+            // Q <- Q H
+            for i in 0..m {
+                let s = (k..m).fold(K::zero(), |acc, l| acc + q[(i, l)] * x[l - k]);
+                let s = s * two;
+                for l in k..m {
+                    q[(i, l)] -= s * x[l - k].conj();
+                }
+            }
+        }
+        Qr { q, r, perm }
+    }
+
+    /// Zeroes `R[i][j]` with a rotation of rows `j` and `i`, updating `Q`
+    /// so that `Q R` is unchanged.
+    fn rotate(&mut self, j: usize, i: usize) {
+        let (a, b) = (self.r[(j, j)], self.r[(i, j)]);
+        if b.is_zero() {
+            return;
+        }
+        let h = vector::norm_2(&[a, b]);
+        // G = [c s; -conj(s) c] with real c maps (a, b) to (phase(a) h, 0).
+        let c = K::from_real(a.modulus() / h);
+        let s = phase(a) * b.conj() / K::from_real(h);
+        for l in 0..self.r.cols() {
+            let (x, y) = (self.r[(j, l)], self.r[(i, l)]);
+            self.r[(j, l)] = c * x + s * y;
+            self.r[(i, l)] = c * y - s.conj() * x;
+        }
+        self.r[(i, j)] = K::zero();
+        // Q <- Q G^H
+        for l in 0..self.q.rows() {
+            let (x, y) = (self.q[(l, j)], self.q[(l, i)]);
+            self.q[(l, j)] = c * x + s.conj() * y;
+            self.q[(l, i)] = c * y - s * x;
+        }
+    }
+
+    /// Updates the factorization of `A` to that of `A` with `row` appended
+    /// at the bottom, using at most `n` Givens rotations.
+    ///
+    /// Fails with `DimensionMismatch` unless `row` has `n` elements.
+    pub fn push_row(&mut self, row: &Vector<K>) -> Result<(), LinalgError> {
+        let (m, n) = self.r.shape();
+        check_dimension(n, row.size())?;
+        let r = Matrix::from_fn(m + 1, n, |i, j| {
+            if i < m {
+                self.r[(i, j)]
+            } else {
+                row[self.perm[j]]
+            }
+        });
+        let q = Matrix::from_fn(m + 1, m + 1, |i, j| match (i < m, j < m) {
+            (true, true) => self.q[(i, j)],
+            (false, false) => K::one(),
+            _ => K::zero(),
+        });
+        self.r = r;
+        self.q = q;
+        for j in 0..n.min(m) {
+            self.rotate(j, m);
+        }
+        Ok(())
+    }
+
+    /// The full `m x m` unitary factor.
+    pub fn q(&self) -> &Matrix<K> {
+        &self.q
+    }
+
+    /// The full `m x n` upper triangular factor.
+    pub fn r(&self) -> &Matrix<K> {
+        &self.r
+    }
+
+    /// The first `min(m, n)` columns of `Q`.
+    pub fn thin_q(&self) -> Matrix<K> {
+        let k = self.r.rows().min(self.r.cols());
+        Matrix::from_fn(self.q.rows(), k, |i, j| self.q[(i, j)])
+    }
+
+    /// The first `min(m, n)` rows of `R`, so that `thin_q() * thin_r()` is
+    /// still `A P`.
+    pub fn thin_r(&self) -> Matrix<K> {
+        let k = self.r.rows().min(self.r.cols());
+        Matrix::from_fn(k, self.r.cols(), |i, j| self.r[(i, j)])
+    }
+
+    /// Column `j` of `A P` is column `permutation()[j]` of `A`.
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Number of diagonal elements of `R` above `max(m, n) * epsilon` times
+    /// the largest one. Only rank-revealing for [`Matrix::qr_pivoted`].
+    pub fn rank(&self) -> usize {
+        let k = self.r.rows().min(self.r.cols());
+        let diag: Vec<K::Real> = (0..k).map(|i| self.r[(i, i)].modulus()).collect();
+        let max = diag.iter().fold(K::Real::zero(), |m, &d| m.max(d));
+        let dims = <K::Real as NumCast>::from(self.r.rows().max(self.r.cols())).unwrap();
+        let tol = max * K::Real::epsilon() * dims;
+        diag.iter().filter(|&&d| d > tol).count()
+    }
+}
+
+/// `z / |z|`, or one for zero.
+fn phase<K: Field>(z: K) -> K {
+    let m = z.modulus();
+    if m.is_zero() {
+        K::one()
+    } else {
+        z / K::from_real(m)
+    }
+}
+
+/// Euclidean norm of `a[k.., j]`.
+fn column_norm<K: Field>(a: &Matrix<K>, k: usize, j: usize) -> K::Real {
+    let col: Vec<K> = (k..a.rows()).map(|i| a[(i, j)]).collect();
+    vector::norm_2(&col)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::super::assert_close;
+    use super::*;
+
+    fn permuted(a: &Matrix<f64>, perm: &[usize]) -> Matrix<f64> {
+        Matrix::from_fn(a.rows(), a.cols(), |i, j| a[(i, perm[j])])
+    }
+
+    fn check(a: &Matrix<f64>, qr: &Qr<f64>) {
+        let (m, n) = a.shape();
+        assert_close(&Matrix::identity(m), &(&qr.q().transpose() * qr.q()), 1e-12);
+        assert_close(&permuted(a, qr.permutation()), &(qr.q() * qr.r()), 1e-12);
+        for i in 0..m {
+            for j in 0..i.min(n) {
+                assert_eq!(0., qr.r()[(i, j)]);
+            }
+        }
+    }
+
+    #[test]
+    fn householder_qr() {
+        let a = Matrix::from([[12., -51., 4.], [6., 167., -68.], [-4., 24., -41.]]);
+        let qr = a.qr();
+        check(&a, &qr);
+        assert!((qr.r()[(0, 0)].abs() - 14.).abs() < 1e-12);
+        assert_eq!(3, qr.rank());
+
+        let tall = Matrix::from([[1., 2.], [3., 4.], [5., 6.], [7., 8.]]);
+        let qr = tall.qr();
+        check(&tall, &qr);
+        assert_eq!((4, 2), qr.thin_q().shape());
+        assert_eq!((2, 2), qr.thin_r().shape());
+        assert_close(&tall, &(&qr.thin_q() * &qr.thin_r()), 1e-12);
+
+        let wide = tall.transpose();
+        check(&wide, &wide.qr());
+    }
+
+    #[test]
+    fn pivoted_qr() {
+        // The third column is the sum of the first two.
+        let a = Matrix::from([[1., 0., 1.], [0., 1., 1.], [1., 1., 2.], [2., 0., 2.]]);
+        let qr = a.qr_pivoted();
+        check(&a, &qr);
+        assert_eq!(2, qr.permutation()[0]);
+        assert_eq!(2, qr.rank());
+        let diag: Vec<f64> = (0..3).map(|i| qr.r()[(i, i)].abs()).collect();
+        assert!(diag[0] >= diag[1] && diag[1] >= diag[2]);
+        assert_eq!(0, Matrix::<f64>::zeros(2, 3).qr_pivoted().rank());
+    }
+
+    #[test]
+    fn givens_qr() {
+        let a = Matrix::from([[6., 5., 0.], [5., 1., 4.], [0., 4., 3.]]);
+        let qr = a.qr_givens();
+        check(&a, &qr);
+        let householder = a.qr();
+        for i in 0..3 {
+            let (g, h) = (qr.r()[(i, i)], householder.r()[(i, i)]);
+            assert!((g.abs() - h.abs()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn givens_row_updates() {
+        let rows = [[1., 2., 3.], [4., 5., 6.], [7., 8., 10.], [1., 0., 1.]];
+        let mut qr = Matrix::from([rows[0]]).qr_givens();
+        for (k, row) in rows.iter().enumerate().skip(1) {
+            qr.push_row(&Vector::from(*row)).unwrap();
+            let a = Matrix::from_fn(k + 1, 3, |i, j| rows[i][j]);
+            check(&a, &qr);
+        }
+        assert!(qr.push_row(&Vector::from([1., 2.])).is_err());
+
+        let a = Matrix::from([[1., 2.], [3., 4.], [5., 6.]]);
+        let mut qr = a.qr_pivoted();
+        qr.push_row(&Vector::from([7., 8.])).unwrap();
+        check(&Matrix::from([[1., 2.], [3., 4.], [5., 6.], [7., 8.]]), &qr);
+    }
+
+    #[test]
+    fn complex_qr() {
+        let a = Matrix::from([
+            [Complex::new(1., 1.), Complex::new(2., 0.)],
+            [Complex::new(0., -1.), Complex::new(1., 3.)],
+            [Complex::new(2., 0.), Complex::new(0., 1.)],
+        ]);
+        for qr in [a.qr(), a.qr_givens()] {
+            let qh_q = &qr.q().conjugate_transpose() * qr.q();
+            let ident = Matrix::<Complex<f64>>::identity(3);
+            let qr_prod = qr.q() * qr.r();
+            for i in 0..3 {
+                for j in 0..2 {
+                    assert!((qr_prod[(i, j)] - a[(i, j)]).norm() < 1e-12);
+                }
+                for j in 0..3 {
+                    assert!((qh_q[(i, j)] - ident[(i, j)]).norm() < 1e-12);
+                }
+            }
+            assert_eq!(Complex::new(0., 0.), qr.r()[(1, 0)]);
+        }
+    }
+}
@@ -0,0 +1,517 @@
+use num_complex::Complex;
+use num_traits::NumCast;
+
+use crate::error::LinalgError;
+use crate::matrix::{Layout, Matrix};
+use crate::scalar::RealField;
+
+/// Francis steps allowed per eigenvalue before giving up.
+const MAX_ITERATIONS: usize = 100;
+
+/// Real Schur decomposition `A = Z T Z^T`, from [`Matrix::schur`].
+///
+/// `Z` is orthogonal and `T` quasi upper triangular: real eigenvalues sit on
+/// its diagonal and each complex conjugate pair occupies a 2x2 diagonal
+/// block.
+#[derive(Clone, Debug)]
+pub struct Schur<K> {
+    t: Matrix<K>,
+    z: Matrix<K>,
+    eigenvalues: Vec<Complex<K>>,
+}
+
+impl<K: RealField> Matrix<K> {
+    /// Real Schur form by Householder reduction to upper Hessenberg form
+    /// followed by the shifted Francis double-step QR iteration.
+    ///
+    /// Fails with `NotSquare`, or with `NoConvergence` if an eigenvalue
+    /// does not deflate within 100 iterations.
+    pub fn schur(&self) -> Result<Schur<K>, LinalgError> {
+        self.check_square()?;
+        let (mut h, mut z) = hessenberg(self);
+        let eigenvalues = francis(&mut h, &mut z)?;
+        Ok(Schur {
+            t: h,
+            z,
+            eigenvalues,
+        })
+    }
+
+    /// Eigenvalues of a general real matrix, in the order they appear on
+    /// the diagonal of its Schur form. Complex conjugate pairs are adjacent,
+    /// the one with positive imaginary part first.
+    pub fn eigenvalues(&self) -> Result<Vec<Complex<K>>, LinalgError> {
+        Ok(self.schur()?.eigenvalues)
+    }
+}
+
+impl<K: RealField> Schur<K> {
+    /// The quasi upper triangular factor `T`.
+    pub fn t(&self) -> &Matrix<K> {
+        &self.t
+    }
+
+    /// The orthogonal factor `Z`, whose columns are the Schur vectors.
+    pub fn z(&self) -> &Matrix<K> {
+        &self.z
+    }
+
+    /// Eigenvalues read off the diagonal blocks of `T`.
+    pub fn eigenvalues(&self) -> &[Complex<K>] {
+        &self.eigenvalues
+    }
+}
+
+fn lit<K: RealField>(x: f64) -> K {
+    <K as NumCast>::from(x).unwrap()
+}
+
+/// Reduces `a` to upper Hessenberg form `H = V^T A V` with Householder
+/// reflections, returning `(H, V)`.
+fn hessenberg<K: RealField>(a: &Matrix<K>) -> (Matrix<K>, Matrix<K>) {
+    let n = a.rows();
+    let mut h = a.to_layout(Layout::RowMajor);
+    let mut v = Matrix::identity(n);
+    let mut ort = vec![K::zero(); n];
+    if n < 3 {
+        return (h, v);
+    }
+    let high = n - 1;
+    for m in 1..high {
+        let scale = (m..=high).fold(K::zero(), |s, i| s + h[(i, m - 1)].abs());
+        if scale.is_zero() {
+            continue;
+        }
+        let mut sum = K::zero();
+        for i in (m..=high).rev() {
+            ort[i] = h[(i, m - 1)] / scale;
+            sum += ort[i] * ort[i];
+        }
+        let mut g = sum.sqrt();
+        if ort[m] > K::zero() {
+            g = -g;
+        }
+        let hh = sum - ort[m] * g;
+        ort[m] -= g;
+
+        // H <- (I - u u^T / hh) H (I - u u^T / hh)
+        for j in m..n {
+            let f = (m..=high)
+                .rev()
+                .fold(K::zero(), |f, i| f + ort[i] * h[(i, j)])
+                / hh;
+            for i in m..=high {
+                h[(i, j)] -= f * ort[i];
+            }
+        }
+        for i in 0..=high {
+            let f = (m..=high)
+                .rev()
+                .fold(K::zero(), |f, j| f + ort[j] * h[(i, j)])
+                / hh;
+            for j in m..=high {
+                h[(i, j)] -= f * ort[j];
+            }
+        }
+        ort[m] *= scale;
+        h[(m, m - 1)] = scale * g;
+    }
+
+    for m in (1..high).rev() {
+        if h[(m, m - 1)].is_zero() {
+            continue;
+        }
+        for i in m + 1..=high {
+            ort[i] = h[(i, m - 1)];
+        }
+        for j in m..=high {
+            let g = (m..=high).fold(K::zero(), |g, i| g + ort[i] * v[(i, j)]);
+            // Double division avoids possible underflow.
+            let g = (g / ort[m]) / h[(m, m - 1)];
+            for i in m..=high {
+                v[(i, j)] += g * ort[i];
+            }
+        }
+    }
+    for i in 2..n {
+        for j in 0..i - 1 {
+            h[(i, j)] = K::zero();
+        }
+    }
+    (h, v)
+}
+
+/// Runs the Francis double-shift QR iteration on the Hessenberg matrix `h`,
+/// leaving its real Schur form in `h` and accumulating the transformations
+/// into `v`. Returns the eigenvalues.
+fn francis<K: RealField>(
+    h: &mut Matrix<K>,
+    v: &mut Matrix<K>,
+) -> Result<Vec<Complex<K>>, LinalgError> {
+    let nn = h.rows();
+    let mut d = vec![K::zero(); nn];
+    let mut e = vec![K::zero(); nn];
+    let eps = K::epsilon();
+    let zero = K::zero();
+    let two = lit::<K>(2.);
+    let mut exshift = zero;
+    let (mut p, mut q, mut r, mut s, mut z);
+    let (mut w, mut x, mut y);
+
+    let mut norm = zero;
+    for i in 0..nn {
+        for j in i.saturating_sub(1)..nn {
+            norm += h[(i, j)].abs();
+        }
+    }
+
+    let mut n = nn as isize - 1;
+    let mut iter = 0;
+    while n >= 0 {
+        let nu = n as usize;
+        // Look for a single small subdiagonal element.
+        let mut l = nu;
+        while l > 0 {
+            s = h[(l - 1, l - 1)].abs() + h[(l, l)].abs();
+            if s.is_zero() {
+                s = norm;
+            }
+            if h[(l, l - 1)].abs() <= eps * s {
+                h[(l, l - 1)] = zero;
+                break;
+            }
+            l -= 1;
+        }
+
+        if l == nu {
+            // One root found.
+            h[(nu, nu)] += exshift;
+            d[nu] = h[(nu, nu)];
+            e[nu] = zero;
+            n -= 1;
+            iter = 0;
+        } else if l == nu - 1 {
+            // Two roots found.
+            w = h[(nu, nu - 1)] * h[(nu - 1, nu)];
+            p = (h[(nu - 1, nu - 1)] - h[(nu, nu)]) / two;
+            q = p * p + w;
+            z = q.abs().sqrt();
+            h[(nu, nu)] += exshift;
+            h[(nu - 1, nu - 1)] += exshift;
+            x = h[(nu, nu)];
+
+            if q >= zero {
+                // Real pair: rotate the block to upper triangular form.
+                z = if p >= zero { p + z } else { p - z };
+                d[nu - 1] = x + z;
+                d[nu] = d[nu - 1];
+                if !z.is_zero() {
+                    d[nu] = x - w / z;
+                }
+                e[nu - 1] = zero;
+                e[nu] = zero;
+                x = h[(nu, nu - 1)];
+                s = x.abs() + z.abs();
+                p = x / s;
+                q = z / s;
+                r = (p * p + q * q).sqrt();
+                p /= r;
+                q /= r;
+                for j in nu - 1..nn {
+                    z = h[(nu - 1, j)];
+                    h[(nu - 1, j)] = q * z + p * h[(nu, j)];
+                    h[(nu, j)] = q * h[(nu, j)] - p * z;
+                }
+                for i in 0..=nu {
+                    z = h[(i, nu - 1)];
+                    h[(i, nu - 1)] = q * z + p * h[(i, nu)];
+                    h[(i, nu)] = q * h[(i, nu)] - p * z;
+                }
+                for i in 0..nn {
+                    z = v[(i, nu - 1)];
+                    v[(i, nu - 1)] = q * z + p * v[(i, nu)];
+                    v[(i, nu)] = q * v[(i, nu)] - p * z;
+                }
+            } else {
+                // Complex pair.
+                d[nu - 1] = x + p;
+                d[nu] = x + p;
+                e[nu - 1] = z;
+                e[nu] = -z;
+            }
+            n -= 2;
+            iter = 0;
+        } else {
+            // Form the shift.
+            x = h[(nu, nu)];
+            y = h[(nu - 1, nu - 1)];
+            w = h[(nu, nu - 1)] * h[(nu - 1, nu)];
+
+            // Exceptional shifts break cycles.
+            if iter == 10 {
+                exshift += x;
+                for i in 0..=nu {
+                    h[(i, i)] -= x;
+                }
+                s = h[(nu, nu - 1)].abs() + h[(nu - 1, nu - 2)].abs();
+                x = lit::<K>(0.75) * s;
+                y = x;
+                w = lit::<K>(-0.4375) * s * s;
+            }
+            if iter == 30 {
+                s = (y - x) / two;
+                s = s * s + w;
+                if s > zero {
+                    s = s.sqrt();
+                    if y < x {
+                        s = -s;
+                    }
+                    s = x - w / ((y - x) / two + s);
+                    for i in 0..=nu {
+                        h[(i, i)] -= s;
+                    }
+                    exshift += s;
+                    x = lit::<K>(0.964);
+                    y = x;
+                    w = x;
+                }
+            }
+            iter += 1;
+            if iter > MAX_ITERATIONS {
+                return Err(LinalgError::NoConvergence { iterations: iter });
+            }
+
+            // Look for two consecutive small subdiagonal elements.
+            let mut m = nu - 2;
+            loop {
+                z = h[(m, m)];
+                r = x - z;
+                s = y - z;
+                p = (r * s - w) / h[(m + 1, m)] + h[(m, m + 1)];
+                q = h[(m + 1, m + 1)] - z - r - s;
+                r = h[(m + 2, m + 1)];
+                s = p.abs() + q.abs() + r.abs();
+                p /= s;
+                q /= s;
+                r /= s;
+                if m == l {
+                    break;
+                }
+                let lhs = h[(m, m - 1)].abs() * (q.abs() + r.abs());
+                let rhs =
+                    eps * (p.abs() * (h[(m - 1, m - 1)].abs() + z.abs() + h[(m + 1, m + 1)].abs()));
+                if lhs < rhs {
+                    break;
+                }
+                m -= 1;
+            }
+
+            for i in m + 2..=nu {
+                h[(i, i - 2)] = zero;
+                if i > m + 2 {
+                    h[(i, i - 3)] = zero;
+                }
+            }
+
+            // Double QR step on rows l..=n and columns m..=n.
+            for k in m..nu {
+                let notlast = k != nu - 1;
+                if k != m {
+                    p = h[(k, k - 1)];
+                    q = h[(k + 1, k - 1)];
+                    r = if notlast { h[(k + 2, k - 1)] } else { zero };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x.is_zero() {
+                        continue;
+                    }
+                    p /= x;
+                    q /= x;
+                    r /= x;
+                }
+                s = (p * p + q * q + r * r).sqrt();
+                if p < zero {
+                    s = -s;
+                }
+                if s.is_zero() {
+                    continue;
+                }
+                if k != m {
+                    h[(k, k - 1)] = -s * x;
+                } else if l != m {
+                    h[(k, k - 1)] = -h[(k, k - 1)];
+                }
+                p += s;
+                x = p / s;
+                y = q / s;
+                z = r / s;
+                q /= p;
+                r /= p;
+
+                for j in k..nn {
+                    p = h[(k, j)] + q * h[(k + 1, j)];
+                    if notlast {
+                        p += r * h[(k + 2, j)];
+                        h[(k + 2, j)] -= p * z;
+                    }
+                    h[(k, j)] -= p * x;
+                    h[(k + 1, j)] -= p * y;
+                }
+                for i in 0..=nu.min(k + 3) {
+                    p = x * h[(i, k)] + y * h[(i, k + 1)];
+                    if notlast {
+                        p += z * h[(i, k + 2)];
+                        h[(i, k + 2)] -= p * r;
+                    }
+                    h[(i, k)] -= p;
+                    h[(i, k + 1)] -= p * q;
+                }
+                for i in 0..nn {
+                    p = x * v[(i, k)] + y * v[(i, k + 1)];
+                    if notlast {
+                        p += z * v[(i, k + 2)];
+                        v[(i, k + 2)] -= p * r;
+                    }
+                    v[(i, k)] -= p;
+                    v[(i, k + 1)] -= p * q;
+                }
+            }
+        }
+    }
+    for i in 1..nn {
+        for j in 0..i - 1 {
+            h[(i, j)] = zero;
+        }
+        if e[i - 1].is_zero() {
+            h[(i, i - 1)] = zero;
+        }
+    }
+    Ok(d.into_iter()
+        .zip(e)
+        .map(|(re, im)| Complex::new(re, im))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::assert_close;
+    use super::*;
+
+    fn check(a: &Matrix<f64>, schur: &Schur<f64>) {
+        let n = a.rows();
+        let (t, z) = (schur.t(), schur.z());
+        assert_close(&Matrix::identity(n), &(&z.transpose() * z), 1e-12);
+        assert_close(a, &(&(z * t) * &z.transpose()), 1e-10);
+        for i in 0..n {
+            for j in 0..i.saturating_sub(1) {
+                assert_eq!(0., t[(i, j)]);
+            }
+        }
+        // Subdiagonal entries only appear in 2x2 blocks of complex pairs.
+        for i in 1..n {
+            if t[(i, i - 1)] != 0. {
+                assert!(schur.eigenvalues()[i].im != 0.);
+                assert!(i < 2 || t[(i - 1, i - 2)] == 0.);
+            }
+        }
+    }
+
+    fn sorted(mut w: Vec<Complex<f64>>) -> Vec<Complex<f64>> {
+        w.sort_by(|a, b| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im)));
+        w
+    }
+
+    fn assert_eigenvalues(expected: &[Complex<f64>], actual: Vec<Complex<f64>>) {
+        let (expected, actual) = (sorted(expected.to_vec()), sorted(actual));
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(&actual) {
+            assert!((e - a).norm() < 1e-9, "expected {}, found {}", e, a);
+        }
+    }
+
+    #[test]
+    fn real_eigenvalues() {
+        let a = Matrix::from([[2., 0., 0.], [1., 3., 0.], [4., 5., 6.]]);
+        let schur = a.schur().unwrap();
+        check(&a, &schur);
+        let real = |x| Complex::new(x, 0.);
+        assert_eigenvalues(&[real(2.), real(3.), real(6.)], a.eigenvalues().unwrap());
+
+        let a = Matrix::from([
+            [4., 1., 2., 3.],
+            [0., 3., 1., 1.],
+            [2., 1., 5., 0.],
+            [1., 0., 1., 2.],
+        ]);
+        let schur = a.schur().unwrap();
+        check(&a, &schur);
+        let trace: f64 = schur.eigenvalues().iter().map(|w| w.re).sum();
+        assert!((trace - a.trace()).abs() < 1e-10);
+        let det = schur
+            .eigenvalues()
+            .iter()
+            .fold(Complex::new(1., 0.), |acc, w| acc * w);
+        assert!((det.re - a.determinant()).abs() < 1e-9 && det.im.abs() < 1e-9);
+    }
+
+    #[test]
+    fn complex_eigenvalues() {
+        // Rotation by 90 degrees.
+        let a = Matrix::from([[0., -1.], [1., 0.]]);
+        let w = a.eigenvalues().unwrap();
+        assert_eigenvalues(&[Complex::new(0., 1.), Complex::new(0., -1.)], w.clone());
+        assert!(w[0].im > 0.);
+
+        let a = Matrix::from([
+            [1., 2., 0., 0.],
+            [-2., 1., 0., 0.],
+            [0., 0., 3., 1.],
+            [0., 0., 0., 3.],
+        ]);
+        let schur = a.schur().unwrap();
+        check(&a, &schur);
+        assert_eigenvalues(
+            &[
+                Complex::new(1., 2.),
+                Complex::new(1., -2.),
+                Complex::new(3., 0.),
+                Complex::new(3., 0.),
+            ],
+            a.eigenvalues().unwrap(),
+        );
+
+        // Companion matrix of x^3 - 1.
+        let a = Matrix::from([[0., 0., 1.], [1., 0., 0.], [0., 1., 0.]]);
+        let schur = a.schur().unwrap();
+        check(&a, &schur);
+        let h = 3f64.sqrt() / 2.;
+        assert_eigenvalues(
+            &[
+                Complex::new(1., 0.),
+                Complex::new(-0.5, h),
+                Complex::new(-0.5, -h),
+            ],
+            a.eigenvalues().unwrap(),
+        );
+    }
+
+    #[test]
+    fn schur_edge_cases() {
+        assert_eq!(
+            LinalgError::NotSquare { rows: 1, cols: 2 },
+            Matrix::from([[1., 2.]]).schur().unwrap_err()
+        );
+        assert!(Matrix::<f64>::zeros(0, 0).eigenvalues().unwrap().is_empty());
+        assert_eq!(
+            vec![Complex::new(5., 0.)],
+            Matrix::from([[5.]]).eigenvalues().unwrap()
+        );
+        let zero = Matrix::<f64>::zeros(3, 3);
+        check(&zero, &zero.schur().unwrap());
+
+        let a = Matrix::from([[1f32, 2.], [3., 4.]]);
+        let w = a.eigenvalues().unwrap();
+        let disc = 33f32.sqrt();
+        assert!((w[0].re + w[1].re - 5.).abs() < 1e-5);
+        assert!((w[0].re - w[1].re).abs() - disc < 1e-4);
+    }
+}
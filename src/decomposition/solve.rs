@@ -0,0 +1,360 @@
+use num_traits::{Float, Zero};
+
+use crate::error::{check_dimension, LinalgError};
+use crate::matrix::Matrix;
+use crate::scalar::Field;
+use crate::vector::Vector;
+
+/// Factorization used by [`Matrix::solve_with`] and
+/// [`Matrix::solve_many_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SolveMethod {
+    /// Picked from the structure of the matrix: substitution for triangular
+    /// matrices, Cholesky for Hermitian ones with a positive diagonal
+    /// (falling back to LU if they turn out indefinite), LU for other
+    /// square matrices and QR for rectangular ones.
+    #[default]
+    Auto,
+    /// Forward substitution, reading only the lower triangle.
+    LowerTriangular,
+    /// Back substitution, reading only the upper triangle.
+    UpperTriangular,
+    /// See [`Matrix::cholesky`].
+    Cholesky,
+    /// See [`Matrix::lu`].
+    Lu,
+    /// Householder QR: the least-squares solution of an overdetermined
+    /// system, or the minimum-norm one of an underdetermined system. Fails
+    /// with `Singular` only when `R` has an exactly zero diagonal element;
+    /// see [`Matrix::lstsq`] for numerically rank-deficient matrices.
+    Qr,
+}
+
+/// Options for [`Matrix::solve_with`] and [`Matrix::solve_many_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolveOptions {
+    pub method: SolveMethod,
+}
+
+impl SolveOptions {
+    /// Options forcing `method`.
+    pub fn method(method: SolveMethod) -> Self {
+        SolveOptions { method }
+    }
+}
+
+impl<K: Field> Matrix<K> {
+    /// Solves `A x = b` with [`SolveMethod::Auto`].
+    ///
+    /// Fails with `DimensionMismatch` unless `b` has one element per row,
+    /// or with `Singular`.
+    pub fn solve(&self, b: &Vector<K>) -> Result<Vector<K>, LinalgError> {
+        self.solve_with(b, SolveOptions::default())
+    }
+
+    /// Solves `A X = B` for all columns of `B` with [`SolveMethod::Auto`],
+    /// factoring `A` once.
+    pub fn solve_many(&self, b: &Matrix<K>) -> Result<Matrix<K>, LinalgError> {
+        self.solve_many_with(b, SolveOptions::default())
+    }
+
+    /// Solves `A x = b` with the method from `options`. Besides the errors
+    /// of [`Matrix::solve`], a forced method fails like the factorization
+    /// it runs.
+    pub fn solve_with(
+        &self,
+        b: &Vector<K>,
+        options: SolveOptions,
+    ) -> Result<Vector<K>, LinalgError> {
+        check_dimension(self.rows(), b.size())?;
+        let x = self.solve_many_with(&Matrix::from_fn(b.size(), 1, |i, _| b[i]), options)?;
+        Ok(Vector::from(
+            (0..x.rows()).map(|i| x[(i, 0)]).collect::<Vec<_>>(),
+        ))
+    }
+
+    /// Solves `A X = B` with the method from `options`.
+    pub fn solve_many_with(
+        &self,
+        b: &Matrix<K>,
+        options: SolveOptions,
+    ) -> Result<Matrix<K>, LinalgError> {
+        check_dimension(self.rows(), b.rows())?;
+        match options.method {
+            SolveMethod::Auto => match self.solve_method() {
+                SolveMethod::Cholesky => match self.cholesky() {
+                    Ok(chol) => chol.solve_matrix(b),
+                    Err(LinalgError::NotPositiveDefinite { .. }) => self.lu()?.solve_matrix(b),
+                    Err(e) => Err(e),
+                },
+                method => self.solve_many_with(b, SolveOptions::method(method)),
+            },
+            SolveMethod::LowerTriangular => self.solve_triangular(b, true),
+            SolveMethod::UpperTriangular => self.solve_triangular(b, false),
+            SolveMethod::Cholesky => self.cholesky()?.solve_matrix(b),
+            SolveMethod::Lu => self.lu()?.solve_matrix(b),
+            SolveMethod::Qr => self.solve_qr(b),
+        }
+    }
+
+    /// The method [`SolveMethod::Auto`] stands for with this matrix.
+    fn solve_method(&self) -> SolveMethod {
+        let (m, n) = self.shape();
+        if m != n {
+            return SolveMethod::Qr;
+        }
+        let zero_where = |f: fn(usize, usize) -> bool| {
+            (0..n).all(|i| (0..n).all(|j| !f(i, j) || self[(i, j)].is_zero()))
+        };
+        if zero_where(|i, j| i > j) {
+            SolveMethod::UpperTriangular
+        } else if zero_where(|i, j| i < j) {
+            SolveMethod::LowerTriangular
+        } else if (0..n).all(|i| {
+            let d = self[(i, i)];
+            d == K::from_real(d.re())
+                && d.re() > K::Real::zero()
+                && (0..i).all(|j| self[(i, j)] == self[(j, i)].conj())
+        }) {
+            SolveMethod::Cholesky
+        } else {
+            SolveMethod::Lu
+        }
+    }
+
+    fn solve_triangular(&self, b: &Matrix<K>, lower: bool) -> Result<Matrix<K>, LinalgError> {
+        self.check_square()?;
+        if (0..self.rows()).any(|i| self[(i, i)].is_zero()) {
+            return Err(LinalgError::Singular);
+        }
+        let mut x = b.clone();
+        substitute(self, &mut x, lower);
+        Ok(x)
+    }
+
+    fn solve_qr(&self, b: &Matrix<K>) -> Result<Matrix<K>, LinalgError> {
+        let (m, n) = self.shape();
+        if m >= n {
+            // x = R^-1 Q^H b, keeping the first n rows.
+            let qr = self.qr();
+            let r = full_rank(qr.thin_r())?;
+            let mut x = &qr.thin_q().conjugate_transpose() * b;
+            substitute(&r, &mut x, false);
+            Ok(x)
+        } else {
+            // A^H = Q R, so A x = R^H Q^H x = b and x = Q R^-H b is the
+            // solution in the row space of A.
+            let qr = self.conjugate_transpose().qr();
+            let r = full_rank(qr.thin_r())?;
+            let mut y = b.clone();
+            substitute(&r.conjugate_transpose(), &mut y, true);
+            Ok(&qr.thin_q() * &y)
+        }
+    }
+}
+
+/// Passes the square factor `r` on, or fails with `Singular` when a diagonal
+/// element is exactly zero or not finite. Scaling alone does not make `r`
+/// singular, as with [`crate::decomposition::Lu::is_singular`].
+fn full_rank<K: Field>(r: Matrix<K>) -> Result<Matrix<K>, LinalgError> {
+    let singular = (0..r.rows()).any(|i| {
+        let d = r[(i, i)].modulus();
+        d.is_zero() || !d.is_finite()
+    });
+    if singular {
+        Err(LinalgError::Singular)
+    } else {
+        Ok(r)
+    }
+}
+
+/// Overwrites `x` with `T^-1 x`, for `T` the lower or upper triangle of the
+/// square matrix `t`.
+fn substitute<K: Field>(t: &Matrix<K>, x: &mut Matrix<K>, lower: bool) {
+    let n = t.rows();
+    for j in 0..x.cols() {
+        for step in 0..n {
+            let i = if lower { step } else { n - 1 - step };
+            let known = if lower { 0..i } else { i + 1..n };
+            let s = known.fold(x[(i, j)], |s, k| s - t[(i, k)] * x[(k, j)]);
+            x[(i, j)] = s / t[(i, i)];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::assert_close;
+    use super::*;
+
+    fn check(a: &Matrix<f64>, x: &Vector<f64>, b: &Vector<f64>) {
+        let ax = a * x;
+        for (e, f) in b.e.iter().zip(&ax.e) {
+            assert!((e - f).abs() < 1e-12, "expected {:?}, found {:?}", b, ax);
+        }
+    }
+
+    #[test]
+    fn automatic_method() {
+        let b = Vector::from([1., 2., 3.]);
+        let cases = [
+            (
+                Matrix::from([[2., 1., 1.], [0., 3., 1.], [0., 0., 4.]]),
+                SolveMethod::UpperTriangular,
+            ),
+            (
+                Matrix::from([[2., 0., 0.], [1., 3., 0.], [1., 1., 4.]]),
+                SolveMethod::LowerTriangular,
+            ),
+            (
+                Matrix::from([[4., 12., -16.], [12., 37., -43.], [-16., -43., 98.]]),
+                SolveMethod::Cholesky,
+            ),
+            (
+                Matrix::from([[1., 2., 3.], [2., 1., 0.], [3., 0., 1.]]),
+                SolveMethod::Cholesky,
+            ),
+            (
+                Matrix::from([[0., 1., 2.], [1., 1., 1.], [4., 0., 1.]]),
+                SolveMethod::Lu,
+            ),
+        ];
+        for (a, method) in &cases {
+            assert_eq!(*method, a.solve_method());
+            check(a, &a.solve(&b).unwrap(), &b);
+        }
+        let rect = Matrix::from([[1., 2.], [3., 4.], [5., 6.]]);
+        assert_eq!(SolveMethod::Qr, rect.solve_method());
+    }
+
+    #[test]
+    fn rectangular_systems() {
+        // Overdetermined: the residual is orthogonal to the columns.
+        let a = Matrix::from([[1., 1.], [1., 2.], [1., 3.], [1., 4.]]);
+        let b = Vector::from([6f64, 5., 7., 10.]);
+        let x = a.solve(&b).unwrap();
+        assert!((x[0] - 3.5).abs() < 1e-12 && (x[1] - 1.4).abs() < 1e-12);
+        let mut r = &a * &x;
+        r.sub_mut(&b);
+        for j in 0..2 {
+            let dot: f64 = (0..4).map(|i| a[(i, j)] * r[i]).sum();
+            assert!(dot.abs() < 1e-12);
+        }
+
+        // Underdetermined: the minimum-norm solution of x + y + z = 3.
+        let a = Matrix::from([[1., 1., 1.]]);
+        let b = Vector::from([3.]);
+        let x = a.solve(&b).unwrap();
+        check(&a, &x, &b);
+        for xi in &x.e {
+            assert!((xi - 1.).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn solve_many_columns() {
+        let a = Matrix::from([[4., 7.], [2., 6.]]);
+        let x = a.solve_many(&Matrix::identity(2)).unwrap();
+        assert_close(&a.inverse().unwrap(), &x, 1e-12);
+
+        let a = Matrix::from([[1., 0.], [0., 1.], [1., 1.]]);
+        let b = Matrix::from([[1., 2.], [1., 4.], [2., 6.]]);
+        assert_close(
+            &Matrix::from([[1., 2.], [1., 4.]]),
+            &a.solve_many(&b).unwrap(),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn forced_methods() {
+        let spd = Matrix::from([[4., 2.], [2., 3.]]);
+        let b = Vector::from([2., 1.]);
+        for method in [SolveMethod::Cholesky, SolveMethod::Lu, SolveMethod::Qr] {
+            check(
+                &spd,
+                &spd.solve_with(&b, SolveOptions::method(method)).unwrap(),
+                &b,
+            );
+        }
+
+        // Only the requested triangle is read.
+        let x = spd
+            .solve_with(&b, SolveOptions::method(SolveMethod::LowerTriangular))
+            .unwrap();
+        check(&Matrix::from([[4., 0.], [2., 3.]]), &x, &b);
+        let x = spd
+            .solve_with(&b, SolveOptions::method(SolveMethod::UpperTriangular))
+            .unwrap();
+        check(&Matrix::from([[4., 2.], [0., 3.]]), &x, &b);
+
+        let indefinite = Matrix::from([[1., 2.], [2., 1.]]);
+        assert_eq!(
+            Err(LinalgError::NotPositiveDefinite { pivot: 1 }),
+            indefinite.solve_with(&b, SolveOptions::method(SolveMethod::Cholesky))
+        );
+        let rect = Matrix::from([[1., 2., 3.]]);
+        assert_eq!(
+            Err(LinalgError::NotSquare { rows: 1, cols: 3 }),
+            rect.solve_with(&Vector::from([1.]), SolveOptions::method(SolveMethod::Lu))
+        );
+    }
+
+    #[test]
+    fn solve_errors() {
+        let a = Matrix::from([[1., 2.], [3., 4.]]);
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 2,
+                found: 3
+            }),
+            a.solve(&Vector::from([1., 2., 3.]))
+        );
+        assert!(a.solve_many(&Matrix::identity(3)).is_err());
+        let b = Vector::from([1., 2.]);
+        for singular in [
+            Matrix::from([[1., 2.], [2., 4.]]),
+            Matrix::from([[1., 2.], [0., 0.]]),
+            Matrix::from([[1., 0.], [1., 0.]]),
+        ] {
+            assert_eq!(Err(LinalgError::Singular), singular.solve(&b));
+        }
+
+        // Scaling alone does not make a triangular matrix singular.
+        let scaled = Matrix::from([[1e20, 0.], [0., 1.]]);
+        assert_eq!(
+            Vector::from([1e-20, 2.]),
+            scaled.solve(&Vector::from([1., 2.])).unwrap()
+        );
+
+        // Nor does it make QR reject a full-rank system.
+        let tall = Matrix::from([[1e20, 0.], [0., 1.], [0., 0.]]);
+        assert_eq!(
+            Vector::from([1e-20, 2.]),
+            tall.solve(&Vector::from([1., 2., 0.])).unwrap()
+        );
+        assert_eq!(
+            Vector::from([1e-20, 2.]),
+            scaled
+                .solve_with(
+                    &Vector::from([1., 2.]),
+                    SolveOptions::method(SolveMethod::Qr)
+                )
+                .unwrap()
+        );
+        assert_eq!(
+            Vector::from([1e-20, 2., 0.]),
+            tall.transpose().solve(&Vector::from([1., 2.])).unwrap()
+        );
+
+        let rank_deficient = Matrix::from([[1., 0.], [2., 0.], [3., 0.]]);
+        assert_eq!(
+            Err(LinalgError::Singular),
+            rank_deficient.solve(&Vector::from([1., 2., 3.]))
+        );
+        assert!(rank_deficient.transpose().solve(&b).is_err());
+        // Rounding leaves a tiny nonzero diagonal in R; only the rank sees it.
+        let dependent = Matrix::from([[1., 2.], [2., 4.], [3., 6.]]);
+        assert_eq!(1, dependent.qr_pivoted().rank());
+    }
+}
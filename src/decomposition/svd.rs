@@ -0,0 +1,406 @@
+use std::cmp::Ordering;
+
+use num_traits::{Float, NumCast, One, Zero};
+
+use crate::error::LinalgError;
+use crate::matrix::Matrix;
+use crate::scalar::Field;
+use crate::vector;
+
+/// Sweeps after which the one-sided Jacobi iteration gives up.
+const MAX_SWEEPS: usize = 64;
+
+/// Singular value decomposition `A = U Σ V^H` of an `m x n` matrix, from
+/// [`Matrix::svd`] or [`Matrix::thin_svd`].
+///
+/// The full variant has a unitary `m x m` `U` and `n x n` `V`; the thin one
+/// keeps only the first `k = min(m, n)` columns of each. Singular values
+/// are sorted in decreasing order.
+#[derive(Clone, Debug)]
+pub struct Svd<K: Field> {
+    u: Matrix<K>,
+    s: Vec<K::Real>,
+    vt: Matrix<K>,
+}
+
+impl<K: Field> Matrix<K> {
+    /// Full SVD by one-sided Jacobi rotations, which also resolves small
+    /// singular values to high relative accuracy.
+    ///
+    /// Fails with `NoConvergence` if the columns are not orthogonal after 64
+    /// sweeps.
+    pub fn svd(&self) -> Result<Svd<K>, LinalgError> {
+        Svd::compute(self, true)
+    }
+
+    /// Thin SVD, with `U` of size `m x k` and `V^H` of size `k x n`.
+    pub fn thin_svd(&self) -> Result<Svd<K>, LinalgError> {
+        Svd::compute(self, false)
+    }
+
+    /// Moore-Penrose pseudoinverse; see [`Svd::pinv`].
+    pub fn pinv(&self, tol: Option<K::Real>) -> Result<Matrix<K>, LinalgError> {
+        Ok(self.thin_svd()?.pinv(tol))
+    }
+
+    /// Number of singular values above `tol`; see [`Svd::rank`]. Unlike
+    /// [`Matrix::rank`], this tolerates rounding errors in the entries.
+    pub fn numerical_rank(&self, tol: Option<K::Real>) -> Result<usize, LinalgError> {
+        Ok(self.thin_svd()?.rank(tol))
+    }
+
+    /// 2-norm condition number; see [`Svd::cond`].
+    pub fn cond(&self) -> Result<K::Real, LinalgError> {
+        Ok(self.thin_svd()?.cond())
+    }
+
+    /// Spectral norm, the largest singular value.
+    pub fn norm_2(&self) -> Result<K::Real, LinalgError> {
+        Ok(self.thin_svd()?.norm_2())
+    }
+}
+
+impl<K: Field> Svd<K> {
+    fn compute(a: &Matrix<K>, full: bool) -> Result<Self, LinalgError> {
+        if a.rows() >= a.cols() {
+            jacobi(a, full)
+        } else {
+            // A^H = U' Σ V'^H, so A = V' Σ U'^H.
+            let svd = jacobi(&a.conjugate_transpose(), full)?;
+            Ok(Svd {
+                u: svd.vt.conjugate_transpose(),
+                s: svd.s,
+                vt: svd.u.conjugate_transpose(),
+            })
+        }
+    }
+
+    /// The left singular vectors as columns.
+    pub fn u(&self) -> &Matrix<K> {
+        &self.u
+    }
+
+    /// The `min(m, n)` singular values, largest first.
+    pub fn singular_values(&self) -> &[K::Real] {
+        &self.s
+    }
+
+    /// `Σ` as a matrix shaped to fit between [`Svd::u`] and [`Svd::vt`].
+    pub fn sigma(&self) -> Matrix<K> {
+        Matrix::from_fn(self.u.cols(), self.vt.rows(), |i, j| {
+            if i == j {
+                K::from_real(self.s[i])
+            } else {
+                K::zero()
+            }
+        })
+    }
+
+    /// The right singular vectors as rows.
+    pub fn vt(&self) -> &Matrix<K> {
+        &self.vt
+    }
+
+    /// Threshold below which a singular value counts as zero. Defaults to
+    /// `max(m, n) * epsilon` times the largest singular value.
+    fn threshold(&self, tol: Option<K::Real>) -> K::Real {
+        tol.unwrap_or_else(|| {
+            let n = self.u.rows().max(self.vt.cols());
+            <K::Real as NumCast>::from(n).unwrap() * K::Real::epsilon() * self.norm_2()
+        })
+    }
+
+    /// Number of singular values greater than `tol`, or than the default
+    /// threshold `max(m, n) * epsilon * σ_max` when `tol` is `None`.
+    pub fn rank(&self, tol: Option<K::Real>) -> usize {
+        let tol = self.threshold(tol);
+        self.s.iter().take_while(|&&s| s > tol).count()
+    }
+
+    /// Moore-Penrose pseudoinverse `V Σ^+ U^H`, inverting the singular
+    /// values above `tol` (defaulting as in [`Svd::rank`]) and dropping the
+    /// others.
+    pub fn pinv(&self, tol: Option<K::Real>) -> Matrix<K> {
+        let r = self.rank(tol);
+        let (m, n) = (self.u.rows(), self.vt.cols());
+        Matrix::from_fn(n, m, |i, j| {
+            (0..r).fold(K::zero(), |acc, k| {
+                acc + self.vt[(k, i)].conj() * self.u[(j, k)].conj() / K::from_real(self.s[k])
+            })
+        })
+    }
+
+    /// Ratio of the largest to the smallest singular value; infinite when
+    /// the matrix is rank deficient.
+    pub fn cond(&self) -> K::Real {
+        match (self.s.first(), self.s.last()) {
+            (Some(&max), Some(&min)) if !min.is_zero() => max / min,
+            (Some(_), Some(_)) => K::Real::infinity(),
+            _ => K::Real::zero(),
+        }
+    }
+
+    /// Spectral norm, the largest singular value.
+    pub fn norm_2(&self) -> K::Real {
+        self.s.first().copied().unwrap_or_else(K::Real::zero)
+    }
+}
+
+/// One-sided (Hestenes) Jacobi SVD of a matrix with `m >= n`: rotates the
+/// columns of `A V` until they are mutually orthogonal.
+fn jacobi<K: Field>(a: &Matrix<K>, full: bool) -> Result<Svd<K>, LinalgError> {
+    let (m, n) = a.shape();
+    let mut g: Vec<Vec<K>> = (0..n)
+        .map(|j| (0..m).map(|i| a[(i, j)]).collect())
+        .collect();
+    let mut v: Vec<Vec<K>> = (0..n)
+        .map(|j| {
+            (0..n)
+                .map(|i| if i == j { K::one() } else { K::zero() })
+                .collect()
+        })
+        .collect();
+    let eps = K::Real::epsilon();
+    let mut sweeps = 0;
+    loop {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                rotated |= rotate(&mut g, &mut v, p, q, eps);
+            }
+        }
+        if !rotated {
+            break;
+        }
+        sweeps += 1;
+        if sweeps == MAX_SWEEPS {
+            return Err(LinalgError::NoConvergence { iterations: sweeps });
+        }
+    }
+
+    let s: Vec<K::Real> = g.iter().map(|c| vector::norm_2(c)).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&x, &y| s[y].partial_cmp(&s[x]).unwrap_or(Ordering::Equal));
+    let mut u: Vec<Option<Vec<K>>> = order
+        .iter()
+        .map(|&j| {
+            (!s[j].is_zero()).then(|| {
+                let d = K::from_real(s[j]);
+                g[j].iter().map(|&x| x / d).collect()
+            })
+        })
+        .collect();
+    if full {
+        u.resize(m, None);
+    }
+    let u = complete(u, m);
+    let cols = u.len();
+    Ok(Svd {
+        u: Matrix::from_fn(m, cols, |i, j| u[j][i]),
+        s: order.iter().map(|&j| s[j]).collect(),
+        vt: Matrix::from_fn(n, n, |i, j| v[order[i]][j].conj()),
+    })
+}
+
+/// Applies the rotation that makes columns `p` and `q` of `g` orthogonal,
+/// and accumulates it into `v`. Returns false when they already are, to
+/// working precision.
+fn rotate<K: Field>(g: &mut [Vec<K>], v: &mut [Vec<K>], p: usize, q: usize, eps: K::Real) -> bool {
+    let alpha = g[p]
+        .iter()
+        .fold(K::Real::zero(), |s, x| s + x.modulus_squared());
+    let beta = g[q]
+        .iter()
+        .fold(K::Real::zero(), |s, x| s + x.modulus_squared());
+    let gamma = g[p]
+        .iter()
+        .zip(&g[q])
+        .fold(K::zero(), |s, (&x, &y)| s + x.conj() * y);
+    let m = gamma.modulus();
+    if m.is_zero() || m <= eps * (alpha * beta).sqrt() {
+        return false;
+    }
+    // Same rotation as the Hermitian Jacobi eigensolver, applied to the
+    // Gram matrix [[alpha, gamma], [conj(gamma), beta]].
+    let one = K::Real::one();
+    let e = gamma / K::from_real(m);
+    let theta = (beta - alpha) / (m + m);
+    let t = if theta.is_zero() {
+        one
+    } else {
+        one.copysign(theta) / (theta.abs() + (one + theta * theta).sqrt())
+    };
+    let c = one / (one + t * t).sqrt();
+    let s = t * c;
+    let (jpp, jpq) = (K::from_real(c), K::from_real(s));
+    let (jqp, jqq) = (-K::from_real(s) * e.conj(), K::from_real(c) * e.conj());
+    for cols in [g, v] {
+        for k in 0..cols[p].len() {
+            let (x, y) = (cols[p][k], cols[q][k]);
+            cols[p][k] = x * jpp + y * jqp;
+            cols[q][k] = x * jpq + y * jqq;
+        }
+    }
+    true
+}
+
+/// Fills the missing columns of `u` with unit vectors of length `m`
+/// orthogonal to all others, taking at each step the standard basis vector
+/// that keeps most of its length once projected off them.
+fn complete<K: Field>(mut u: Vec<Option<Vec<K>>>, m: usize) -> Vec<Vec<K>> {
+    for j in 0..u.len() {
+        if u[j].is_some() {
+            continue;
+        }
+        let basis: Vec<&Vec<K>> = u.iter().flatten().collect();
+        let best = (0..m)
+            .map(|k| {
+                let mut x = vec![K::zero(); m];
+                x[k] = K::one();
+                // Projecting twice keeps the result orthogonal to working
+                // precision.
+                for _ in 0..2 {
+                    for b in &basis {
+                        let d = b
+                            .iter()
+                            .zip(&x)
+                            .fold(K::zero(), |s, (&b, &x)| s + b.conj() * x);
+                        for (x, &b) in x.iter_mut().zip(b.iter()) {
+                            *x -= d * b;
+                        }
+                    }
+                }
+                (vector::norm_2(&x), x)
+            })
+            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal))
+            .map(|(norm, x)| {
+                let d = K::from_real(norm);
+                x.into_iter().map(|x| x / d).collect()
+            });
+        u[j] = best;
+    }
+    u.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::super::assert_close;
+    use super::*;
+
+    fn check(a: &Matrix<f64>, svd: &Svd<f64>) {
+        let (u, vt) = (svd.u(), svd.vt());
+        assert_close(&Matrix::identity(u.cols()), &(&u.transpose() * u), 1e-12);
+        assert_close(&Matrix::identity(vt.rows()), &(vt * &vt.transpose()), 1e-12);
+        assert_close(a, &(&(u * &svd.sigma()) * vt), 1e-12);
+        let s = svd.singular_values();
+        assert!(s.windows(2).all(|p| p[0] >= p[1]));
+        assert!(s.iter().all(|&s| s >= 0.));
+    }
+
+    #[test]
+    fn svd_shapes() {
+        let a = Matrix::from([[3., 2., 2.], [2., 3., -2.]]);
+        let svd = a.svd().unwrap();
+        check(&a, &svd);
+        assert_eq!((2, 2), svd.u().shape());
+        assert_eq!((3, 3), svd.vt().shape());
+        let s = svd.singular_values();
+        assert!((s[0] - 5.).abs() < 1e-12 && (s[1] - 3.).abs() < 1e-12);
+
+        let thin = a.thin_svd().unwrap();
+        check(&a, &thin);
+        assert_eq!((2, 2), thin.u().shape());
+        assert_eq!((2, 3), thin.vt().shape());
+
+        let a = a.transpose();
+        let svd = a.svd().unwrap();
+        check(&a, &svd);
+        assert_eq!((3, 3), svd.u().shape());
+        let thin = a.thin_svd().unwrap();
+        check(&a, &thin);
+        assert_eq!((3, 2), thin.u().shape());
+        assert_eq!((2, 2), thin.sigma().shape());
+    }
+
+    #[test]
+    fn svd_rank_deficient() {
+        let a = Matrix::from([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.], [2., 4., 6.]]);
+        let svd = a.svd().unwrap();
+        check(&a, &svd);
+        assert_eq!(2, svd.rank(None));
+        assert_eq!(1, svd.rank(Some(3.)));
+        assert_eq!(
+            f64::INFINITY,
+            Matrix::from([[1., 1.], [1., 1.]]).cond().unwrap()
+        );
+
+        let zero = Matrix::<f64>::zeros(2, 3);
+        let svd = zero.svd().unwrap();
+        check(&zero, &svd);
+        assert_eq!(0, svd.rank(None));
+        assert_close(&Matrix::zeros(3, 2), &svd.pinv(None), 0.);
+    }
+
+    #[test]
+    fn pseudoinverse() {
+        let a = Matrix::from([[1., 2.], [3., 4.], [5., 6.]]);
+        let pinv = a.pinv(None).unwrap();
+        // Penrose conditions.
+        assert_close(&a, &(&(&a * &pinv) * &a), 1e-12);
+        assert_close(&pinv, &(&(&pinv * &a) * &pinv), 1e-12);
+        let ap = &a * &pinv;
+        assert_close(&ap.transpose(), &ap, 1e-12);
+        assert_close(&Matrix::identity(2), &(&pinv * &a), 1e-12);
+
+        let square = Matrix::from([[4., 7.], [2., 6.]]);
+        assert_close(
+            &square.inverse().unwrap(),
+            &square.pinv(None).unwrap(),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn norms_and_condition() {
+        let a = Matrix::from([[2f64, 0.], [0., -0.5]]);
+        assert!((a.norm_2().unwrap() - 2.).abs() < 1e-15);
+        assert!((a.cond().unwrap() - 4.).abs() < 1e-14);
+        assert_eq!(0., Matrix::<f64>::zeros(0, 3).norm_2().unwrap());
+
+        // Hilbert matrix of order 4.
+        let h = Matrix::from_fn(4, 4, |i, j| 1. / (i + j + 1) as f64);
+        let cond = h.cond().unwrap();
+        assert!((cond / 15_513.738_738_929_1 - 1.).abs() < 1e-9);
+        assert_eq!(4, h.numerical_rank(None).unwrap());
+    }
+
+    #[test]
+    fn complex_svd() {
+        let i = Complex::new(0., 1.);
+        let one = Complex::new(1., 0.);
+        let a = Matrix::from([[one, i], [i, one], [one, one]]);
+        let svd = a.svd().unwrap();
+        let (u, vt) = (svd.u(), svd.vt());
+        let usv = &(u * &svd.sigma()) * vt;
+        let uhu = &u.conjugate_transpose() * u;
+        for r in 0..3 {
+            for c in 0..3 {
+                let id = if r == c { one } else { Complex::new(0., 0.) };
+                assert!((uhu[(r, c)] - id).norm() < 1e-12);
+            }
+            for c in 0..2 {
+                assert!((usv[(r, c)] - a[(r, c)]).norm() < 1e-12);
+            }
+        }
+        let pinv = svd.pinv(None);
+        let pa = &pinv * &a;
+        for r in 0..2 {
+            for c in 0..2 {
+                let id = if r == c { one } else { Complex::new(0., 0.) };
+                assert!((pa[(r, c)] - id).norm() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::cmp::Ordering;
+
+use num_traits::{Float, One, Zero};
+
+use crate::error::LinalgError;
+use crate::matrix::Matrix;
+use crate::scalar::Field;
+use crate::vector;
+
+/// Sweeps after which the Jacobi iteration gives up; convergence is
+/// quadratic and takes well under ten sweeps in practice.
+const MAX_SWEEPS: usize = 64;
+
+/// Eigendecomposition `A = V diag(w) V^H` of a Hermitian (real symmetric)
+/// matrix, from [`Matrix::symmetric_eigen`].
+#[derive(Clone, Debug)]
+pub struct SymmetricEigen<K: Field> {
+    eigenvalues: Vec<K::Real>,
+    eigenvectors: Matrix<K>,
+}
+
+impl<K: Field> Matrix<K> {
+    /// Eigenvalues and eigenvectors of a Hermitian (real symmetric) matrix
+    /// by cyclic Jacobi rotations. Only the lower triangle is read.
+    ///
+    /// Fails with `NotSquare`, or with `NoConvergence` if the off-diagonal
+    /// part has not vanished after 64 sweeps, as happens with NaNs.
+    pub fn symmetric_eigen(&self) -> Result<SymmetricEigen<K>, LinalgError> {
+        self.check_square()?;
+        let n = self.rows();
+        let mut a = Matrix::from_fn(n, n, |i, j| match i.cmp(&j) {
+            Ordering::Greater => self[(i, j)],
+            Ordering::Equal => K::from_real(self[(i, i)].re()),
+            Ordering::Less => self[(j, i)].conj(),
+        });
+        let mut v = Matrix::identity(n);
+        let fro = vector::norm_2(a.as_slice());
+        let tol = fro * K::Real::epsilon();
+        let mut sweeps = 0;
+        loop {
+            // A NaN norm fails the test, so NaNs end in `NoConvergence`.
+            if off_diagonal_norm(&a) <= tol {
+                break;
+            }
+            if sweeps == MAX_SWEEPS {
+                return Err(LinalgError::NoConvergence { iterations: sweeps });
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    rotate(&mut a, &mut v, p, q);
+                }
+            }
+            sweeps += 1;
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&x, &y| {
+            a[(x, x)]
+                .re()
+                .partial_cmp(&a[(y, y)].re())
+                .unwrap_or(Ordering::Equal)
+        });
+        Ok(SymmetricEigen {
+            eigenvalues: order.iter().map(|&i| a[(i, i)].re()).collect(),
+            eigenvectors: Matrix::from_fn(n, n, |i, j| v[(i, order[j])]),
+        })
+    }
+}
+
+impl<K: Field> SymmetricEigen<K> {
+    /// Eigenvalues in ascending order.
+    pub fn eigenvalues(&self) -> &[K::Real] {
+        &self.eigenvalues
+    }
+
+    /// Orthonormal eigenvectors as columns, in the order of
+    /// [`SymmetricEigen::eigenvalues`].
+    pub fn eigenvectors(&self) -> &Matrix<K> {
+        &self.eigenvectors
+    }
+}
+
+/// Frobenius norm of the strictly upper triangle of a Hermitian matrix.
+fn off_diagonal_norm<K: Field>(a: &Matrix<K>) -> K::Real {
+    let n = a.rows();
+    let upper: Vec<K> = (0..n)
+        .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+        .map(|(i, j)| a[(i, j)])
+        .collect();
+    vector::norm_2(&upper)
+}
+
+/// Applies the Jacobi rotation `J` that zeroes `a[p][q]`: `a <- J^H a J`
+/// and `v <- v J`.
+fn rotate<K: Field>(a: &mut Matrix<K>, v: &mut Matrix<K>, p: usize, q: usize) {
+    let apq = a[(p, q)];
+    let m = apq.modulus();
+    if m.is_zero() {
+        return;
+    }
+    let (app, aqq) = (a[(p, p)].re(), a[(q, q)].re());
+    let one = K::Real::one();
+    // After scaling column q by conj(e), a[p][q] = |a[p][q]| is real and
+    // the classic real rotation applies.
+    let e = apq / K::from_real(m);
+    let theta = (aqq - app) / (m + m);
+    let t = if theta.is_zero() {
+        one
+    } else {
+        one.copysign(theta) / (theta.abs() + (one + theta * theta).sqrt())
+    };
+    let c = one / (one + t * t).sqrt();
+    let s = t * c;
+    // J = [[c, s], [-s conj(e), c conj(e)]] on rows/columns p and q.
+    let (jpp, jpq) = (K::from_real(c), K::from_real(s));
+    let (jqp, jqq) = (-K::from_real(s) * e.conj(), K::from_real(c) * e.conj());
+
+    let n = a.rows();
+    for k in 0..n {
+        let (x, y) = (a[(k, p)], a[(k, q)]);
+        a[(k, p)] = x * jpp + y * jqp;
+        a[(k, q)] = x * jpq + y * jqq;
+    }
+    for k in 0..n {
+        let (x, y) = (a[(p, k)], a[(q, k)]);
+        a[(p, k)] = jpp.conj() * x + jqp.conj() * y;
+        a[(q, k)] = jpq.conj() * x + jqq.conj() * y;
+    }
+    a[(p, q)] = K::zero();
+    a[(q, p)] = K::zero();
+    a[(p, p)] = K::from_real(app - t * m);
+    a[(q, q)] = K::from_real(aqq + t * m);
+    for k in 0..n {
+        let (x, y) = (v[(k, p)], v[(k, q)]);
+        v[(k, p)] = x * jpp + y * jqp;
+        v[(k, q)] = x * jpq + y * jqq;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::super::assert_close;
+    use super::*;
+
+    fn check(a: &Matrix<f64>, eigen: &SymmetricEigen<f64>) {
+        let n = a.rows();
+        let v = eigen.eigenvectors();
+        let w = eigen.eigenvalues();
+        assert_close(&Matrix::identity(n), &(&v.transpose() * v), 1e-12);
+        let vw = Matrix::from_fn(n, n, |i, j| v[(i, j)] * w[j]);
+        assert_close(&vw, &(a * v), 1e-10);
+        assert!(w.windows(2).all(|p| p[0] <= p[1]));
+    }
+
+    #[test]
+    fn real_symmetric() {
+        let a = Matrix::from([[2., 1.], [1., 2.]]);
+        let eigen = a.symmetric_eigen().unwrap();
+        check(&a, &eigen);
+        assert!((eigen.eigenvalues()[0] - 1.).abs() < 1e-12);
+        assert!((eigen.eigenvalues()[1] - 3.).abs() < 1e-12);
+
+        let a = Matrix::from([
+            [4., 1., -2., 2.],
+            [1., 2., 0., 1.],
+            [-2., 0., 3., -2.],
+            [2., 1., -2., -1.],
+        ]);
+        let eigen = a.symmetric_eigen().unwrap();
+        check(&a, &eigen);
+        let trace: f64 = eigen.eigenvalues().iter().sum();
+        assert!((trace - a.trace()).abs() < 1e-10);
+
+        let a = Matrix::from([[3., 0., 0.], [0., -1., 0.], [0., 0., 2.]]);
+        let eigen = a.symmetric_eigen().unwrap();
+        assert_eq!(&[-1., 2., 3.], eigen.eigenvalues());
+        check(&a, &eigen);
+    }
+
+    #[test]
+    fn graph_laplacian() {
+        // Path graph on three vertices: spectrum 0, 1, 3.
+        let a = Matrix::from([[1., -1., 0.], [-1., 2., -1.], [0., -1., 1.]]);
+        let eigen = a.symmetric_eigen().unwrap();
+        check(&a, &eigen);
+        for (w, e) in eigen.eigenvalues().iter().zip([0., 1., 3.]) {
+            assert!((w - e).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn complex_hermitian() {
+        let i = Complex::new(0., 1.);
+        let two = Complex::new(2., 0.);
+        let a = Matrix::from([[two, -i], [i, two]]);
+        let eigen = a.symmetric_eigen().unwrap();
+        assert!((eigen.eigenvalues()[0] - 1.).abs() < 1e-12);
+        assert!((eigen.eigenvalues()[1] - 3.).abs() < 1e-12);
+        let v = eigen.eigenvectors();
+        let vhv = &v.conjugate_transpose() * v;
+        let av = &a * v;
+        for r in 0..2 {
+            for c in 0..2 {
+                let expected = if r == c { 1. } else { 0. };
+                assert!((vhv[(r, c)] - Complex::new(expected, 0.)).norm() < 1e-12);
+                let vw = v[(r, c)] * eigen.eigenvalues()[c];
+                assert!((av[(r, c)] - vw).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn eigen_errors() {
+        assert_eq!(
+            LinalgError::NotSquare { rows: 1, cols: 2 },
+            Matrix::from([[1., 2.]]).symmetric_eigen().unwrap_err()
+        );
+        assert_eq!(
+            LinalgError::NoConvergence { iterations: 64 },
+            Matrix::from([[1., f64::NAN], [f64::NAN, 1.]])
+                .symmetric_eigen()
+                .unwrap_err()
+        );
+        let empty = Matrix::<f64>::zeros(0, 0).symmetric_eigen().unwrap();
+        assert!(empty.eigenvalues().is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// Error returned by the fallible (`try_*`) operations of this crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinalgError {
+    /// Two operands that must have the same length do not.
+    DimensionMismatch { expected: usize, found: usize },
+    /// Two matrix operands have incompatible `(rows, cols)` shapes.
+    ShapeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// The operation is only defined for square matrices.
+    NotSquare { rows: usize, cols: usize },
+    /// The matrix has no inverse.
+    Singular,
+    /// A Cholesky factorization found a pivot that is not positive, so the
+    /// matrix is not positive definite.
+    NotPositiveDefinite { pivot: usize },
+    /// An iterative algorithm did not converge within its iteration limit.
+    NoConvergence { iterations: usize },
+    /// An index lies outside a `(rows, cols)` matrix.
+    OutOfBounds {
+        index: (usize, usize),
+        shape: (usize, usize),
+    },
+    /// The operation needs at least one element.
+    Empty,
+    /// A row does not have as many elements as the first one.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A parameter is outside its domain, for instance negative or NaN
+    /// where a non-negative number is required.
+    InvalidArgument(&'static str),
+}
+
+impl Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinalgError::DimensionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "dimension mismatch: expected {}, found {}",
+                    expected, found
+                )
+            }
+            LinalgError::ShapeMismatch { expected, found } => write!(
+                f,
+                "shape mismatch: expected {}x{}, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            LinalgError::NotSquare { rows, cols } => {
+                write!(f, "matrix is not square ({}x{})", rows, cols)
+            }
+            LinalgError::Singular => write!(f, "matrix is singular"),
+            LinalgError::NotPositiveDefinite { pivot } => {
+                write!(f, "matrix is not positive definite (pivot {})", pivot)
+            }
+            LinalgError::NoConvergence { iterations } => {
+                write!(f, "no convergence after {} iterations", iterations)
+            }
+            LinalgError::OutOfBounds { index, shape } => write!(
+                f,
+                "index ({}, {}) out of bounds for a {}x{} matrix",
+                index.0, index.1, shape.0, shape.1
+            ),
+            LinalgError::Empty => write!(f, "empty input"),
+            LinalgError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "ragged rows: row {} has {} elements, expected {}",
+                row, found, expected
+            ),
+            LinalgError::InvalidArgument(reason) => write!(f, "invalid argument: {}", reason),
+        }
+    }
+}
+
+impl Error for LinalgError {}
+
+/// Returns `DimensionMismatch` unless `expected == found`.
+pub(crate) fn check_dimension(expected: usize, found: usize) -> Result<(), LinalgError> {
+    if expected != found {
+        return Err(LinalgError::DimensionMismatch { expected, found });
+    }
+    Ok(())
+}
+
+/// Returns `ShapeMismatch` unless `expected == found`.
+pub(crate) fn check_shape(
+    expected: (usize, usize),
+    found: (usize, usize),
+) -> Result<(), LinalgError> {
+    if expected != found {
+        return Err(LinalgError::ShapeMismatch { expected, found });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_display() {
+        assert_eq!(
+            "dimension mismatch: expected 2, found 3",
+            LinalgError::DimensionMismatch {
+                expected: 2,
+                found: 3
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "shape mismatch: expected 2x2, found 3x1",
+            LinalgError::ShapeMismatch {
+                expected: (2, 2),
+                found: (3, 1)
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "matrix is not square (2x3)",
+            LinalgError::NotSquare { rows: 2, cols: 3 }.to_string()
+        );
+        assert_eq!(
+            "matrix is not positive definite (pivot 2)",
+            LinalgError::NotPositiveDefinite { pivot: 2 }.to_string()
+        );
+        assert_eq!(
+            "no convergence after 30 iterations",
+            LinalgError::NoConvergence { iterations: 30 }.to_string()
+        );
+        assert_eq!(
+            "index (2, 0) out of bounds for a 2x2 matrix",
+            LinalgError::OutOfBounds {
+                index: (2, 0),
+                shape: (2, 2)
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "ragged rows: row 1 has 1 elements, expected 2",
+            LinalgError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "invalid argument: the ridge parameter must be non-negative",
+            LinalgError::InvalidArgument("the ridge parameter must be non-negative").to_string()
+        );
+    }
+
+    #[test]
+    fn error_checks() {
+        assert_eq!(Ok(()), check_dimension(3, 3));
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }),
+            check_dimension(3, 2)
+        );
+        assert_eq!(Ok(()), check_shape((2, 3), (2, 3)));
+        assert!(check_shape((2, 3), (3, 2)).is_err());
+    }
+}
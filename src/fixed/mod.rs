@@ -0,0 +1,504 @@
+//! Stack-allocated vectors and matrices whose dimensions are part of their
+//! type.
+//!
+//! The shape checks that [`Vector`] and [`Matrix`] perform at run time are
+//! type errors here, and nothing is heap-allocated. Both types convert to and
+//! from their dynamic counterparts.
+
+use std::fmt;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+
+use crate::error::{check_dimension, check_shape, LinalgError};
+use crate::matrix::Matrix;
+use crate::scalar::{Field, Ring, Scalar};
+use crate::vector::{self, Vector};
+
+/// A vector of `N` elements stored inline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SVector<K, const N: usize> {
+    pub e: [K; N],
+}
+
+/// A matrix of `R` rows and `C` columns stored inline, row by row.
+///
+/// Products only type-check when the inner dimensions agree:
+///
+/// ```
+/// use matrix::SMatrix;
+///
+/// let a = SMatrix::<f64, 2, 3>::zeros();
+/// let b = SMatrix::<f64, 3, 4>::zeros();
+/// let c: SMatrix<f64, 2, 4> = a * b;
+/// ```
+///
+/// ```compile_fail
+/// use matrix::SMatrix;
+///
+/// let a = SMatrix::<f64, 2, 3>::zeros();
+/// let c = a * a;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SMatrix<K, const R: usize, const C: usize> {
+    data: [[K; C]; R],
+}
+
+pub type Vector2<K> = SVector<K, 2>;
+pub type Vector3<K> = SVector<K, 3>;
+pub type Vector4<K> = SVector<K, 4>;
+
+pub type Matrix2<K> = SMatrix<K, 2, 2>;
+pub type Matrix3<K> = SMatrix<K, 3, 3>;
+pub type Matrix4<K> = SMatrix<K, 4, 4>;
+
+impl<K, const N: usize> From<[K; N]> for SVector<K, N> {
+    fn from(e: [K; N]) -> Self {
+        SVector { e }
+    }
+}
+
+impl<K, const N: usize> From<SVector<K, N>> for Vector<K> {
+    fn from(v: SVector<K, N>) -> Self {
+        Vector::from(v.e)
+    }
+}
+
+/// Fails with `DimensionMismatch` unless the vector has exactly `N`
+/// elements.
+impl<K, const N: usize> TryFrom<Vector<K>> for SVector<K, N> {
+    type Error = LinalgError;
+
+    fn try_from(v: Vector<K>) -> Result<Self, Self::Error> {
+        check_dimension(N, v.e.len())?;
+        let Ok(e) = v.e.try_into() else {
+            unreachable!("length was checked")
+        };
+        Ok(SVector { e })
+    }
+}
+
+impl<K: Debug, const N: usize> Display for SVector<K, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, e) in self.e.iter().enumerate() {
+            write!(f, "[{:?}]", e)?;
+            if i < N - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<K, const N: usize> Index<usize> for SVector<K, N> {
+    type Output = K;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.e[index]
+    }
+}
+
+impl<K, const N: usize> IndexMut<usize> for SVector<K, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.e[index]
+    }
+}
+
+impl<K: Scalar, const N: usize> AddAssign for SVector<K, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.e.iter_mut().zip(rhs.e).for_each(|(a, b)| *a += b);
+    }
+}
+
+impl<K: Scalar, const N: usize> SubAssign for SVector<K, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.e.iter_mut().zip(rhs.e).for_each(|(a, b)| *a -= b);
+    }
+}
+
+impl<K: Scalar, const N: usize> MulAssign<K> for SVector<K, N> {
+    fn mul_assign(&mut self, rhs: K) {
+        self.e.iter_mut().for_each(|a| *a *= rhs);
+    }
+}
+
+impl<K: Scalar, const N: usize> Add for SVector<K, N> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<K: Scalar, const N: usize> Sub for SVector<K, N> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<K: Scalar, const N: usize> Mul<K> for SVector<K, N> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: K) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<K: Scalar, const N: usize> SVector<K, N> {
+    pub fn zeros() -> Self {
+        SVector { e: [K::zero(); N] }
+    }
+
+    pub fn size(&self) -> usize {
+        N
+    }
+
+    pub fn as_slice(&self) -> &[K] {
+        &self.e
+    }
+
+    pub fn dot(&self, v: &Self) -> K {
+        self.e
+            .iter()
+            .zip(&v.e)
+            .fold(K::zero(), |acc, (a, b)| acc + *a * *b)
+    }
+
+    /// Hermitian inner product, see [`Vector::dotc`].
+    pub fn dotc(&self, v: &Self) -> K {
+        self.e
+            .iter()
+            .zip(&v.e)
+            .fold(K::zero(), |acc, (a, b)| acc + a.conj() * *b)
+    }
+
+    pub fn norm_1(&self) -> K::Real
+    where
+        K: Field,
+    {
+        vector::norm_1(&self.e)
+    }
+
+    /// Euclidean norm, scaled like [`Vector::norm`].
+    pub fn norm(&self) -> K::Real
+    where
+        K: Field,
+    {
+        vector::norm_2(&self.e)
+    }
+
+    pub fn norm_inf(&self) -> K::Real
+    where
+        K: Field,
+    {
+        vector::norm_inf(&self.e)
+    }
+
+    /// Panics if `p` is less than one or NaN, like [`Vector::norm_p`].
+    pub fn norm_p(&self, p: K::Real) -> K::Real
+    where
+        K: Field,
+    {
+        vector::norm_p(&self.e, p)
+    }
+}
+
+impl<K: Scalar> SVector<K, 3> {
+    /// Cross product, only defined for 3-dimensional vectors.
+    pub fn cross(&self, v: &Self) -> Self {
+        let (u, v) = (&self.e, &v.e);
+        SVector::from([
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ])
+    }
+}
+
+impl<K, const R: usize, const C: usize> From<[[K; C]; R]> for SMatrix<K, R, C> {
+    fn from(data: [[K; C]; R]) -> Self {
+        SMatrix { data }
+    }
+}
+
+impl<K: Copy, const R: usize, const C: usize> From<SMatrix<K, R, C>> for Matrix<K> {
+    fn from(m: SMatrix<K, R, C>) -> Self {
+        Matrix::from(m.data)
+    }
+}
+
+/// Fails with `ShapeMismatch` unless the matrix is `R x C`.
+impl<K: Copy, const R: usize, const C: usize> TryFrom<&Matrix<K>> for SMatrix<K, R, C> {
+    type Error = LinalgError;
+
+    fn try_from(m: &Matrix<K>) -> Result<Self, Self::Error> {
+        check_shape((R, C), m.shape())?;
+        Ok(SMatrix::from_fn(|i, j| m[(i, j)]))
+    }
+}
+
+impl<K: Copy, const R: usize, const C: usize> TryFrom<Matrix<K>> for SMatrix<K, R, C> {
+    type Error = LinalgError;
+
+    fn try_from(m: Matrix<K>) -> Result<Self, Self::Error> {
+        SMatrix::try_from(&m)
+    }
+}
+
+impl<K, const R: usize, const C: usize> Display for SMatrix<K, R, C>
+where
+    K: Debug + Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.data.iter().enumerate() {
+            write!(f, "[")?;
+            for (j, e) in row.iter().enumerate() {
+                if j != 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{:.1}", e)?;
+            }
+            write!(f, "]")?;
+            if i < R - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<K, const R: usize, const C: usize> Index<(usize, usize)> for SMatrix<K, R, C> {
+    type Output = K;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        &self.data[i][j]
+    }
+}
+
+impl<K, const R: usize, const C: usize> IndexMut<(usize, usize)> for SMatrix<K, R, C> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[i][j]
+    }
+}
+
+impl<K: Scalar, const R: usize, const C: usize> AddAssign for SMatrix<K, R, C> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.data
+            .iter_mut()
+            .flatten()
+            .zip(rhs.data.iter().flatten())
+            .for_each(|(a, b)| *a += *b);
+    }
+}
+
+impl<K: Scalar, const R: usize, const C: usize> SubAssign for SMatrix<K, R, C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.data
+            .iter_mut()
+            .flatten()
+            .zip(rhs.data.iter().flatten())
+            .for_each(|(a, b)| *a -= *b);
+    }
+}
+
+impl<K: Scalar, const R: usize, const C: usize> MulAssign<K> for SMatrix<K, R, C> {
+    fn mul_assign(&mut self, rhs: K) {
+        self.data.iter_mut().flatten().for_each(|a| *a *= rhs);
+    }
+}
+
+impl<K: Scalar, const R: usize, const C: usize> Add for SMatrix<K, R, C> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<K: Scalar, const R: usize, const C: usize> Sub for SMatrix<K, R, C> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<K: Scalar, const R: usize, const C: usize> Mul<K> for SMatrix<K, R, C> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: K) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<K: Scalar, const R: usize, const C: usize> Mul<SVector<K, C>> for SMatrix<K, R, C> {
+    type Output = SVector<K, R>;
+
+    fn mul(self, rhs: SVector<K, C>) -> Self::Output {
+        self.mul_vec(&rhs)
+    }
+}
+
+impl<K: Scalar, const R: usize, const C: usize, const P: usize> Mul<SMatrix<K, C, P>>
+    for SMatrix<K, R, C>
+{
+    type Output = SMatrix<K, R, P>;
+
+    fn mul(self, rhs: SMatrix<K, C, P>) -> Self::Output {
+        self.mul_mat(&rhs)
+    }
+}
+
+impl<K, const R: usize, const C: usize> SMatrix<K, R, C> {
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> K,
+    {
+        SMatrix {
+            data: std::array::from_fn(|i| std::array::from_fn(|j| f(i, j))),
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (R, C)
+    }
+}
+
+impl<K: Scalar, const R: usize, const C: usize> SMatrix<K, R, C> {
+    pub fn zeros() -> Self {
+        SMatrix {
+            data: [[K::zero(); C]; R],
+        }
+    }
+
+    pub fn transpose(&self) -> SMatrix<K, C, R> {
+        SMatrix::from_fn(|i, j| self.data[j][i])
+    }
+
+    pub fn mul_vec(&self, vec: &SVector<K, C>) -> SVector<K, R> {
+        SVector::from(std::array::from_fn(|i| {
+            SVector::from(self.data[i]).dot(vec)
+        }))
+    }
+
+    pub fn mul_mat<const P: usize>(&self, mat: &SMatrix<K, C, P>) -> SMatrix<K, R, P> {
+        let mut result = SMatrix::zeros();
+        for i in 0..R {
+            for k in 0..C {
+                let a = self.data[i][k];
+                for j in 0..P {
+                    result.data[i][j] += a * mat.data[k][j];
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<K: Scalar, const N: usize> SMatrix<K, N, N> {
+    pub fn identity() -> Self {
+        SMatrix::from_fn(|i, j| if i == j { K::one() } else { K::zero() })
+    }
+
+    pub fn trace(&self) -> K {
+        (0..N).fold(K::zero(), |acc, i| acc + self.data[i][i])
+    }
+
+    /// Exact for integer matrices, see [`Matrix::determinant`].
+    pub fn determinant(&self) -> K
+    where
+        K: Ring,
+    {
+        Matrix::from(*self).determinant()
+    }
+
+    /// Fails with `Singular` like [`Matrix::inverse`].
+    pub fn inverse(&self) -> Result<Self, LinalgError>
+    where
+        K: Field,
+    {
+        let inv = Matrix::from(*self).inverse()?;
+        Ok(SMatrix::from_fn(|i, j| inv[(i, j)]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_vectors() {
+        let u = Vector3::from([1., 2., 3.]);
+        let v = Vector3::from([4., 5., 6.]);
+        assert_eq!(Vector3::from([5., 7., 9.]), u + v);
+        assert_eq!(Vector3::from([-3., -3., -3.]), u - v);
+        assert_eq!(Vector3::from([2., 4., 6.]), u * 2.);
+        assert_eq!(32., u.dot(&v));
+        assert_eq!(Vector3::from([-3., 6., -3.]), u.cross(&v));
+        assert_eq!(5., Vector2::from([3., -4.]).norm());
+        assert_eq!(4., Vector2::from([3., -4.]).norm_inf());
+        assert_eq!(0., Vector4::<f64>::zeros().norm_1());
+        assert_eq!("[1.0]\n[2.0]\n[3.0]", u.to_string());
+    }
+
+    #[test]
+    fn fixed_matrices() {
+        let a = SMatrix::from([[1, 2, 3], [4, 5, 6]]);
+        let b = SMatrix::from([[1, 0], [0, 1], [1, 1]]);
+        assert_eq!(SMatrix::from([[4, 5], [10, 11]]), a * b);
+        assert_eq!(SVector::from([14, 32]), a * SVector::from([1, 2, 3]));
+        assert_eq!(SMatrix::from([[1, 4], [2, 5], [3, 6]]), a.transpose());
+        assert_eq!(SMatrix::from([[2, 4, 6], [8, 10, 12]]), a + a);
+        assert_eq!(SMatrix::zeros(), a - a);
+
+        let m = Matrix2::from([[1, 2], [3, 4]]);
+        assert_eq!(5, m.trace());
+        assert_eq!(-2, m.determinant());
+        assert_eq!(m, m * Matrix2::identity());
+
+        let m = Matrix2::from([[2., 0.], [0., 4.]]);
+        assert_eq!(Ok(Matrix2::from([[0.5, 0.], [0., 0.25]])), m.inverse());
+        assert_eq!(
+            Err(LinalgError::Singular),
+            Matrix2::<f64>::zeros().inverse()
+        );
+        assert_eq!("[2.0, 0.0]\n[0.0, 4.0]", m.to_string());
+    }
+
+    #[test]
+    fn dynamic_conversions() {
+        let u = Vector3::from([1, 2, 3]);
+        assert_eq!(Vector::from([1, 2, 3]), Vector::from(u));
+        assert_eq!(Ok(u), Vector3::try_from(Vector::from([1, 2, 3])));
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }),
+            Vector3::try_from(Vector::from([1, 2]))
+        );
+
+        let m = SMatrix::from([[1, 2, 3], [4, 5, 6]]);
+        let dynamic = Matrix::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(dynamic, Matrix::from(m));
+        assert_eq!(Ok(m), SMatrix::try_from(&dynamic));
+        assert_eq!(
+            Ok(m),
+            SMatrix::try_from(dynamic.to_layout(crate::Layout::ColMajor))
+        );
+        assert_eq!(
+            Err(LinalgError::ShapeMismatch {
+                expected: (3, 2),
+                found: (2, 3)
+            }),
+            SMatrix::<i32, 3, 2>::try_from(&dynamic)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use crate::blas::axpy;
+use crate::error::LinalgError;
+use crate::scalar::Field;
+use crate::vector::Vector;
+
+use super::{IterativeOptions, IterativeSolution, LinearOperator};
+
+/// BiCGSTAB (van der Vorst) for general square `A`, right preconditioned.
+/// Fails with `NoConvergence` on a breakdown, when an inner product it
+/// divides by vanishes.
+pub fn bicgstab<K, A>(
+    a: &A,
+    b: &Vector<K>,
+    mut options: IterativeOptions<K>,
+) -> Result<IterativeSolution<K>, LinalgError>
+where
+    K: Field,
+    A: LinearOperator<K> + ?Sized,
+{
+    let (mut x, mut r) = options.start(a, b)?;
+    let target = options.tol * b.norm();
+    let residual = r.norm();
+    if residual <= target {
+        return Ok(IterativeSolution {
+            x,
+            iterations: 0,
+            residual,
+        });
+    }
+    let r_hat = r.clone();
+    let (mut rho, mut alpha, mut omega) = (K::one(), K::one(), K::one());
+    let mut v = Vector::from(vec![K::zero(); b.size()]);
+    let mut p = v.clone();
+    for k in 1..=options.max_iter {
+        let rho_next = r_hat.dotc(&r);
+        if rho_next.is_zero() || omega.is_zero() {
+            return Err(LinalgError::NoConvergence { iterations: k });
+        }
+        // p = r + beta (p - omega v)
+        let beta = (rho_next / rho) * (alpha / omega);
+        axpy(-omega, v.view(), p.view_mut());
+        p.scl(beta);
+        p.add_mut(&r);
+        rho = rho_next;
+
+        let p_hat = options.precondition(&p);
+        v = a.apply(&p_hat);
+        let denom = r_hat.dotc(&v);
+        if denom.is_zero() {
+            return Err(LinalgError::NoConvergence { iterations: k });
+        }
+        alpha = rho / denom;
+        axpy(alpha, p_hat.view(), x.view_mut());
+        let mut s = r;
+        axpy(-alpha, v.view(), s.view_mut());
+        let residual = s.norm();
+        if residual <= target {
+            options.report(k, residual);
+            return Ok(IterativeSolution {
+                x,
+                iterations: k,
+                residual,
+            });
+        }
+
+        let s_hat = options.precondition(&s);
+        let t = a.apply(&s_hat);
+        let tt = t.dotc(&t);
+        omega = if tt.is_zero() {
+            K::zero()
+        } else {
+            t.dotc(&s) / tt
+        };
+        axpy(omega, s_hat.view(), x.view_mut());
+        r = s;
+        axpy(-omega, t.view(), r.view_mut());
+        let residual = r.norm();
+        options.report(k, residual);
+        if residual <= target {
+            return Ok(IterativeSolution {
+                x,
+                iterations: k,
+                residual,
+            });
+        }
+    }
+    Err(LinalgError::NoConvergence {
+        iterations: options.max_iter,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::assert_solves;
+    use super::super::{Jacobi, Ssor};
+    use super::*;
+    use crate::matrix::Matrix;
+
+    fn nonsymmetric(n: usize) -> Matrix<f64> {
+        Matrix::from_fn(n, n, |i, j| match (i as isize) - (j as isize) {
+            0 => 5. + (i % 3) as f64,
+            -1 => -1.,
+            1 => -2.,
+            -3 => 0.5,
+            _ => 0.,
+        })
+    }
+
+    #[test]
+    fn bicgstab_converges() {
+        let a = nonsymmetric(50);
+        let b = Vector::from((0..50).map(|i| 1. + (i % 4) as f64).collect::<Vec<_>>());
+        let mut calls = 0;
+        let mut count = |_, _| calls += 1;
+        let solution = bicgstab(
+            &a,
+            &b,
+            IterativeOptions::default().tol(1e-10).callback(&mut count),
+        )
+        .unwrap();
+        assert_solves(&a, solution.solution(), &b, 1e-10);
+        assert_eq!(solution.iterations(), calls);
+
+        let jacobi = Jacobi::new(&a).unwrap();
+        let ssor = Ssor::new(&a, 1.2).unwrap();
+        for m in [&jacobi as &dyn super::super::Preconditioner<f64>, &ssor] {
+            let solution = bicgstab(
+                &a,
+                &b,
+                IterativeOptions::default().tol(1e-10).preconditioner(m),
+            )
+            .unwrap();
+            assert_solves(&a, solution.solution(), &b, 1e-10);
+        }
+    }
+
+    #[test]
+    fn bicgstab_failures() {
+        let a = nonsymmetric(50);
+        let b = Vector::from(vec![1.; 50]);
+        assert_eq!(
+            LinalgError::NoConvergence { iterations: 1 },
+            bicgstab(&a, &b, IterativeOptions::default().max_iter(1)).unwrap_err()
+        );
+        // A singular operator breaks down instead of looping.
+        let zero = Matrix::<f64>::zeros(3, 3);
+        assert!(bicgstab(
+            &zero,
+            &Vector::from([1., 2., 3.]),
+            IterativeOptions::default()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::blas::axpy;
+use crate::error::LinalgError;
+use crate::scalar::Field;
+use crate::vector::Vector;
+
+use super::{IterativeOptions, IterativeSolution, LinearOperator};
+
+/// Preconditioned conjugate gradient, for Hermitian (real symmetric)
+/// positive definite `A` and preconditioner.
+pub fn cg<K, A>(
+    a: &A,
+    b: &Vector<K>,
+    mut options: IterativeOptions<K>,
+) -> Result<IterativeSolution<K>, LinalgError>
+where
+    K: Field,
+    A: LinearOperator<K> + ?Sized,
+{
+    let (mut x, mut r) = options.start(a, b)?;
+    let target = options.tol * b.norm();
+    let mut residual = r.norm();
+    if residual <= target {
+        return Ok(IterativeSolution {
+            x,
+            iterations: 0,
+            residual,
+        });
+    }
+    let mut z = options.precondition(&r);
+    let mut p = z.clone();
+    let mut rz = r.dotc(&z);
+    for k in 1..=options.max_iter {
+        let q = a.apply(&p);
+        let alpha = rz / p.dotc(&q);
+        axpy(alpha, p.view(), x.view_mut());
+        axpy(-alpha, q.view(), r.view_mut());
+        residual = r.norm();
+        options.report(k, residual);
+        if residual <= target {
+            return Ok(IterativeSolution {
+                x,
+                iterations: k,
+                residual,
+            });
+        }
+        z = options.precondition(&r);
+        let rz_next = r.dotc(&z);
+        // p = z + beta p
+        p.scl(rz_next / rz);
+        p.add_mut(&z);
+        rz = rz_next;
+    }
+    Err(LinalgError::NoConvergence {
+        iterations: options.max_iter,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::super::tests::{assert_solves, laplacian};
+    use super::super::Jacobi;
+    use super::*;
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn cg_converges() {
+        let a = laplacian(20);
+        let b = Vector::from((0..20).map(|i| i as f64).collect::<Vec<_>>());
+        let mut history = Vec::new();
+        let mut record = |k, r| history.push((k, r));
+        let solution = cg(
+            &a,
+            &b,
+            IterativeOptions::default().tol(1e-10).callback(&mut record),
+        )
+        .unwrap();
+        assert_solves(&a, solution.solution(), &b, 1e-10);
+        // At most n steps in exact arithmetic.
+        assert!(solution.iterations() <= 20);
+        assert_eq!(solution.iterations(), history.len());
+        assert_eq!(solution.residual_norm(), history.last().unwrap().1);
+        assert!(history.iter().enumerate().all(|(i, &(k, _))| k == i + 1));
+    }
+
+    #[test]
+    fn cg_preconditioned() {
+        // Badly scaled diagonal: Jacobi makes it a one-step problem.
+        let a = Matrix::from_fn(4, 4, |i, j| if i == j { 10f64.powi(i as i32) } else { 0. });
+        let b = Vector::from([1., 1., 1., 1.]);
+        let jacobi = Jacobi::new(&a).unwrap();
+        let solution = cg(&a, &b, IterativeOptions::default().preconditioner(&jacobi)).unwrap();
+        assert_eq!(1, solution.iterations());
+        assert_solves(&a, solution.solution(), &b, 1e-12);
+    }
+
+    #[test]
+    fn cg_complex_and_failures() {
+        let i = Complex::new(0., 1.);
+        let two = Complex::new(2., 0.);
+        let a = Matrix::from([[two, -i], [i, two]]);
+        let b = Vector::from([Complex::new(1., 0.), Complex::new(0., 1.)]);
+        let x = cg(&a, &b, IterativeOptions::default())
+            .unwrap()
+            .into_solution();
+        let mut r = b.clone();
+        r.sub_mut(&a.mul_vec(&x));
+        assert!(r.norm() < 1e-7);
+
+        assert_eq!(
+            LinalgError::NoConvergence { iterations: 2 },
+            cg(
+                &laplacian(20),
+                &Vector::from(vec![1.; 20]),
+                IterativeOptions::default().max_iter(2)
+            )
+            .unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use num_traits::{Float, Zero};
+
+use crate::blas::axpy;
+use crate::error::LinalgError;
+use crate::scalar::Field;
+use crate::vector::{self, Vector};
+
+use super::{IterativeOptions, IterativeSolution, LinearOperator};
+
+/// Restarted GMRES(`restart`) for general square `A`, right preconditioned
+/// so that the reported residual is the true one.
+pub fn gmres<K, A>(
+    a: &A,
+    b: &Vector<K>,
+    mut options: IterativeOptions<K>,
+) -> Result<IterativeSolution<K>, LinalgError>
+where
+    K: Field,
+    A: LinearOperator<K> + ?Sized,
+{
+    let (mut x, mut r) = options.start(a, b)?;
+    let target = options.tol * b.norm();
+    let m = options.restart.max(1);
+    let mut total = 0;
+    loop {
+        let beta = r.norm();
+        if beta <= target {
+            return Ok(IterativeSolution {
+                x,
+                iterations: total,
+                residual: beta,
+            });
+        }
+        if total == options.max_iter {
+            return Err(LinalgError::NoConvergence { iterations: total });
+        }
+
+        // Arnoldi on A M^-1, with the Hessenberg matrix reduced to upper
+        // triangular form by Givens rotations as it grows.
+        r.scl(K::from_real(beta.recip()));
+        let mut v = vec![r];
+        let mut z: Vec<Vector<K>> = Vec::with_capacity(m);
+        let mut h: Vec<Vec<K>> = Vec::with_capacity(m);
+        let mut rotations: Vec<(K, K)> = Vec::with_capacity(m);
+        let mut g = vec![K::from_real(beta)];
+        while z.len() < m && total < options.max_iter {
+            let j = z.len();
+            total += 1;
+            z.push(options.precondition(&v[j]));
+            let mut w = a.apply(&z[j]);
+            let mut col = Vec::with_capacity(j + 2);
+            for vi in &v {
+                let hij = vi.dotc(&w);
+                axpy(-hij, vi.view(), w.view_mut());
+                col.push(hij);
+            }
+            let norm = w.norm();
+            col.push(K::from_real(norm));
+            for (i, &(c, s)) in rotations.iter().enumerate() {
+                let (x, y) = (col[i], col[i + 1]);
+                col[i] = c * x + s * y;
+                col[i + 1] = c * y - s.conj() * x;
+            }
+            let (c, s) = givens(col[j], col[j + 1]);
+            col[j] = c * col[j] + s * col[j + 1];
+            col[j + 1] = K::zero();
+            g.push(-s.conj() * g[j]);
+            g[j] = c * g[j];
+            rotations.push((c, s));
+            h.push(col);
+            let residual = g[j + 1].modulus();
+            options.report(total, residual);
+            if residual <= target || norm.is_zero() {
+                break;
+            }
+            w.scl(K::from_real(norm.recip()));
+            v.push(w);
+        }
+
+        // Solve the triangular system H y = g and update x = x + Z y.
+        let k = z.len();
+        let mut y = vec![K::zero(); k];
+        for i in (0..k).rev() {
+            let s = (i + 1..k).fold(g[i], |s, l| s - h[l][i] * y[l]);
+            y[i] = s / h[i][i];
+        }
+        for (zi, &yi) in z.iter().zip(&y) {
+            axpy(yi, zi.view(), x.view_mut());
+        }
+        // Restart from the true residual, which also catches a recurrence
+        // made optimistic by rounding.
+        r = b.clone();
+        r.sub_mut(&a.apply(&x));
+    }
+}
+
+/// Rotation `[c s; -conj(s) c]` with real `c` that maps `(a, b)` to
+/// `(r, 0)`.
+fn givens<K: Field>(a: K, b: K) -> (K, K) {
+    let h = vector::norm_2(&[a, b]);
+    if h.is_zero() {
+        return (K::one(), K::zero());
+    }
+    let m = a.modulus();
+    let phase = if m.is_zero() {
+        K::one()
+    } else {
+        a / K::from_real(m)
+    };
+    (K::from_real(m / h), phase * b.conj() / K::from_real(h))
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::super::tests::assert_solves;
+    use super::super::Ssor;
+    use super::*;
+    use crate::matrix::Matrix;
+
+    /// Convection-diffusion: tridiagonal and far from symmetric.
+    fn convection(n: usize) -> Matrix<f64> {
+        Matrix::from_fn(n, n, |i, j| {
+            if i == j {
+                4.
+            } else if j == i + 1 {
+                -0.5
+            } else if i == j + 1 {
+                -2.5
+            } else {
+                0.
+            }
+        })
+    }
+
+    #[test]
+    fn gmres_nonsymmetric() {
+        let a = convection(40);
+        let b = Vector::from((0..40).map(|i| (i as f64).sin()).collect::<Vec<_>>());
+        for restart in [5, 10, 40] {
+            let mut history = Vec::new();
+            let mut record = |k, r| history.push((k, r));
+            let solution = gmres(
+                &a,
+                &b,
+                IterativeOptions::default()
+                    .tol(1e-10)
+                    .restart(restart)
+                    .callback(&mut record),
+            )
+            .unwrap();
+            assert_solves(&a, solution.solution(), &b, 1e-10);
+            assert_eq!(solution.iterations(), history.len());
+        }
+        // Full GMRES finishes in at most n steps.
+        let full = gmres(&a, &b, IterativeOptions::default().tol(1e-10).restart(40)).unwrap();
+        assert!(full.iterations() <= 40);
+    }
+
+    #[test]
+    fn gmres_preconditioned() {
+        let a = convection(40);
+        let b = Vector::from(vec![1.; 40]);
+        let options = || IterativeOptions::default().tol(1e-10).restart(5);
+        let plain = gmres(&a, &b, options()).unwrap();
+        let ssor = Ssor::new(&a, 1.).unwrap();
+        let preconditioned = gmres(&a, &b, options().preconditioner(&ssor)).unwrap();
+        assert_solves(&a, preconditioned.solution(), &b, 1e-10);
+        assert!(preconditioned.iterations() < plain.iterations());
+    }
+
+    #[test]
+    fn gmres_complex() {
+        let i = Complex::new(0., 1.);
+        let one = Complex::new(1., 0.);
+        let a = Matrix::from([
+            [one + i, one, Complex::new(0., 0.)],
+            [i, -one, one],
+            [one, i, one],
+        ]);
+        let b = Vector::from([one, i, one + i]);
+        let x = gmres(&a, &b, IterativeOptions::default().tol(1e-12))
+            .unwrap()
+            .into_solution();
+        let mut r = b.clone();
+        r.sub_mut(&a.mul_vec(&x));
+        assert!(r.norm() < 1e-11);
+
+        assert!(gmres(
+            &convection(40),
+            &Vector::from(vec![1.; 40]),
+            IterativeOptions::default().max_iter(3)
+        )
+        .is_err());
+    }
+}
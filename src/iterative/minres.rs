@@ -0,0 +1,149 @@
+use num_traits::{Float, One, Zero};
+
+use crate::blas::axpy;
+use crate::error::LinalgError;
+use crate::scalar::Field;
+use crate::vector::Vector;
+
+use super::{IterativeOptions, IterativeSolution, LinearOperator};
+
+/// MINRES (Paige and Saunders), for Hermitian (real symmetric) `A` that may
+/// be indefinite. A preconditioner must be Hermitian positive definite; the
+/// residual is then measured in the norm it induces.
+pub fn minres<K, A>(
+    a: &A,
+    b: &Vector<K>,
+    mut options: IterativeOptions<K>,
+) -> Result<IterativeSolution<K>, LinalgError>
+where
+    K: Field,
+    A: LinearOperator<K> + ?Sized,
+{
+    let zero = K::Real::zero();
+    let (mut x, r) = options.start(a, b)?;
+    let norm = |r: &Vector<K>, z: &Vector<K>| r.dotc(z).re().max(zero).sqrt();
+    let target = options.tol * norm(b, &options.precondition(b));
+    let mut y = options.precondition(&r);
+    let beta1 = norm(&r, &y);
+    if beta1 <= target {
+        return Ok(IterativeSolution {
+            x,
+            iterations: 0,
+            residual: beta1,
+        });
+    }
+
+    // Lanczos vectors r1, r2 and the QR factorization of the tridiagonal
+    // matrix, updated one Givens rotation at a time.
+    let (mut r1, mut r2) = (r.clone(), r);
+    let mut beta = beta1;
+    let mut old_beta = zero;
+    let (mut dbar, mut epsilon, mut phibar) = (zero, zero, beta1);
+    let (mut cs, mut sn) = (-K::Real::one(), zero);
+    let mut w = Vector::from(vec![K::zero(); b.size()]);
+    let mut w2 = w.clone();
+    for k in 1..=options.max_iter {
+        if beta.is_zero() {
+            break;
+        }
+        let mut v = y;
+        v.scl(K::from_real(beta.recip()));
+        y = a.apply(&v);
+        if k >= 2 {
+            axpy(K::from_real(-beta / old_beta), r1.view(), y.view_mut());
+        }
+        let alpha = v.dotc(&y).re();
+        axpy(K::from_real(-alpha / beta), r2.view(), y.view_mut());
+        r1 = r2;
+        r2 = y;
+        y = options.precondition(&r2);
+        old_beta = beta;
+        beta = norm(&r2, &y);
+
+        let old_epsilon = epsilon;
+        let delta = cs * dbar + sn * alpha;
+        let gbar = sn * dbar - cs * alpha;
+        epsilon = sn * beta;
+        dbar = -cs * beta;
+        let gamma = gbar.hypot(beta).max(K::Real::epsilon());
+        cs = gbar / gamma;
+        sn = beta / gamma;
+        let phi = cs * phibar;
+        phibar = sn * phibar;
+
+        // w = (v - old_epsilon w1 - delta w2) / gamma
+        let w1 = w2;
+        w2 = w;
+        w = v;
+        axpy(K::from_real(-old_epsilon), w1.view(), w.view_mut());
+        axpy(K::from_real(-delta), w2.view(), w.view_mut());
+        w.scl(K::from_real(gamma.recip()));
+        axpy(K::from_real(phi), w.view(), x.view_mut());
+
+        options.report(k, phibar);
+        if phibar <= target {
+            return Ok(IterativeSolution {
+                x,
+                iterations: k,
+                residual: phibar,
+            });
+        }
+    }
+    Err(LinalgError::NoConvergence {
+        iterations: options.max_iter,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{assert_solves, laplacian};
+    use super::super::{cg, Jacobi};
+    use super::*;
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn minres_indefinite() {
+        // Symmetric with eigenvalues of both signs, where CG may break down.
+        let a = Matrix::from_fn(30, 30, |i, j| match i.abs_diff(j) {
+            0 => i as f64 - 14.5,
+            1 => 1.,
+            _ => 0.,
+        });
+        let b = Vector::from((0..30).map(|i| (i % 7) as f64 - 3.).collect::<Vec<_>>());
+        let mut history = Vec::new();
+        let mut record = |_, r| history.push(r);
+        let solution = minres(
+            &a,
+            &b,
+            IterativeOptions::default().tol(1e-10).callback(&mut record),
+        )
+        .unwrap();
+        assert_solves(&a, solution.solution(), &b, 1e-9);
+        // The residual never increases.
+        assert!(history.windows(2).all(|p| p[1] <= p[0] * (1. + 1e-12)));
+    }
+
+    #[test]
+    fn minres_matches_cg() {
+        let a = laplacian(15);
+        let b = Vector::from(vec![1.; 15]);
+        let options = || IterativeOptions::default().tol(1e-12);
+        let x = minres(&a, &b, options()).unwrap().into_solution();
+        let y = cg(&a, &b, options()).unwrap().into_solution();
+        for (x, y) in x.e.iter().zip(&y.e) {
+            assert!((x - y).abs() < 1e-9);
+        }
+
+        let scaled = Matrix::from_fn(15, 15, |i, j| a[(i, j)] * (i + 1) as f64 * (j + 1) as f64);
+        let jacobi = Jacobi::new(&scaled).unwrap();
+        let solution = minres(
+            &scaled,
+            &b,
+            IterativeOptions::default()
+                .tol(1e-12)
+                .preconditioner(&jacobi),
+        )
+        .unwrap();
+        assert_solves(&scaled, solution.solution(), &b, 1e-9);
+    }
+}
@@ -0,0 +1,255 @@
+//! Iterative (Krylov subspace) solvers for systems too large to factor.
+//!
+//! The solvers only ever multiply by the matrix, through the
+//! [`LinearOperator`] trait, so the operator can be a [`Matrix`], a closure
+//! or anything else that computes `A x`. All of them stop once the residual
+//! norm drops below `tol * |b|` and fail with `NoConvergence` after
+//! `max_iter` iterations.
+
+use num_traits::Float;
+
+use crate::error::{check_dimension, LinalgError};
+use crate::matrix::Matrix;
+use crate::scalar::{Field, Scalar};
+use crate::vector::Vector;
+
+mod bicgstab;
+mod cg;
+mod gmres;
+mod minres;
+mod preconditioner;
+
+pub use bicgstab::bicgstab;
+pub use cg::cg;
+pub use gmres::gmres;
+pub use minres::minres;
+pub use preconditioner::{IncompleteCholesky, Jacobi, Preconditioner, RowAccess, Ssor};
+
+/// A linear map `x -> A x`.
+pub trait LinearOperator<K> {
+    fn apply(&self, x: &Vector<K>) -> Vector<K>;
+}
+
+impl<K: Scalar> LinearOperator<K> for Matrix<K> {
+    fn apply(&self, x: &Vector<K>) -> Vector<K> {
+        self.mul_vec(x)
+    }
+}
+
+impl<K, F> LinearOperator<K> for F
+where
+    F: Fn(&Vector<K>) -> Vector<K>,
+{
+    fn apply(&self, x: &Vector<K>) -> Vector<K> {
+        self(x)
+    }
+}
+
+/// Stopping criteria and extras shared by the iterative solvers.
+pub struct IterativeOptions<'a, K: Field> {
+    /// Relative residual `|b - A x| / |b|` to reach. Defaults to the
+    /// square root of epsilon.
+    pub tol: K::Real,
+    /// Iterations allowed before failing with `NoConvergence`, counting
+    /// inner GMRES steps. Defaults to 1000.
+    pub max_iter: usize,
+    /// Krylov subspace dimension between GMRES restarts. Defaults to 30.
+    pub restart: usize,
+    /// Initial guess, zero by default.
+    pub x0: Option<Vector<K>>,
+    /// Applied on the right by GMRES and BiCGSTAB, symmetrically by CG and
+    /// MINRES.
+    pub preconditioner: Option<&'a dyn Preconditioner<K>>,
+    /// Called after each iteration with its number and residual norm.
+    pub callback: Option<&'a mut dyn FnMut(usize, K::Real)>,
+}
+
+impl<K: Field> Default for IterativeOptions<'_, K> {
+    fn default() -> Self {
+        IterativeOptions {
+            tol: K::Real::epsilon().sqrt(),
+            max_iter: 1000,
+            restart: 30,
+            x0: None,
+            preconditioner: None,
+            callback: None,
+        }
+    }
+}
+
+impl<'a, K: Field> IterativeOptions<'a, K> {
+    pub fn tol(mut self, tol: K::Real) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    pub fn max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    pub fn restart(mut self, restart: usize) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    pub fn x0(mut self, x0: Vector<K>) -> Self {
+        self.x0 = Some(x0);
+        self
+    }
+
+    pub fn preconditioner(mut self, preconditioner: &'a dyn Preconditioner<K>) -> Self {
+        self.preconditioner = Some(preconditioner);
+        self
+    }
+
+    pub fn callback(mut self, callback: &'a mut dyn FnMut(usize, K::Real)) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+
+    /// Applies the preconditioner, if any.
+    fn precondition(&self, r: &Vector<K>) -> Vector<K> {
+        match self.preconditioner {
+            Some(m) => m.precondition(r),
+            None => r.clone(),
+        }
+    }
+
+    fn report(&mut self, iteration: usize, residual: K::Real) {
+        if let Some(callback) = self.callback.as_mut() {
+            callback(iteration, residual);
+        }
+    }
+
+    /// The initial guess and its residual `b - A x0`.
+    fn start<A>(&mut self, a: &A, b: &Vector<K>) -> Result<(Vector<K>, Vector<K>), LinalgError>
+    where
+        A: LinearOperator<K> + ?Sized,
+    {
+        match self.x0.take() {
+            Some(x) => {
+                check_dimension(b.size(), x.size())?;
+                let mut r = b.clone();
+                r.sub_mut(&a.apply(&x));
+                Ok((x, r))
+            }
+            None => Ok((Vector::from(vec![K::zero(); b.size()]), b.clone())),
+        }
+    }
+}
+
+/// Outcome of a converged iterative solve.
+#[derive(Clone, Debug)]
+pub struct IterativeSolution<K: Field> {
+    x: Vector<K>,
+    iterations: usize,
+    residual: K::Real,
+}
+
+impl<K: Field> IterativeSolution<K> {
+    /// The solution `x`.
+    pub fn solution(&self) -> &Vector<K> {
+        &self.x
+    }
+
+    /// Takes the solution `x`.
+    pub fn into_solution(self) -> Vector<K> {
+        self.x
+    }
+
+    /// Iterations taken.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Final residual norm, as reported to the callback.
+    pub fn residual_norm(&self) -> K::Real {
+        self.residual
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// The 1D Laplacian `tridiag(-1, 2, -1)`, symmetric positive definite.
+    pub(crate) fn laplacian(n: usize) -> Matrix<f64> {
+        Matrix::from_fn(n, n, |i, j| match i.abs_diff(j) {
+            0 => 2.,
+            1 => -1.,
+            _ => 0.,
+        })
+    }
+
+    pub(crate) fn assert_solves<A: LinearOperator<f64> + ?Sized>(
+        a: &A,
+        x: &Vector<f64>,
+        b: &Vector<f64>,
+        tol: f64,
+    ) {
+        let mut r = b.clone();
+        r.sub_mut(&a.apply(x));
+        assert!(r.norm() <= tol * b.norm(), "residual {}", r.norm());
+    }
+
+    #[test]
+    fn operators() {
+        let a = Matrix::from([[1., 2.], [3., 4.]]);
+        let x = Vector::from([1., 1.]);
+        assert_eq!(Vector::from([3., 7.]), a.apply(&x));
+        let double = |x: &Vector<f64>| {
+            let mut y = x.clone();
+            y.scl(2.);
+            y
+        };
+        assert_eq!(Vector::from([2., 2.]), double.apply(&x));
+    }
+
+    #[test]
+    fn matrix_free() {
+        // The Laplacian applied without storing it.
+        let n = 50;
+        let a = |x: &Vector<f64>| {
+            Vector::from(
+                (0..n)
+                    .map(|i| {
+                        let left = if i > 0 { x[i - 1] } else { 0. };
+                        let right = if i + 1 < n { x[i + 1] } else { 0. };
+                        2. * x[i] - left - right
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let b = Vector::from(vec![1.; n]);
+        let tol = 1e-10;
+        for solution in [
+            cg(&a, &b, IterativeOptions::default().tol(tol)),
+            minres(&a, &b, IterativeOptions::default().tol(tol)),
+            gmres(&a, &b, IterativeOptions::default().tol(tol).restart(n)),
+            bicgstab(&a, &b, IterativeOptions::default().tol(tol)),
+        ] {
+            let solution = solution.unwrap();
+            assert_solves(&laplacian(n), solution.solution(), &b, 1e-9);
+        }
+    }
+
+    #[test]
+    fn initial_guess() {
+        let a = laplacian(10);
+        let b = Vector::from(vec![1.; 10]);
+        let x = cg(&a, &b, IterativeOptions::default().tol(1e-12)).unwrap();
+        let again = cg(
+            &a,
+            &b,
+            IterativeOptions::default().x0(x.into_solution()).tol(1e-8),
+        )
+        .unwrap();
+        assert_eq!(0, again.iterations());
+
+        let zero = Vector::from(vec![0.; 10]);
+        let solution = gmres(&a, &zero, IterativeOptions::default()).unwrap();
+        assert_eq!(zero, *solution.solution());
+        assert!(cg(&a, &b, IterativeOptions::default().x0(Vector::from([1.]))).is_err());
+    }
+}
@@ -0,0 +1,384 @@
+use num_traits::{Float, NumCast, Zero};
+
+use crate::error::LinalgError;
+use crate::matrix::Matrix;
+use crate::scalar::{Field, Scalar};
+use crate::sparse::CsrMatrix;
+use crate::vector::Vector;
+
+/// An approximation `M` of `A` that is cheap to invert.
+pub trait Preconditioner<K> {
+    /// Returns `M^-1 r`.
+    fn precondition(&self, r: &Vector<K>) -> Vector<K>;
+}
+
+/// Row-by-row access to the elements of a matrix, from which the
+/// preconditioners are built in time proportional to the number of
+/// elements visited.
+pub trait RowAccess<K> {
+    fn shape(&self) -> (usize, usize);
+
+    /// The `(col, value)` pairs of row `i` that may be nonzero, by
+    /// increasing column.
+    fn row_entries(&self, i: usize) -> impl Iterator<Item = (usize, K)> + '_;
+}
+
+/// Visits every element, skipping the zeros.
+impl<K: Scalar> RowAccess<K> for Matrix<K> {
+    fn shape(&self) -> (usize, usize) {
+        self.shape()
+    }
+
+    fn row_entries(&self, i: usize) -> impl Iterator<Item = (usize, K)> + '_ {
+        (0..self.cols())
+            .map(move |j| (j, self[(i, j)]))
+            .filter(|(_, v)| !v.is_zero())
+    }
+}
+
+fn check_square<K, A: RowAccess<K> + ?Sized>(a: &A) -> Result<usize, LinalgError> {
+    match a.shape() {
+        (rows, cols) if rows == cols => Ok(rows),
+        (rows, cols) => Err(LinalgError::NotSquare { rows, cols }),
+    }
+}
+
+/// Diagonal of `a`, failing with `Singular` if it has a zero.
+fn diagonal<K: Scalar, A: RowAccess<K> + ?Sized>(a: &A) -> Result<Vec<K>, LinalgError> {
+    (0..check_square(a)?)
+        .map(|i| {
+            a.row_entries(i)
+                .find(|&(j, _)| j == i)
+                .map(|(_, d)| d)
+                .filter(|d| !d.is_zero())
+                .ok_or(LinalgError::Singular)
+        })
+        .collect()
+}
+
+/// Jacobi (diagonal) preconditioner `M = diag(A)`.
+#[derive(Clone, Debug)]
+pub struct Jacobi<K> {
+    inverse: Vec<K>,
+}
+
+impl<K: Field> Jacobi<K> {
+    /// Fails with `NotSquare`, or with `Singular` if the diagonal has a zero.
+    pub fn new<A: RowAccess<K> + ?Sized>(a: &A) -> Result<Self, LinalgError> {
+        let inverse = diagonal(a)?.into_iter().map(|d| K::one() / d).collect();
+        Ok(Jacobi { inverse })
+    }
+}
+
+impl<K: Field> Preconditioner<K> for Jacobi<K> {
+    fn precondition(&self, r: &Vector<K>) -> Vector<K> {
+        Vector::from(
+            r.e.iter()
+                .zip(&self.inverse)
+                .map(|(&r, &d)| r * d)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Symmetric successive over-relaxation preconditioner
+/// `M = ω / (2 - ω) (D / ω + L) (D / ω)^-1 (D / ω + U)`, where `D`, `L`
+/// and `U` are the diagonal, strictly lower and strictly upper parts of
+/// `A`. `ω = 1` gives symmetric Gauss-Seidel.
+#[derive(Clone, Debug)]
+pub struct Ssor<K: Field> {
+    /// `A` without its diagonal.
+    off_diagonal: CsrMatrix<K>,
+    diagonal: Vec<K>,
+    omega: K::Real,
+}
+
+impl<K: Field> Ssor<K> {
+    /// Fails with `InvalidArgument` unless `0 < omega < 2`, with
+    /// `NotSquare`, or with `Singular` if the diagonal has a zero.
+    pub fn new<A: RowAccess<K> + ?Sized>(a: &A, omega: K::Real) -> Result<Self, LinalgError> {
+        let two = <K::Real as NumCast>::from(2).unwrap();
+        // Written so that NaN is rejected too.
+        if !(omega > K::Real::zero() && omega < two) {
+            return Err(LinalgError::InvalidArgument(
+                "the SSOR relaxation factor must lie in (0, 2)",
+            ));
+        }
+        let diagonal = diagonal(a)?;
+        let n = diagonal.len();
+        let (mut row_ptr, mut col_indices, mut values) = (vec![0], Vec::new(), Vec::new());
+        for i in 0..n {
+            for (j, v) in a.row_entries(i).filter(|&(j, _)| j != i) {
+                col_indices.push(j);
+                values.push(v);
+            }
+            row_ptr.push(values.len());
+        }
+        Ok(Ssor {
+            off_diagonal: CsrMatrix::from_parts(n, n, row_ptr, col_indices, values),
+            diagonal,
+            omega,
+        })
+    }
+}
+
+impl<K: Field> Preconditioner<K> for Ssor<K> {
+    fn precondition(&self, r: &Vector<K>) -> Vector<K> {
+        let n = self.diagonal.len();
+        let omega = K::from_real(self.omega);
+        let two = K::one() + K::one();
+        // Forward sweep with D / ω + L, scaling by D / ω, then backward
+        // sweep with D / ω + U.
+        let mut z = r.e.clone();
+        for i in 0..n {
+            let s = self
+                .off_diagonal
+                .row(i)
+                .take_while(|&(j, _)| j < i)
+                .fold(z[i], |s, (j, v)| s - v * z[j]);
+            z[i] = s * omega / self.diagonal[i];
+        }
+        for (z, &d) in z.iter_mut().zip(&self.diagonal) {
+            *z *= d / omega;
+        }
+        for i in (0..n).rev() {
+            let s = self
+                .off_diagonal
+                .row(i)
+                .skip_while(|&(j, _)| j < i)
+                .fold(z[i], |s, (j, v)| s - v * z[j]);
+            z[i] = s * omega / self.diagonal[i];
+        }
+        let scale = (two - omega) / omega;
+        Vector::from(z.into_iter().map(|z| z * scale).collect::<Vec<_>>())
+    }
+}
+
+/// Zero fill-in incomplete Cholesky preconditioner `M = L L^H`, where `L`
+/// has the sparsity pattern of the lower triangle of `A`.
+#[derive(Clone, Debug)]
+pub struct IncompleteCholesky<K> {
+    /// Each row ends with its diagonal element.
+    l: CsrMatrix<K>,
+}
+
+impl<K: Field> IncompleteCholesky<K> {
+    /// Factors a Hermitian (real symmetric) matrix row by row, reading only
+    /// its lower triangle.
+    ///
+    /// Fails with `NotSquare`, or with `NotPositiveDefinite` if a pivot is
+    /// not positive, which can happen even for positive definite `A`
+    /// unless it is, for example, diagonally dominant.
+    pub fn new<A: RowAccess<K> + ?Sized>(a: &A) -> Result<Self, LinalgError> {
+        let n = check_square(a)?;
+        let (mut row_ptr, mut col_indices, mut values) = (vec![0], Vec::new(), Vec::new());
+        for i in 0..n {
+            let start = values.len();
+            for (k, v) in a.row_entries(i).take_while(|&(k, _)| k <= i) {
+                col_indices.push(k);
+                values.push(v);
+            }
+            if col_indices[start..].last() != Some(&i) {
+                return Err(LinalgError::NotPositiveDefinite { pivot: i });
+            }
+            // l_ik = (a_ik - Σ_{j<k} l_ij conj(l_kj)) / l_kk over the
+            // pattern, then l_ii = sqrt(a_ii - Σ_{j<i} |l_ij|^2).
+            let diag = values.len() - 1;
+            for p in start..diag {
+                let k = col_indices[p];
+                let (row_k, l_kk) = (row_ptr[k]..row_ptr[k + 1] - 1, values[row_ptr[k + 1] - 1]);
+                let (mut q, mut s) = (row_k.start, K::zero());
+                for r in start..p {
+                    let j = col_indices[r];
+                    while q < row_k.end && col_indices[q] < j {
+                        q += 1;
+                    }
+                    if q < row_k.end && col_indices[q] == j {
+                        s += values[r] * values[q].conj();
+                    }
+                }
+                values[p] = (values[p] - s) / l_kk;
+            }
+            let d = values[start..diag]
+                .iter()
+                .fold(values[diag].re(), |d, l| d - l.modulus_squared());
+            if d.is_nan() || d <= K::Real::zero() {
+                return Err(LinalgError::NotPositiveDefinite { pivot: i });
+            }
+            values[diag] = K::from_real(d.sqrt());
+            row_ptr.push(values.len());
+        }
+        Ok(IncompleteCholesky {
+            l: CsrMatrix::from_parts(n, n, row_ptr, col_indices, values),
+        })
+    }
+
+    /// The incomplete factor `L`.
+    pub fn l(&self) -> &CsrMatrix<K> {
+        &self.l
+    }
+}
+
+impl<K: Field> Preconditioner<K> for IncompleteCholesky<K> {
+    fn precondition(&self, r: &Vector<K>) -> Vector<K> {
+        let n = self.l.rows();
+        let (row_ptr, cols, values) = (self.l.row_ptr(), self.l.col_indices(), self.l.values());
+        let mut z = r.e.clone();
+        // Forward substitution with the rows of L, then back substitution
+        // with L^H, whose columns they are.
+        for i in 0..n {
+            let diag = row_ptr[i + 1] - 1;
+            let s = (row_ptr[i]..diag).fold(z[i], |s, p| s - values[p] * z[cols[p]]);
+            z[i] = s / values[diag];
+        }
+        for i in (0..n).rev() {
+            let diag = row_ptr[i + 1] - 1;
+            z[i] /= values[diag];
+            let zi = z[i];
+            for p in row_ptr[i]..diag {
+                z[cols[p]] -= values[p].conj() * zi;
+            }
+        }
+        Vector::from(z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{assert_solves, laplacian};
+    use super::super::{cg, IterativeOptions};
+    use super::*;
+
+    /// The 2D Laplacian on a `k x k` grid.
+    fn laplacian_2d(k: usize) -> Matrix<f64> {
+        let n = k * k;
+        Matrix::from_fn(n, n, |i, j| {
+            let (xi, yi, xj, yj) = (i % k, i / k, j % k, j / k);
+            match xi.abs_diff(xj) + yi.abs_diff(yj) {
+                0 => 4.,
+                1 => -1.,
+                _ => 0.,
+            }
+        })
+    }
+
+    fn inverse_of(m: &dyn Preconditioner<f64>, n: usize) -> Matrix<f64> {
+        let cols: Vec<Vector<f64>> = (0..n)
+            .map(|j| {
+                m.precondition(&Vector::from(
+                    (0..n)
+                        .map(|i| if i == j { 1. } else { 0. })
+                        .collect::<Vec<_>>(),
+                ))
+            })
+            .collect();
+        Matrix::from_fn(n, n, |i, j| cols[j][i])
+    }
+
+    #[test]
+    fn exact_on_easy_matrices() {
+        // IC(0) is the exact Cholesky factor of a tridiagonal matrix, and
+        // SSOR with ω = 1 inverts a triangular one.
+        let a = laplacian(6);
+        let ic = IncompleteCholesky::new(&a).unwrap();
+        let l = a.cholesky().unwrap();
+        for i in 0..6 {
+            for j in 0..6 {
+                assert!((ic.l().get(i, j) - l.l()[(i, j)]).abs() < 1e-12);
+            }
+        }
+        let lower = Matrix::from([[2., 0.], [1., 4.]]);
+        let inverse = inverse_of(&Ssor::new(&lower, 1.).unwrap(), 2);
+        let expected = lower.inverse().unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((inverse[(i, j)] - expected[(i, j)]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn ssor_is_symmetric() {
+        let a = laplacian_2d(3);
+        let m = inverse_of(&Ssor::new(&a, 1.5).unwrap(), 9);
+        for i in 0..9 {
+            for j in 0..9 {
+                assert!((m[(i, j)] - m[(j, i)]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn preconditioned_cg() {
+        let a = laplacian_2d(12);
+        let b = Vector::from(vec![1.; 144]);
+        let iterations = |m: Option<&dyn Preconditioner<f64>>| {
+            let mut options = IterativeOptions::default().tol(1e-10);
+            options.preconditioner = m;
+            let solution = cg(&a, &b, options).unwrap();
+            assert_solves(&a, solution.solution(), &b, 1e-10);
+            solution.iterations()
+        };
+        let plain = iterations(None);
+        let jacobi = Jacobi::new(&a).unwrap();
+        let ssor = Ssor::new(&a, 1.).unwrap();
+        let ic = IncompleteCholesky::new(&a).unwrap();
+        assert!(iterations(Some(&jacobi)) <= plain);
+        assert!(iterations(Some(&ssor)) < plain);
+        assert!(iterations(Some(&ic)) < plain);
+    }
+
+    #[test]
+    fn sparse_laplacian() {
+        let a = CsrMatrix::from(&laplacian_2d(12));
+        let b = Vector::from(vec![1.; 144]);
+        let ic = IncompleteCholesky::new(&a).unwrap();
+        // L keeps the pattern of the lower triangle, where L L^T matches A.
+        assert_eq!((a.nnz() + 144) / 2, ic.l().nnz());
+        let llt = ic.l().mul_mat(&ic.l().transpose());
+        for i in 0..144 {
+            for (j, v) in a.row(i) {
+                assert!((llt.get(i, j) - v).abs() < 1e-12);
+            }
+        }
+        let jacobi = Jacobi::new(&a).unwrap();
+        let ssor = Ssor::new(&a, 1.).unwrap();
+        let plain = cg(&a, &b, IterativeOptions::default().tol(1e-10))
+            .unwrap()
+            .iterations();
+        for m in [&jacobi as &dyn Preconditioner<f64>, &ssor, &ic] {
+            let options = IterativeOptions::default().tol(1e-10).preconditioner(m);
+            let solution = cg(&a, &b, options).unwrap();
+            assert_solves(&a, solution.solution(), &b, 1e-10);
+            assert!(solution.iterations() <= plain);
+        }
+    }
+
+    #[test]
+    fn preconditioner_errors() {
+        let a = Matrix::from([[0., 1.], [1., 0.]]);
+        assert_eq!(LinalgError::Singular, Jacobi::new(&a).unwrap_err());
+        assert_eq!(LinalgError::Singular, Ssor::new(&a, 1.).unwrap_err());
+        assert_eq!(
+            LinalgError::NotPositiveDefinite { pivot: 0 },
+            IncompleteCholesky::new(&a).unwrap_err()
+        );
+        assert!(Jacobi::new(&Matrix::from([[1., 2.]])).is_err());
+        let missing = CsrMatrix::from(&Matrix::from([[1., 0.], [1., 0.]]));
+        assert_eq!(LinalgError::Singular, Jacobi::new(&missing).unwrap_err());
+        assert_eq!(
+            LinalgError::NotPositiveDefinite { pivot: 1 },
+            IncompleteCholesky::new(&missing).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn ssor_rejects_bad_omega() {
+        for omega in [0., 2., -1., f64::NAN] {
+            assert_eq!(
+                LinalgError::InvalidArgument("the SSOR relaxation factor must lie in (0, 2)"),
+                Ssor::new(&laplacian(3), omega).unwrap_err()
+            );
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! Dense linear algebra over generic scalars.
+//!
+//! The crate is organised around two owning containers, [`Vector`] and
+//! [`Matrix`], plus a handful of free functions that operate on them.
+//! [`MatrixView`] and [`VectorView`] borrow strided blocks, rows, columns
+//! and diagonals of them without copying, and the [`blas`] module exposes
+//! the same kernels under their BLAS names.
+
+use std::ops::{Add, Mul, Sub};
+
+pub mod blas;
+pub mod decomposition;
+pub mod error;
+pub mod fixed;
+pub mod iterative;
+pub mod matrix;
+pub mod scalar;
+mod simd;
+pub mod sparse;
+pub mod vector;
+
+pub use num_complex::Complex;
+
+pub use crate::decomposition::{
+    Cholesky, Eigen, Ldlt, Lstsq, LstsqOptions, Lu, Qr, Schur, SolveMethod, SolveOptions, Svd,
+    SymmetricEigen,
+};
+pub use crate::error::LinalgError;
+pub use crate::fixed::{Matrix2, Matrix3, Matrix4, SMatrix, SVector, Vector2, Vector3, Vector4};
+pub use crate::matrix::{
+    gemm, gemm_with, try_gemm_with, GemmOptions, Layout, Matrix, MatrixView, MatrixViewMut,
+    Transpose,
+};
+pub use crate::scalar::{Field, RealField, Ring, Scalar};
+pub use crate::sparse::{CooMatrix, CscMatrix, CsrMatrix};
+pub use crate::vector::{Vector, VectorView, VectorViewMut};
+
+use crate::error::check_dimension;
+
+// MANDATORY -- ex01
+/// Computes `coefs[0] * u[0] + coefs[1] * u[1] + ...`.
+///
+/// Panics if `u` and `coefs` differ in length.
+pub fn linear_combination<K>(u: &[Vector<K>], coefs: &[K]) -> Vector<K>
+where
+    K: Scalar,
+{
+    assert_eq!(
+        u.len(),
+        coefs.len(),
+        "Lengths of u and coefs must be the same"
+    );
+
+    let mut result = Vector::from(vec![K::zero(); u[0].size()]);
+    for (v, &coef) in u.iter().zip(coefs) {
+        blas::axpy(coef, v.view(), result.view_mut());
+    }
+    result
+}
+
+/// Fallible [`linear_combination`]: fails when `u` is empty, when `u` and
+/// `coefs` differ in length, or when the vectors differ in size.
+pub fn try_linear_combination<K>(u: &[Vector<K>], coefs: &[K]) -> Result<Vector<K>, LinalgError>
+where
+    K: Scalar,
+{
+    let first = u.first().ok_or(LinalgError::Empty)?;
+    check_dimension(u.len(), coefs.len())?;
+    for v in u {
+        check_dimension(first.size(), v.size())?;
+    }
+    Ok(linear_combination(u, coefs))
+}
+// END of ex01
+
+// MANDATORY -- ex02
+/// Linear interpolation between `u` (at `t = 0`) and `v` (at `t = 1`).
+pub fn lerp<V>(u: V, v: V, t: f32) -> V
+where
+    V: Add<Output = V> + Sub<Output = V> + Mul<f32, Output = V>,
+{
+    let factor = 1.0 - t;
+    u * factor + v * t
+}
+// END of ex02
+
+// MANDATORY -- ex05
+// NOTE -- The usagge of the standard library's cos function is forbidden.
+/// Cosine of the angle between `u` and `v`.
+///
+/// For complex vectors this is the real part of their Hermitian inner
+/// product over the product of their norms.
+pub fn angle_cos<K>(u: &Vector<K>, v: &Vector<K>) -> K::Real
+where
+    K: Field,
+{
+    u.dotc(v).re() / (u.norm() * v.norm())
+}
+
+/// Fallible [`angle_cos`]: fails when the vectors differ in size.
+pub fn try_angle_cos<K>(u: &Vector<K>, v: &Vector<K>) -> Result<K::Real, LinalgError>
+where
+    K: Field,
+{
+    check_dimension(u.size(), v.size())?;
+    Ok(angle_cos(u, v))
+}
+// END of ex05
+
+// MANDATORY -- ex06
+// NOTE -- If one or both vectors are not 3-dimensional, the behavior is undefined.
+/// Cross product of two 3-dimensional vectors.
+///
+/// Panics if either vector is not 3-dimensional; [`SVector::cross`] checks
+/// this at compile time instead.
+pub fn cross_product<K>(u: &Vector<K>, v: &Vector<K>) -> Vector<K>
+where
+    K: Scalar,
+{
+    assert_eq!(u.size(), 3, "u must be 3-dimensional");
+    assert_eq!(v.size(), 3, "v must be 3-dimensional");
+    let s1 = (u.e[1] * v.e[2]) - (u.e[2] * v.e[1]);
+    let s2 = (u.e[2] * v.e[0]) - (u.e[0] * v.e[2]);
+    let s3 = (u.e[0] * v.e[1]) - (u.e[1] * v.e[0]);
+    Vector::from([s1, s2, s3])
+}
+
+/// Fallible [`cross_product`]: fails when either vector is not
+/// 3-dimensional.
+pub fn try_cross_product<K>(u: &Vector<K>, v: &Vector<K>) -> Result<Vector<K>, LinalgError>
+where
+    K: Scalar,
+{
+    check_dimension(3, u.size())?;
+    check_dimension(3, v.size())?;
+    Ok(cross_product(u, v))
+}
+// END of ex06
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_linear_combination() {
+        let e1 = Vector::from([1., 0., 0.]);
+        let e2 = Vector::from([0., 1., 0.]);
+        let e3 = Vector::from([0., 0., 1.]);
+        let v1 = Vector::from([1., 2., 3.]);
+        let v2 = Vector::from([0., 10., -100.]);
+        assert_eq!(
+            vec![10., -2., 0.5],
+            linear_combination::<f32>(&[e1, e2, e3], &[10., -2., 0.5]).e
+        );
+        assert_eq!(
+            vec![10., 0., 230.],
+            linear_combination::<f32>(&[v1.clone(), v2.clone()], &[10., -2.]).e
+        );
+
+        println!("{}", lerp(21., 42., 0.3));
+        println!(
+            "{}",
+            lerp(Vector::from([2., 1.]), Vector::from([4., 2.]), 0.3)
+        );
+
+        println!(
+            "{}",
+            lerp(
+                Matrix::from([[2., 1.], [4., 2.]]),
+                Matrix::from([[20., 1.], [30., 1.]]),
+                0.3
+            )
+        );
+    }
+
+    #[test]
+    fn try_linear_combination_errors() {
+        let u = Vector::from([1., 2.]);
+        let v = Vector::from([1., 2., 3.]);
+        assert_eq!(
+            Ok(vec![3., 6.]),
+            try_linear_combination(&[u.clone(), u.clone()], &[1., 2.]).map(|r| r.e)
+        );
+        assert_eq!(
+            Err(LinalgError::Empty),
+            try_linear_combination::<f32>(&[], &[])
+        );
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 1,
+                found: 2
+            }),
+            try_linear_combination(std::slice::from_ref(&u), &[1., 2.])
+        );
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 2,
+                found: 3
+            }),
+            try_linear_combination(&[u, v], &[1., 2.])
+        );
+    }
+
+    #[test]
+    fn cosine_tests() {
+        let u = Vector::from(vec![1f64, 0.]);
+        let v = Vector::from(vec![1., 0.]);
+        assert!((angle_cos(&u, &v) - 1.0).abs() < f64::EPSILON * 100.);
+        // 1.0
+        let u = Vector::from(vec![1f64, 0.]);
+        let v = Vector::from(vec![0., 1.]);
+        assert!((angle_cos(&u, &v) - 0.0).abs() < f64::EPSILON * 100.);
+        // 0.0
+        let u = Vector::from(vec![-1f64, 1.]);
+        let v = Vector::from(vec![1., -1.]);
+        assert!((angle_cos(&u, &v) + 1.0).abs() < f64::EPSILON * 100.);
+        // -1.0
+        let u = Vector::from(vec![2f64, 1.]);
+        let v = Vector::from(vec![4., 2.]);
+        assert!((angle_cos(&u, &v) - 1.0).abs() < f64::EPSILON * 100.);
+        // 1.0
+        let u = Vector::from(vec![1f64, 2., 3.]);
+        let v = Vector::from(vec![4., 5., 6.]);
+        assert!((angle_cos(&u, &v) - 0.974_631_846_197_076_2).abs() < f64::EPSILON * 100.);
+        // 0.974631846
+
+        //// undefined
+        // let u = Vector::from(vec![1., 2.]);
+        // let v = Vector::from(vec![4., 5., 6.]);
+        // println!("{}", angle_cos(&u, &v)); // PANIC
+
+        //// NaN
+        // let u = Vector::from(vec![]);
+        // let v = Vector::from(vec![4., 5., 6.]);
+        // println!("{}", angle_cos(&u, &v)); // PANIC
+
+        // let u = Vector::from(vec![1.]);
+        // let v = Vector::from(vec![]);
+        // println!("{}", angle_cos(&u, &v)); // PANIC
+    }
+
+    #[test]
+    fn complex_cosine() {
+        let u = Vector::from([Complex::new(1., 1.), Complex::new(0., 0.)]);
+        let v = Vector::from([Complex::new(0., 2.), Complex::new(0., 0.)]);
+        // <u, v> = (1 - i) * 2i = 2 + 2i
+        assert!((angle_cos(&u, &v) - 0.5_f64.sqrt()).abs() < f64::EPSILON * 100.);
+        assert!((angle_cos(&u, &u) - 1.).abs() < f64::EPSILON * 100.);
+    }
+
+    #[test]
+    fn cross_product_basics() {
+        let u = Vector::from([0., 0., 1.]);
+        let v = Vector::from([1., 0., 0.]);
+        assert_eq!(vec![0., 1., 0.], cross_product(&u, &v).e);
+
+        let u = Vector::from([1., 2., 3.]);
+        let v = Vector::from([4., 5., 6.]);
+        assert_eq!(vec![-3., 6., -3.], cross_product(&u, &v).e);
+
+        let u = Vector::from([4., 2., -3.]);
+        let v = Vector::from([-2., -5., 16.]);
+        assert_eq!(vec![17., -58., -16.], cross_product(&u, &v).e);
+
+        assert!(try_cross_product(&u, &v).is_ok());
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }),
+            try_cross_product(&Vector::from([1., 2.]), &v)
+        );
+        assert!(try_angle_cos(&Vector::from([1., 2.]), &v).is_err());
+    }
+}
@@ -1,183 +1,277 @@
-// use std::ops::{AddAssign, MulAssign};
-
-use std::ops::{Add, AddAssign, Mul, MulAssign, Sub};
-
-use num_traits::Float;
-
-pub use crate::matrix::Matrix;
-pub use crate::vector::Vector;
-
-mod matrix;
-mod vector;
-
-// MANDATORY -- ex01
-#[allow(dead_code)]
-fn linear_combination<K>(u: &[Vector<K>], coefs: &[K]) -> Vector<K>
-where
-    K: MulAssign + AddAssign + Copy + Clone + Default,
-{
-    assert_eq!(
-        u.len(),
-        coefs.len(),
-        "Lengths of u and coefs must be the same"
-    );
-
-    let mut result = Vector::from(vec![K::default(); u[0].size()]);
-    u.to_vec()
-        .iter_mut()
-        .zip(coefs.iter())
-        .for_each(|(v, &coef)| {
-            v.scl(coef);
-            result.add_mut(v);
-        });
-    result
+use std::env;
+use std::error::Error;
+use std::process::ExitCode;
+
+use matrix::{
+    lerp, try_angle_cos, try_cross_product, try_linear_combination, LinalgError, Matrix, Vector,
+};
+
+const USAGE: &str = "\
+usage: matrix <command> [operands...]
+
+Vectors are written as comma-separated numbers (`1,2,3`), matrices
+separate their rows with semicolons (`1,2;3,4`).
+
+commands:
+  add <u> <v>                    sum of two vectors or matrices
+  sub <u> <v>                    difference of two vectors or matrices
+  scl <u> <a>                    vector or matrix scaled by a
+  lerp <u> <v> <t>               linear interpolation between u and v
+  dot <u> <v>                    dot product of two vectors
+  norm <u>                       taxicab, euclidean and supremum norms
+  cos <u> <v>                    cosine of the angle between two vectors
+  cross <u> <v>                  cross product of two 3-dimensional vectors
+  lincomb <u> <a> [<v> <b> ...]  linear combination a * u + b * v + ...
+  mul <m> <u>                    product of a matrix and a vector or matrix
+  trace <m>                      sum of the diagonal of a square matrix
+  transpose <m>                  transpose of a matrix
+  rref <m>                       reduced row echelon form of a matrix
+  det <m>                        determinant of a square matrix
+  inverse <m>                    inverse of a square matrix
+  rank <m>                       rank of a matrix
+  solve <m> <b>                  solution of m x = b, least squares if m
+                                 is not square";
+
+#[derive(Debug)]
+enum Operand {
+    Vector(Vector<f32>),
+    Matrix(Matrix<f32>),
+}
+
+fn parse_scalar(s: &str) -> Result<f32, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("`{}` is not a number", s.trim()))
 }
-// END of ex01
-
-// #[allow(dead_code)]
-// fn linear_combination<K>(u: &[Vector<K>], coefs: &[K]) -> Result<Vector<K>, &'static str>
-// where
-//     K: MulAssign + AddAssign + Copy + Clone + Default,
-// {
-//     if u.len() != coefs.len() {
-//         return Err("Lengths of u and coefs must be the same");
-//     }
-
-//     let mut result = Vector::from(vec![K::default(); u[0].size()]);
-//     u.to_vec()
-//         .iter_mut()
-//         .zip(coefs.iter())
-//         .for_each(|(v, &coef)| {
-//             v.scl(coef);
-//             result.add_mut(v);
-//         });
-//     Ok(result)
-// }
-
-// MANDATORY -- ex02
-// linear interpolation
-#[allow(dead_code)]
-fn lerp<V>(u: V, v: V, t: f32) -> V
-where
-    V: Add<Output = V> + Sub<Output = V> + Mul<f32, Output = V>,
-{
-    let factor = 1.0 - t;
-    u * factor + v * t
+
+fn parse_vector(s: &str) -> Result<Vector<f32>, String> {
+    s.split(',')
+        .map(parse_scalar)
+        .collect::<Result<Vec<_>, _>>()
+        .map(Vector::from)
 }
-// END of ex02
-
-// MANDATORY -- ex05
-// NOTE -- The usagge of the standard library's cos function is forbidden.
-#[allow(dead_code)]
-fn angle_cos<K>(u: &Vector<K>, v: &Vector<K>) -> f32
-where
-    K: Default + Mul<Output = K> + AddAssign + Copy + Float,
-    f32: AddAssign<K>,
-{
-    u.dot(v).to_f32().unwrap() / (u.clone().norm() * v.clone().norm())
+
+fn parse_operand(s: &str) -> Result<Operand, String> {
+    if !s.contains(';') {
+        return parse_vector(s).map(Operand::Vector);
+    }
+    let rows = s
+        .split(';')
+        .map(|row| parse_vector(row).map(|v| v.e))
+        .collect::<Result<Vec<_>, _>>()?;
+    Matrix::try_from_rows(rows)
+        .map(Operand::Matrix)
+        .map_err(|e| format!("`{}`: {}", s, e))
 }
-// END of ex05
-
-// MANDATORY -- ex06
-// NOTE -- If one or both vectors are not 3-dimensional, the behavior is undefined.
-#[allow(dead_code)]
-fn cross_product<K>(u: &Vector<K>, v: &Vector<K>) -> Vector<K>
-where
-    K: Sub<Output = K> + Mul<Output = K> + Copy,
-{
-    assert_eq!(u.size(), 3, "u must be 3-dimensional");
-    assert_eq!(v.size(), 3, "v must be 3-dimensional");
-    let s1 = (u.e[1] * v.e[2]) - (u.e[2] * v.e[1]);
-    let s2 = (u.e[2] * v.e[0]) - (u.e[0] * v.e[2]);
-    let s3 = (u.e[0] * v.e[1]) - (u.e[1] * v.e[0]);
-    Vector::from([s1, s2, s3])
+
+fn expect_vector(s: &str) -> Result<Vector<f32>, String> {
+    match parse_operand(s)? {
+        Operand::Vector(v) => Ok(v),
+        Operand::Matrix(_) => Err(format!("`{}` must be a vector", s)),
+    }
+}
+
+fn expect_matrix(s: &str) -> Result<Matrix<f32>, String> {
+    match parse_operand(s)? {
+        Operand::Matrix(m) => Ok(m),
+        Operand::Vector(v) => Ok(Matrix::from(vec![v.e])),
+    }
+}
+
+fn check_sizes(u: &Vector<f32>, v: &Vector<f32>) -> Result<(), String> {
+    if u.size() != v.size() {
+        return Err(LinalgError::DimensionMismatch {
+            expected: u.size(),
+            found: v.size(),
+        }
+        .to_string());
+    }
+    Ok(())
+}
+
+fn check_shapes(u: &Matrix<f32>, v: &Matrix<f32>) -> Result<(), String> {
+    if u.shape() != v.shape() {
+        return Err(LinalgError::ShapeMismatch {
+            expected: u.shape(),
+            found: v.shape(),
+        }
+        .to_string());
+    }
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<String, Box<dyn Error>> {
+    let (command, operands) = args.split_first().ok_or(USAGE)?;
+    let operands: Vec<&str> = operands.iter().map(String::as_str).collect();
+
+    let output = match (command.as_str(), operands.as_slice()) {
+        ("add" | "sub", [u, v]) => {
+            let add = command == "add";
+            match (parse_operand(u)?, parse_operand(v)?) {
+                (Operand::Vector(u), Operand::Vector(v)) if add => u.try_add(&v)?.to_string(),
+                (Operand::Vector(u), Operand::Vector(v)) => u.try_sub(&v)?.to_string(),
+                (Operand::Matrix(mut u), Operand::Matrix(v)) => {
+                    if add {
+                        u.try_add_mut(&v)?;
+                    } else {
+                        u.try_sub_mut(&v)?;
+                    }
+                    u.to_string()
+                }
+                _ => return Err("cannot mix vectors and matrices".into()),
+            }
+        }
+        ("scl", [u, a]) => {
+            let a = parse_scalar(a)?;
+            match parse_operand(u)? {
+                Operand::Vector(mut u) => {
+                    u.scl(a);
+                    u.to_string()
+                }
+                Operand::Matrix(mut u) => {
+                    u.scl(a);
+                    u.to_string()
+                }
+            }
+        }
+        ("lerp", [u, v, t]) => {
+            let t = parse_scalar(t)?;
+            match (parse_operand(u)?, parse_operand(v)?) {
+                (Operand::Vector(u), Operand::Vector(v)) => {
+                    check_sizes(&u, &v)?;
+                    lerp(u, v, t).to_string()
+                }
+                (Operand::Matrix(u), Operand::Matrix(v)) => {
+                    check_shapes(&u, &v)?;
+                    lerp(u, v, t).to_string()
+                }
+                _ => return Err("cannot mix vectors and matrices".into()),
+            }
+        }
+        ("dot", [u, v]) => expect_vector(u)?.try_dot(&expect_vector(v)?)?.to_string(),
+        ("norm", [u]) => {
+            let u = expect_vector(u)?;
+            format!("{} {} {}", u.norm_1(), u.norm(), u.norm_inf())
+        }
+        ("cos", [u, v]) => try_angle_cos(&expect_vector(u)?, &expect_vector(v)?)?.to_string(),
+        ("cross", [u, v]) => try_cross_product(&expect_vector(u)?, &expect_vector(v)?)?.to_string(),
+        ("lincomb", pairs) if !pairs.is_empty() && pairs.len().is_multiple_of(2) => {
+            let mut vectors = Vec::with_capacity(pairs.len() / 2);
+            let mut coefs = Vec::with_capacity(pairs.len() / 2);
+            for pair in pairs.chunks(2) {
+                vectors.push(expect_vector(pair[0])?);
+                coefs.push(parse_scalar(pair[1])?);
+            }
+            try_linear_combination(&vectors, &coefs)?.to_string()
+        }
+        ("mul", [m, u]) => {
+            let m = expect_matrix(m)?;
+            match parse_operand(u)? {
+                Operand::Vector(u) => m.try_mul_vec(&u)?.to_string(),
+                Operand::Matrix(u) => m.try_mul_mat(&u)?.to_string(),
+            }
+        }
+        ("trace", [m]) => expect_matrix(m)?.try_trace()?.to_string(),
+        ("transpose", [m]) => expect_matrix(m)?.transpose().to_string(),
+        ("rref", [m]) => expect_matrix(m)?.row_echelon().to_string(),
+        ("det", [m]) => expect_matrix(m)?.try_determinant()?.to_string(),
+        ("inverse", [m]) => expect_matrix(m)?.inverse()?.to_string(),
+        ("rank", [m]) => expect_matrix(m)?.rank().to_string(),
+        ("solve", [m, b]) => expect_matrix(m)?.solve(&expect_vector(b)?)?.to_string(),
+        _ => return Err(USAGE.into()),
+    };
+    Ok(output)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(&args) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::from(2)
+        }
+    }
 }
-// END of ex06
 
 #[cfg(test)]
 mod tests {
-    use std::f32::EPSILON;
-
     use super::*;
 
+    fn run_str(args: &[&str]) -> Result<String, Box<dyn Error>> {
+        run(&args.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+    }
+
     #[test]
-    pub fn test_linear_combination() {
-        let e1 = Vector::from([1., 0., 0.]);
-        let e2 = Vector::from([0., 1., 0.]);
-        let e3 = Vector::from([0., 0., 1.]);
-        let v1 = Vector::from([1., 2., 3.]);
-        let v2 = Vector::from([0., 10., -100.]);
-        println!(
-            "{:#?}",
-            linear_combination::<f32>(&[e1, e2, e3], &[10., -2., 0.5])
-        );
-        // [10.]
-        // [-2.]
-        // [0.5]
-        println!(
-            "{:?}",
-            linear_combination::<f32>(&[v1.clone(), v2.clone()], &[10., -2.])
+    fn parse_operands() {
+        assert!(matches!(parse_operand("1,2,3"), Ok(Operand::Vector(v)) if v.e == [1., 2., 3.]));
+        assert!(matches!(parse_operand("1,2;3,4"), Ok(Operand::Matrix(m)) if m.shape() == (2, 2)));
+        assert!(parse_operand("1,2;3").is_err());
+        assert!(parse_operand("1,x").is_err());
+    }
+
+    #[test]
+    fn run_commands() {
+        assert_eq!("[3.0]\n[5.0]", run_str(&["add", "1,2", "2,3"]).unwrap());
+        assert_eq!("8", run_str(&["dot", "1,2", "2,3"]).unwrap());
+        assert_eq!(
+            "[-3.0]\n[6.0]\n[-3.0]",
+            run_str(&["cross", "1,2,3", "4,5,6"]).unwrap()
         );
-        // [10.]
-        // [0.]
-        // [230.]
-
-        println!("{}", lerp(21., 42., 0.3));
-        println!(
-            "{}",
-            lerp(Vector::from([2., 1.]), Vector::from([4., 2.]), 0.3)
+        assert_eq!(
+            "[10.0]\n[0.0]\n[230.0]",
+            run_str(&["lincomb", "1,2,3", "10", "0,10,-100", "-2"]).unwrap()
         );
-
-        println!(
-            "{}",
-            lerp(
-                Matrix::from([[2., 1.], [4., 2.]]),
-                Matrix::from([[20., 1.], [30., 1.]]),
-                0.3
-            )
+        assert_eq!(
+            "[2.0, 4.0]\n[6.0, 8.0]",
+            run_str(&["scl", "1,2;3,4", "2"]).unwrap()
         );
     }
 
     #[test]
-    fn cosine_tests() {
-        let u = Vector::from(vec![1., 0.]);
-        let v = Vector::from(vec![1., 0.]);
-        assert_eq!((angle_cos(&u, &v) - 1.0).abs() < EPSILON * 100., true);
-        // 1.0
-        let u = Vector::from(vec![1., 0.]);
-        let v = Vector::from(vec![0., 1.]);
-        assert_eq!((angle_cos(&u, &v) - 0.0).abs() < EPSILON * 100., true);
-        // 0.0
-        let u = Vector::from(vec![-1., 1.]);
-        let v = Vector::from(vec![1., -1.]);
-        assert_eq!((angle_cos(&u, &v) + 1.0).abs() < EPSILON * 100., true);
-        // -1.0
-        let u = Vector::from(vec![2., 1.]);
-        let v = Vector::from(vec![4., 2.]);
-        assert_eq!((angle_cos(&u, &v) - 1.0).abs() < EPSILON * 100., true);
-        // 1.0
-        let u = Vector::from(vec![1., 2., 3.]);
-        let v = Vector::from(vec![4., 5., 6.]);
+    fn run_matrix_commands() {
+        assert_eq!("5", run_str(&["trace", "1,2;3,4"]).unwrap());
         assert_eq!(
-            (angle_cos(&u, &v) - 0.974631846).abs() < EPSILON * 100.,
-            true
+            "[5.0]\n[11.0]",
+            run_str(&["mul", "1,2;3,4", "1,2"]).unwrap()
         );
-        // 0.974631846
-
-        //// undefined
-        // let u = Vector::from(vec![1., 2.]);
-        // let v = Vector::from(vec![4., 5., 6.]);
-        // println!("{}", angle_cos(&u, &v)); // PANIC
-
-        //// NaN
-        // let u = Vector::from(vec![]);
-        // let v = Vector::from(vec![4., 5., 6.]);
-        // println!("{}", angle_cos(&u, &v)); // PANIC
+        assert_eq!(
+            "[7.0, 10.0]\n[15.0, 22.0]",
+            run_str(&["mul", "1,2;3,4", "1,2;3,4"]).unwrap()
+        );
+        assert!(run_str(&["mul", "1,2;3,4", "1,2,3"]).is_err());
+        assert_eq!(
+            "[1.0, 3.0]\n[2.0, 4.0]",
+            run_str(&["transpose", "1,2;3,4"]).unwrap()
+        );
+        assert_eq!("-2", run_str(&["det", "1,2;3,4"]).unwrap());
+        assert_eq!("1", run_str(&["rank", "1,2;2,4"]).unwrap());
+        assert_eq!(
+            "[1.0, 2.0]\n[0.0, 0.0]",
+            run_str(&["rref", "1,2;2,4"]).unwrap()
+        );
+        assert_eq!(
+            "[0.5, 0.0]\n[0.0, 0.5]",
+            run_str(&["inverse", "2,0;0,2"]).unwrap()
+        );
+        assert!(run_str(&["inverse", "1,2;2,4"]).is_err());
+        assert_eq!(
+            "[1.0]\n[0.5]",
+            run_str(&["solve", "2,0;0,4", "2,2"]).unwrap()
+        );
+        assert!(run_str(&["solve", "1,2;2,4", "1,1"]).is_err());
+        assert!(run_str(&["det", "1,2,3"]).is_err());
+    }
 
-        // let u = Vector::from(vec![1.]);
-        // let v = Vector::from(vec![]);
-        // println!("{}", angle_cos(&u, &v)); // PANIC
+    #[test]
+    fn run_rejects_bad_input() {
+        assert!(run_str(&[]).is_err());
+        assert!(run_str(&["dot", "1,2", "1,2,3"]).is_err());
+        assert!(run_str(&["add", "1,2", "1,2;3,4"]).is_err());
+        assert!(run_str(&["cross", "1,2", "3,4"]).is_err());
+        assert!(run_str(&["lincomb", "1,2"]).is_err());
     }
 }
-
-fn main() {}
@@ -0,0 +1,614 @@
+use crate::error::{check_dimension, check_shape, LinalgError};
+use crate::scalar::Scalar;
+use crate::simd::{self, TileKernel};
+
+use super::{MatrixView, MatrixViewMut};
+
+/// Rows of the register block of the generic micro-kernel.
+const MR: usize = 4;
+/// Columns of the register block of the generic micro-kernel.
+const NR: usize = 4;
+
+/// How [`gemm_with`] reads an operand, as in the BLAS `TRANS` arguments.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transpose {
+    /// `op(X) = X`
+    #[default]
+    NoTrans,
+    /// `op(X) = X^T`
+    Trans,
+    /// `op(X) = X^H`
+    ConjTrans,
+}
+
+/// Operand flags and blocking parameters of [`gemm_with`].
+///
+/// The product is computed one `mc x kc` block of `op(A)` by one
+/// `kc x nc` block of `op(B)` at a time, both copied into contiguous
+/// buffers first, by a register-blocked micro-kernel that uses SIMD for
+/// `f32` and `f64`. The defaults keep a block of `A` in a typical L2 cache
+/// and a sliver of `B` in L1 for `f64`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GemmOptions {
+    pub trans_a: Transpose,
+    pub trans_b: Transpose,
+    /// Rows of `op(A)` per packed block. Defaults to 128.
+    pub mc: usize,
+    /// Inner dimension per packed block. Defaults to 256.
+    pub kc: usize,
+    /// Columns of `op(B)` per packed block. Defaults to 4096.
+    pub nc: usize,
+    /// Threads sharing the columns of `C` when the crate is built with the
+    /// `parallel` feature, ignored otherwise. They are started once per
+    /// call. Defaults to the available parallelism.
+    pub threads: Option<usize>,
+}
+
+impl Default for GemmOptions {
+    fn default() -> Self {
+        GemmOptions {
+            trans_a: Transpose::NoTrans,
+            trans_b: Transpose::NoTrans,
+            mc: 128,
+            kc: 256,
+            nc: 4096,
+            threads: None,
+        }
+    }
+}
+
+impl GemmOptions {
+    pub fn trans_a(mut self, trans: Transpose) -> Self {
+        self.trans_a = trans;
+        self
+    }
+
+    pub fn trans_b(mut self, trans: Transpose) -> Self {
+        self.trans_b = trans;
+        self
+    }
+
+    /// Sets `mc`, `kc` and `nc`.
+    pub fn tiles(mut self, mc: usize, kc: usize, nc: usize) -> Self {
+        self.mc = mc;
+        self.kc = kc;
+        self.nc = nc;
+        self
+    }
+
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+}
+
+/// `C = alpha A B + beta C`; see [`gemm_with`].
+pub fn gemm<K: Scalar>(alpha: K, a: MatrixView<K>, b: MatrixView<K>, beta: K, c: MatrixViewMut<K>) {
+    gemm_with(alpha, a, b, beta, c, &GemmOptions::default());
+}
+
+/// Panicking version of [`try_gemm_with`].
+pub fn gemm_with<K: Scalar>(
+    alpha: K,
+    a: MatrixView<K>,
+    b: MatrixView<K>,
+    beta: K,
+    c: MatrixViewMut<K>,
+    options: &GemmOptions,
+) {
+    try_gemm_with(alpha, a, b, beta, c, options).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// General matrix product `C = alpha op(A) op(B) + beta C`, the BLAS
+/// `xGEMM` contract, where `op` is set by `options.trans_a` and
+/// `options.trans_b`.
+///
+/// When `beta` is zero `C` is only written, so it may hold NaNs. Fails with
+/// `DimensionMismatch` when the inner dimensions of `op(A)` and `op(B)`
+/// differ, or with `ShapeMismatch` when `C` does not have the shape of
+/// their product.
+///
+/// # Panics
+///
+/// If a tile size is zero.
+pub fn try_gemm_with<K: Scalar>(
+    alpha: K,
+    a: MatrixView<K>,
+    b: MatrixView<K>,
+    beta: K,
+    mut c: MatrixViewMut<K>,
+    options: &GemmOptions,
+) -> Result<(), LinalgError> {
+    assert!(
+        options.mc > 0 && options.kc > 0 && options.nc > 0,
+        "GEMM tile sizes must be positive"
+    );
+    let a = Operand::new(a, options.trans_a);
+    let b = Operand::new(b, options.trans_b);
+    check_dimension(a.cols(), b.rows())?;
+    check_shape((a.rows(), b.cols()), c.shape())?;
+    if beta.is_zero() {
+        c.fill(K::zero());
+    } else if !beta.is_one() {
+        c.scl(beta);
+    }
+    if alpha.is_zero() || a.cols() == 0 {
+        return Ok(());
+    }
+    let product = Product {
+        a: &a,
+        b: &b,
+        alpha,
+        kernel: Kernel(simd::tile_kernel()),
+        options,
+    };
+    product.run(c, threads(options, b.cols(), product.kernel.nr()));
+    Ok(())
+}
+
+/// Threads worth starting for `n` columns, `nr` at a time.
+#[cfg(feature = "parallel")]
+fn threads(options: &GemmOptions, n: usize, nr: usize) -> usize {
+    let available = options.threads.unwrap_or_else(|| {
+        std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+    });
+    available.clamp(1, n.div_ceil(nr).max(1))
+}
+
+#[cfg(not(feature = "parallel"))]
+fn threads(_: &GemmOptions, _: usize, _: usize) -> usize {
+    1
+}
+
+/// `op(X)` for an operand `X`.
+struct Operand<'a, K> {
+    /// `X` or `X^T`, as a view.
+    view: MatrixView<'a, K>,
+    conj: bool,
+}
+
+impl<'a, K: Scalar> Operand<'a, K> {
+    fn new(x: MatrixView<'a, K>, trans: Transpose) -> Self {
+        match trans {
+            Transpose::NoTrans => Operand {
+                view: x,
+                conj: false,
+            },
+            Transpose::Trans => Operand {
+                view: x.transpose_view(),
+                conj: false,
+            },
+            Transpose::ConjTrans => Operand {
+                view: x.transpose_view(),
+                conj: true,
+            },
+        }
+    }
+
+    fn rows(&self) -> usize {
+        self.view.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.view.cols()
+    }
+
+    fn get(&self, i: usize, j: usize) -> K {
+        let x = self.view[(i, j)];
+        if self.conj {
+            x.conj()
+        } else {
+            x
+        }
+    }
+
+    /// Copies rows `ic..ic + mc` and columns `pc..pc + kc`, scaled by
+    /// `alpha`, as consecutive `mr`-row panels stored column by column,
+    /// padding the last panel with zeros.
+    #[allow(clippy::too_many_arguments)]
+    fn pack_rows(
+        &self,
+        ic: usize,
+        mc: usize,
+        pc: usize,
+        kc: usize,
+        mr: usize,
+        alpha: K,
+        packed: &mut Vec<K>,
+    ) {
+        packed.clear();
+        for ir in (0..mc).step_by(mr) {
+            for p in pc..pc + kc {
+                for i in ic + ir..ic + ir + mr {
+                    packed.push(if i < ic + mc {
+                        alpha * self.get(i, p)
+                    } else {
+                        K::zero()
+                    });
+                }
+            }
+        }
+    }
+
+    /// Copies rows `pc..pc + kc` and columns `jc..jc + nc` as consecutive
+    /// `nr`-column panels stored row by row, padding the last panel with
+    /// zeros.
+    fn pack_cols(
+        &self,
+        pc: usize,
+        kc: usize,
+        jc: usize,
+        nc: usize,
+        nr: usize,
+        packed: &mut Vec<K>,
+    ) {
+        packed.clear();
+        for jr in (0..nc).step_by(nr) {
+            for p in pc..pc + kc {
+                for j in jc + jr..jc + jr + nr {
+                    packed.push(if j < jc + nc {
+                        self.get(p, j)
+                    } else {
+                        K::zero()
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// The SIMD micro-kernel of the CPU for `f32` and `f64`, or
+/// [`micro_kernel`] for other scalars.
+#[derive(Clone, Copy)]
+struct Kernel<K>(Option<TileKernel<K>>);
+
+impl<K: Scalar> Kernel<K> {
+    fn mr(&self) -> usize {
+        self.0.as_ref().map_or(MR, TileKernel::mr)
+    }
+
+    fn nr(&self) -> usize {
+        self.0.as_ref().map_or(NR, TileKernel::nr)
+    }
+
+    /// Writes the row-major `mr x nr` product of an `mr`-row panel and an
+    /// `nr`-column panel to `ab`.
+    fn tile(&self, a: &[K], b: &[K], ab: &mut [K]) {
+        match &self.0 {
+            Some(kernel) => kernel.run(a, b, ab),
+            None => micro_kernel(a, b, ab),
+        }
+    }
+}
+
+/// Everything about the product but `C`, shared by the threads.
+struct Product<'a, K> {
+    a: &'a Operand<'a, K>,
+    b: &'a Operand<'a, K>,
+    alpha: K,
+    kernel: Kernel<K>,
+    options: &'a GemmOptions,
+}
+
+impl<K: Scalar> Product<'_, K> {
+    /// Updates `c` with `threads` threads each taking a band of columns.
+    #[cfg(feature = "parallel")]
+    fn run(&self, c: MatrixViewMut<K>, threads: usize) {
+        if threads <= 1 {
+            return self.update(c, 0);
+        }
+        let band = c
+            .cols()
+            .div_ceil(threads)
+            .next_multiple_of(self.kernel.nr());
+        std::thread::scope(|scope| {
+            let mut rest = c;
+            let mut start = 0;
+            while rest.cols() > band {
+                let (head, tail) = rest.split_at_col(band);
+                scope.spawn(move || self.update(head, start));
+                rest = tail;
+                start += band;
+            }
+            self.update(rest, start);
+        });
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn run(&self, c: MatrixViewMut<K>, _: usize) {
+        self.update(c, 0)
+    }
+
+    /// Updates `c`, which holds the columns of `C` from `first` on.
+    fn update(&self, mut c: MatrixViewMut<K>, first: usize) {
+        let GemmOptions { mc, kc, nc, .. } = *self.options;
+        let (m, n) = c.shape();
+        let k = self.a.cols();
+        let (mr, nr) = (self.kernel.mr(), self.kernel.nr());
+        let (mut packed_a, mut packed_b) = (Vec::new(), Vec::new());
+        let mut ab = vec![K::zero(); mr * nr];
+        for jc in (0..n).step_by(nc) {
+            let nc = nc.min(n - jc);
+            for pc in (0..k).step_by(kc) {
+                let kc = kc.min(k - pc);
+                self.b.pack_cols(pc, kc, first + jc, nc, nr, &mut packed_b);
+                for ic in (0..m).step_by(mc) {
+                    let mc = mc.min(m - ic);
+                    self.a
+                        .pack_rows(ic, mc, pc, kc, mr, self.alpha, &mut packed_a);
+                    let c = c.reborrow().submatrix(ic..ic + mc, jc..jc + nc);
+                    self.macro_kernel(&packed_a, &packed_b, kc, &mut ab, c);
+                }
+            }
+        }
+    }
+
+    /// Adds the product of packed blocks of `op(A)` and `op(B)` to `c`,
+    /// with `ab` as room for one tile.
+    fn macro_kernel(
+        &self,
+        packed_a: &[K],
+        packed_b: &[K],
+        kc: usize,
+        ab: &mut [K],
+        mut c: MatrixViewMut<K>,
+    ) {
+        let (mc, nc) = c.shape();
+        let (mr, nr) = (self.kernel.mr(), self.kernel.nr());
+        for (jr, b) in (0..nc).step_by(nr).zip(packed_b.chunks_exact(nr * kc)) {
+            for (ir, a) in (0..mc).step_by(mr).zip(packed_a.chunks_exact(mr * kc)) {
+                self.kernel.tile(a, b, ab);
+                let (rows, cols) = (mr.min(mc - ir), nr.min(nc - jr));
+                add_tile(ab, nr, c.reborrow().submatrix(ir..ir + rows, jr..jr + cols));
+            }
+        }
+    }
+}
+
+/// Adds to `c` the top-left corner of the row-major tile `ab`, which is
+/// `nr` elements wide, through slices of the rows or columns of `c` when
+/// they are contiguous.
+fn add_tile<K: Scalar>(ab: &[K], nr: usize, mut c: MatrixViewMut<K>) {
+    let (m, n) = c.shape();
+    let (row_stride, col_stride) = c.strides();
+    if col_stride == 1 || n == 1 {
+        for (i, ab) in ab.chunks(nr).take(m).enumerate() {
+            let mut row = c.reborrow().row(i);
+            let row = row.as_mut_slice().unwrap();
+            for (c, &x) in row.iter_mut().zip(ab) {
+                *c += x;
+            }
+        }
+    } else if row_stride == 1 || m == 1 {
+        for j in 0..n {
+            let mut column = c.reborrow().column(j);
+            let column = column.as_mut_slice().unwrap();
+            for (c, ab) in column.iter_mut().zip(ab.chunks(nr)) {
+                *c += ab[j];
+            }
+        }
+    } else {
+        for i in 0..m {
+            for j in 0..n {
+                c[(i, j)] += ab[i * nr + j];
+            }
+        }
+    }
+}
+
+/// The generic [`Kernel::tile`], with an `MR x NR` block kept in
+/// registers.
+#[inline(always)]
+fn micro_kernel<K: Scalar>(a: &[K], b: &[K], ab: &mut [K]) {
+    let mut acc = [[K::zero(); NR]; MR];
+    for (a, b) in a.chunks_exact(MR).zip(b.chunks_exact(NR)) {
+        for i in 0..MR {
+            for j in 0..NR {
+                acc[i][j] += a[i] * b[j];
+            }
+        }
+    }
+    for (ab, acc) in ab.chunks_exact_mut(NR).zip(&acc) {
+        ab.copy_from_slice(acc);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex;
+
+    use super::super::Matrix;
+    use super::*;
+
+    /// The textbook triple loop.
+    fn naive<K: Scalar>(a: &Matrix<K>, b: &Matrix<K>) -> Matrix<K> {
+        Matrix::from_fn(a.rows(), b.cols(), |i, j| {
+            (0..a.cols()).fold(K::zero(), |s, k| s + a[(i, k)] * b[(k, j)])
+        })
+    }
+
+    fn sample(rows: usize, cols: usize, seed: i64) -> Matrix<i64> {
+        Matrix::from_fn(rows, cols, |i, j| {
+            ((i as i64 * 31 + j as i64 * 17 + seed) * 7919 % 23) - 11
+        })
+    }
+
+    #[test]
+    fn matches_naive_product() {
+        // Small tiles so every edge case of the blocking is exercised.
+        let options = GemmOptions::default().tiles(5, 3, 7);
+        for (m, k, n) in [
+            (1, 1, 1),
+            (9, 7, 13),
+            (17, 4, 3),
+            (4, 4, 4),
+            (0, 3, 2),
+            (3, 0, 2),
+        ] {
+            let a = sample(m, k, 1);
+            let b = sample(k, n, 2);
+            let mut c = Matrix::zeros(m, n);
+            gemm_with(1, a.view(), b.view(), 0, c.view_mut(), &options);
+            assert_eq!(naive(&a, &b), c, "{}x{}x{}", m, k, n);
+            assert_eq!(naive(&a, &b), a.mul_mat(&b));
+        }
+    }
+
+    #[test]
+    fn alpha_beta_and_flags() {
+        let a = sample(6, 5, 3);
+        let b = sample(6, 4, 4);
+        let c0 = sample(5, 4, 5);
+        let options = GemmOptions::default()
+            .trans_a(Transpose::Trans)
+            .tiles(2, 2, 3);
+        let mut c = c0.clone();
+        gemm_with(2, a.view(), b.view(), -3, c.view_mut(), &options);
+        let expected = naive(&a.transpose(), &b) * 2 - &(c0.clone() * 3);
+        assert_eq!(expected, c);
+
+        // Both operands transposed, written into a strided block of C.
+        let mut big = Matrix::filled(7, 8, 1);
+        let options = GemmOptions::default()
+            .trans_a(Transpose::Trans)
+            .trans_b(Transpose::Trans);
+        gemm_with(
+            1,
+            a.view(),
+            b.transpose_view(),
+            1,
+            big.submatrix_mut(1..6, 2..6),
+            &options,
+        );
+        assert_eq!(
+            big.submatrix(1..6, 2..6),
+            naive(&a.transpose(), &b) + &Matrix::ones(5, 4)
+        );
+        assert_eq!(1, big[(0, 0)]);
+        assert_eq!(1, big[(6, 7)]);
+    }
+
+    #[test]
+    fn beta_zero_overwrites() {
+        let a = Matrix::from([[1., 2.], [3., 4.]]);
+        let mut c = Matrix::filled(2, 2, f64::NAN);
+        gemm(1., a.view(), a.view(), 0., c.view_mut());
+        assert_eq!(Matrix::from([[7., 10.], [15., 22.]]), c);
+        gemm(0., a.view(), a.view(), 0.5, c.view_mut());
+        assert_eq!(Matrix::from([[3.5, 5.], [7.5, 11.]]), c);
+    }
+
+    #[test]
+    fn conjugate_transpose() {
+        let a = Matrix::from([
+            [Complex::new(1., 1.), Complex::new(0., 2.)],
+            [Complex::new(3., 0.), Complex::new(1., -1.)],
+        ]);
+        let mut c = Matrix::zeros(2, 2);
+        let options = GemmOptions::default().trans_a(Transpose::ConjTrans);
+        gemm_with(
+            Complex::new(1., 0.),
+            a.view(),
+            a.view(),
+            Complex::new(0., 0.),
+            c.view_mut(),
+            &options,
+        );
+        assert_eq!(naive(&a.conjugate_transpose(), &a), c);
+    }
+
+    #[test]
+    fn threads_split_columns() {
+        let a = sample(37, 19, 6);
+        let b = sample(19, 23, 7);
+        for threads in [1, 2, 3, 8] {
+            let mut c = Matrix::zeros(37, 23);
+            let options = GemmOptions::default().tiles(8, 8, 8).threads(threads);
+            gemm_with(1, a.view(), b.view(), 0, c.view_mut(), &options);
+            assert_eq!(naive(&a, &b), c);
+        }
+    }
+
+    #[test]
+    fn simd_kernels() {
+        // Integer-valued floats keep the products exact whatever the order
+        // of the sums.
+        let options = GemmOptions::default().tiles(19, 11, 37);
+        for (m, k, n) in [(1, 1, 1), (7, 3, 5), (23, 29, 41), (64, 16, 33)] {
+            let a = sample(m, k, 1);
+            let b = sample(k, n, 2);
+            let expected = naive(&a, &b);
+            let to_f64 =
+                |x: &Matrix<i64>| Matrix::from_fn(x.rows(), x.cols(), |i, j| x[(i, j)] as f64);
+            let to_f32 =
+                |x: &Matrix<i64>| Matrix::from_fn(x.rows(), x.cols(), |i, j| x[(i, j)] as f32);
+
+            let mut c = Matrix::zeros(m, n);
+            gemm_with(
+                1.,
+                to_f64(&a).view(),
+                to_f64(&b).view(),
+                0.,
+                c.view_mut(),
+                &options,
+            );
+            assert_eq!(to_f64(&expected), c, "{}x{}x{}", m, k, n);
+
+            // C is a transposed block: contiguous columns, strided rows.
+            let mut big = Matrix::filled(n + 2, m + 1, 1f32);
+            let c = big.submatrix_mut(1..n + 1, ..m).transpose_view();
+            let at = to_f32(&a).transpose();
+            let opts = options.clone().trans_a(Transpose::Trans);
+            gemm_with(2., at.view(), to_f32(&b).view(), 1., c, &opts);
+            assert_eq!(
+                Matrix::from_fn(n, m, |j, i| 2. * expected[(i, j)] as f32 + 1.),
+                big.submatrix(1..n + 1, ..m).to_matrix()
+            );
+            assert_eq!(1., big[(0, 0)]);
+        }
+    }
+
+    #[test]
+    fn shape_errors() {
+        let a = sample(2, 3, 0);
+        let mut c = Matrix::zeros(2, 2);
+        let options = GemmOptions::default();
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }),
+            try_gemm_with(1, a.view(), a.view(), 0, c.view_mut(), &options)
+        );
+        let mut wide = Matrix::zeros(2, 3);
+        assert_eq!(
+            Err(LinalgError::ShapeMismatch {
+                expected: (2, 2),
+                found: (2, 3)
+            }),
+            try_gemm_with(
+                1,
+                a.view(),
+                a.transpose_view(),
+                0,
+                wide.view_mut(),
+                &options
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "GEMM tile sizes must be positive")]
+    fn zero_tiles() {
+        let a = sample(2, 2, 0);
+        let mut c = Matrix::zeros(2, 2);
+        gemm_with(
+            1,
+            a.view(),
+            a.view(),
+            0,
+            c.view_mut(),
+            &GemmOptions::default().tiles(0, 1, 1),
+        );
+    }
+}
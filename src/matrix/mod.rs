@@ -1,79 +1,243 @@
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Debug, Display};
 use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
 
+use crate::error::{check_dimension, check_shape, LinalgError};
+use crate::scalar::{Field, Ring, Scalar};
 use crate::vector::Vector;
 
+mod gemm;
+mod view;
+
+pub use gemm::{gemm, gemm_with, try_gemm_with, GemmOptions, Transpose};
+pub use view::{MatrixView, MatrixViewMut};
+
+/// Order in which the elements of a [`Matrix`] are laid out in its buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// Rows are contiguous: element `(i, j)` lives at `i * cols + j`.
+    #[default]
+    RowMajor,
+    /// Columns are contiguous: element `(i, j)` lives at `i + j * rows`.
+    ColMajor,
+}
+
+/// A dense `rows x cols` matrix backed by a single contiguous buffer.
 #[derive(Clone, Debug)]
 pub struct Matrix<K> {
-    pub m: Vector<Vector<K>>,
+    data: Vec<K>,
+    rows: usize,
+    cols: usize,
+    layout: Layout,
 }
 
-impl<K: std::ops::Deref> std::ops::Deref for Matrix<K> {
-    type Target = Vector<Vector<K>>;
+// The by-value operators reuse the buffer of an owned operand; only
+// `&a + &b`, `&a - &b` and `&a * k` allocate, once.
 
-    fn deref(&self) -> &Self::Target {
-        &self.m
+impl<K> Add for Matrix<K>
+where
+    K: Scalar,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self.add_mut(&rhs);
+        self
     }
 }
 
-impl<K> Add for Matrix<K>
+impl<K> Add<&Matrix<K>> for Matrix<K>
 where
-    K: AddAssign + Add<Output = K> + Copy, // or whatever bounds you need
+    K: Scalar,
 {
     type Output = Self;
 
+    fn add(mut self, rhs: &Matrix<K>) -> Self::Output {
+        self.add_mut(rhs);
+        self
+    }
+}
+
+impl<K> Add<Matrix<K>> for &Matrix<K>
+where
+    K: Scalar,
+{
+    type Output = Matrix<K>;
+
+    fn add(self, mut rhs: Matrix<K>) -> Self::Output {
+        rhs.add_mut(self);
+        rhs
+    }
+}
+
+impl<K> Add for &Matrix<K>
+where
+    K: Scalar,
+{
+    type Output = Matrix<K>;
+
     fn add(self, rhs: Self) -> Self::Output {
-        let mut mat = self.clone();
-        for (vec1, vec2) in mat.m.e.iter_mut().zip(rhs.m.e.iter()) {
-            vec1.add_mut(vec2);
-        }
-        mat
+        self.clone() + rhs
     }
 }
 
 impl<K> Sub for Matrix<K>
 where
-    K: SubAssign + Sub<Output = K> + Copy, // or whatever bounds you need
+    K: Scalar,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self.sub_mut(&rhs);
+        self
+    }
+}
+
+impl<K> Sub<&Matrix<K>> for Matrix<K>
+where
+    K: Scalar,
 {
     type Output = Self;
 
+    fn sub(mut self, rhs: &Matrix<K>) -> Self::Output {
+        self.sub_mut(rhs);
+        self
+    }
+}
+
+impl<K> Sub<Matrix<K>> for &Matrix<K>
+where
+    K: Scalar,
+{
+    type Output = Matrix<K>;
+
+    fn sub(self, mut rhs: Matrix<K>) -> Self::Output {
+        assert_eq!(self.shape(), rhs.shape());
+        rhs.zip_apply(self, |b, a| *b = a - *b);
+        rhs
+    }
+}
+
+impl<K> Sub for &Matrix<K>
+where
+    K: Scalar,
+{
+    type Output = Matrix<K>;
+
     fn sub(self, rhs: Self) -> Self::Output {
-        let mut mat = self.clone();
-        for (vec1, vec2) in mat.m.e.iter_mut().zip(rhs.m.e.iter()) {
-            vec1.sub_mut(vec2);
-        }
-        mat
+        self.clone() - rhs
     }
 }
 
 impl<K> Mul<K> for Matrix<K>
 where
-    K: MulAssign + Copy, // or whatever bounds you need
+    K: Scalar,
 {
     type Output = Self;
 
+    fn mul(mut self, rhs: K) -> Self::Output {
+        self.scl(rhs);
+        self
+    }
+}
+
+impl<K> Mul<K> for &Matrix<K>
+where
+    K: Scalar,
+{
+    type Output = Matrix<K>;
+
     fn mul(self, rhs: K) -> Self::Output {
-        let mut mat = self.clone();
-        for vec in &mut mat.m.e {
-            vec.scl(rhs);
-        }
-        mat
+        self.clone() * rhs
+    }
+}
+
+impl<K> Mul<Vector<K>> for Matrix<K>
+where
+    K: Scalar,
+{
+    type Output = Vector<K>;
+
+    fn mul(self, rhs: Vector<K>) -> Self::Output {
+        self.mul_vec(&rhs)
     }
 }
 
+impl<K> Mul<&Vector<K>> for &Matrix<K>
+where
+    K: Scalar,
+{
+    type Output = Vector<K>;
+
+    fn mul(self, rhs: &Vector<K>) -> Self::Output {
+        self.mul_vec(rhs)
+    }
+}
+
+impl<K> Mul<Matrix<K>> for Matrix<K>
+where
+    K: Scalar,
+{
+    type Output = Matrix<K>;
+
+    fn mul(self, rhs: Matrix<K>) -> Self::Output {
+        self.mul_mat(&rhs)
+    }
+}
+
+impl<K> Mul<&Matrix<K>> for &Matrix<K>
+where
+    K: Scalar,
+{
+    type Output = Matrix<K>;
+
+    fn mul(self, rhs: &Matrix<K>) -> Self::Output {
+        self.mul_mat(rhs)
+    }
+}
+
+/// Returns `Ragged` for the first row whose length differs from the first
+/// row's.
+fn check_rows<K, R: AsRef<[K]>>(rows: &[R]) -> Result<(), LinalgError> {
+    let expected = rows.first().map_or(0, |row| row.as_ref().len());
+    match rows.iter().position(|row| row.as_ref().len() != expected) {
+        Some(row) => Err(LinalgError::Ragged {
+            row,
+            expected,
+            found: rows[row].as_ref().len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Panics if the rows have different lengths; see [`Matrix::try_from_rows`]
+/// for the fallible version.
 impl<K> From<Vec<Vec<K>>> for Matrix<K>
 where
     K: Copy + Clone,
 {
     fn from(array2d: Vec<Vec<K>>) -> Self {
-        let vec2d: Vec<Vector<K>> = array2d
-            .into_iter()
-            .map(|v| Vector::from(v.as_slice()))
-            .collect();
-        Matrix {
-            m: Vector::from(vec2d),
-        }
+        Matrix::try_from_rows(array2d).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<K> TryFrom<Vec<Vector<K>>> for Matrix<K> {
+    type Error = LinalgError;
+
+    fn try_from(rows: Vec<Vector<K>>) -> Result<Self, Self::Error> {
+        Matrix::try_from_rows(rows.into_iter().map(|row| row.e).collect())
+    }
+}
+
+impl<K> TryFrom<&[Vec<K>]> for Matrix<K>
+where
+    K: Copy,
+{
+    type Error = LinalgError;
+
+    fn try_from(rows: &[Vec<K>]) -> Result<Self, Self::Error> {
+        Matrix::from_rows_checked(rows)
     }
 }
 
@@ -82,11 +246,26 @@ where
     K: Copy + Clone,
 {
     fn from(array2d: [[K; N1]; N2]) -> Self {
-        let vec2d: Vec<Vector<K>> = array2d
-            .iter()
-            .map(|&col_slice| col_slice.to_vec().into())
-            .collect();
-        Matrix { m: vec2d.into() }
+        Matrix {
+            data: array2d.iter().flatten().copied().collect(),
+            rows: N2,
+            cols: N1,
+            layout: Layout::RowMajor,
+        }
+    }
+}
+
+/// Two matrices are equal when they have the same shape and elements,
+/// whatever their layouts.
+impl<K: PartialEq> PartialEq for Matrix<K> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.shape() != other.shape() {
+            return false;
+        }
+        if self.layout == other.layout {
+            return self.data == other.data;
+        }
+        (0..self.rows).all(|i| (0..self.cols).all(|j| self[(i, j)] == other[(i, j)]))
     }
 }
 
@@ -95,110 +274,359 @@ where
     K: Debug + Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (i, v) in self.m.e.iter().enumerate() {
+        for i in 0..self.rows {
             write!(f, "[")?;
-            for (j, element) in v.e.iter().enumerate() {
+            for j in 0..self.cols {
                 if j != 0 {
                     write!(f, ", ")?;
                 }
-                write!(f, "{:.1}", element)?;
+                write!(f, "{:.1}", self[(i, j)])?;
             }
             write!(f, "]")?;
-            if i < self.m.e.len() - 1 {
-                write!(f, "\n")?;
+            if i < self.rows - 1 {
+                writeln!(f)?;
             }
         }
         Ok(())
     }
 }
 
+impl<K> Index<(usize, usize)> for Matrix<K> {
+    type Output = K;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        &self.data[self.offset(i, j)]
+    }
+}
+
+impl<K> IndexMut<(usize, usize)> for Matrix<K> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        let offset = self.offset(i, j);
+        &mut self.data[offset]
+    }
+}
+
+/// Row `index` as a slice, so `m[i][j]` reads like a nested array. The
+/// rows of a column-major matrix are not contiguous, so this panics for
+/// [`Layout::ColMajor`]; use `m[(i, j)]` or [`Matrix::row`] there.
 impl<K> Index<usize> for Matrix<K> {
-    type Output = Vector<K>;
+    type Output = [K];
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.m.e[index]
+        let range = self.row_range(index);
+        &self.data[range]
     }
 }
 
 impl<K> IndexMut<usize> for Matrix<K> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.m.e[index]
+        let range = self.row_range(index);
+        &mut self.data[range]
+    }
+}
+
+impl<K> AddAssign<&Matrix<K>> for Matrix<K>
+where
+    K: Scalar,
+{
+    fn add_assign(&mut self, rhs: &Matrix<K>) {
+        self.add_mut(rhs);
     }
 }
 
 impl<K> AddAssign<Matrix<K>> for Matrix<K>
 where
-    K: AddAssign + Copy, // or whatever bounds you need
+    K: Scalar,
 {
     fn add_assign(&mut self, rhs: Self) {
-        for (vec1, vec2) in self.m.e.iter_mut().zip(rhs.m.e.iter()) {
-            // *vec1 += *vec2;
-            vec1.add_mut(vec2);
-        }
+        self.add_mut(&rhs);
+    }
+}
+
+impl<K> SubAssign<&Matrix<K>> for Matrix<K>
+where
+    K: Scalar,
+{
+    fn sub_assign(&mut self, rhs: &Matrix<K>) {
+        self.sub_mut(rhs);
     }
 }
 
 impl<K> SubAssign<Matrix<K>> for Matrix<K>
 where
-    K: SubAssign + Copy, // or whatever bounds you need
+    K: Scalar,
 {
     fn sub_assign(&mut self, rhs: Matrix<K>) {
-        for (vec1, vec2) in self.m.e.iter_mut().zip(rhs.m.e.iter()) {
-            vec1.sub_mut(vec2);
-        }
+        self.sub_mut(&rhs);
     }
 }
 
 impl<K> MulAssign<K> for Matrix<K>
 where
-    K: MulAssign + Copy, // or whatever bounds you need
+    K: Scalar,
 {
     fn mul_assign(&mut self, rhs: K) {
-        for vec in &mut self.m.e {
-            vec.scl(rhs);
-        }
+        self.scl(rhs);
     }
 }
 
 impl<K> Matrix<K> {
+    /// Panics if the rows have different lengths.
     pub fn new(m: Vector<Vector<K>>) -> Self {
-        Self { m }
+        Matrix::try_from(m.e).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Wraps a buffer holding `rows * cols` elements in the given layout,
+    /// failing with `DimensionMismatch` if its length is wrong.
+    pub fn from_vec(
+        rows: usize,
+        cols: usize,
+        data: Vec<K>,
+        layout: Layout,
+    ) -> Result<Self, LinalgError> {
+        check_dimension(rows * cols, data.len())?;
+        Ok(Matrix {
+            data,
+            rows,
+            cols,
+            layout,
+        })
+    }
+
+    /// Builds a matrix from owned rows, failing with `Ragged` if they have
+    /// different lengths.
+    pub fn try_from_rows(rows: Vec<Vec<K>>) -> Result<Self, LinalgError> {
+        check_rows(&rows)?;
+        let (n, cols) = (rows.len(), rows.first().map_or(0, Vec::len));
+        Ok(Matrix {
+            data: rows.into_iter().flatten().collect(),
+            rows: n,
+            cols,
+            layout: Layout::RowMajor,
+        })
+    }
+
+    /// Builds a matrix by copying borrowed rows, failing with `Ragged` if
+    /// they have different lengths.
+    pub fn from_rows_checked<R: AsRef<[K]>>(rows: &[R]) -> Result<Self, LinalgError>
+    where
+        K: Copy,
+    {
+        check_rows(rows)?;
+        Ok(Matrix {
+            data: rows
+                .iter()
+                .flat_map(|row| row.as_ref().iter().copied())
+                .collect(),
+            rows: rows.len(),
+            cols: rows.first().map_or(0, |row| row.as_ref().len()),
+            layout: Layout::RowMajor,
+        })
+    }
+
+    /// Builds a `rows x cols` matrix whose element `(i, j)` is `f(i, j)`.
+    pub fn from_fn<F>(rows: usize, cols: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> K,
+    {
+        Matrix {
+            data: (0..rows)
+                .flat_map(|i| (0..cols).map(move |j| (i, j)))
+                .map(|(i, j)| f(i, j))
+                .collect(),
+            rows,
+            cols,
+            layout: Layout::RowMajor,
+        }
+    }
+
+    /// Builds a `rows x cols` matrix with every element set to `value`.
+    pub fn filled(rows: usize, cols: usize, value: K) -> Self
+    where
+        K: Copy,
+    {
+        Matrix::from_fn(rows, cols, |_, _| value)
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Self
+    where
+        K: Scalar,
+    {
+        Matrix::filled(rows, cols, K::zero())
+    }
+
+    pub fn ones(rows: usize, cols: usize) -> Self
+    where
+        K: Scalar,
+    {
+        Matrix::filled(rows, cols, K::one())
+    }
+
+    /// The `n x n` identity matrix.
+    pub fn identity(n: usize) -> Self
+    where
+        K: Scalar,
+    {
+        Matrix::from_fn(n, n, |i, j| if i == j { K::one() } else { K::zero() })
     }
 
     pub fn shape(&self) -> (usize, usize) {
-        (self.m.size(), self.m.e.first().map_or(0, Vector::size))
+        (self.rows, self.cols)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Distance in the buffer between `(i, j)` and `(i + 1, j)`, and between
+    /// `(i, j)` and `(i, j + 1)`.
+    pub fn strides(&self) -> (usize, usize) {
+        match self.layout {
+            Layout::RowMajor => (self.cols, 1),
+            Layout::ColMajor => (1, self.rows),
+        }
+    }
+
+    /// The underlying buffer, in [`Matrix::layout`] order.
+    pub fn as_slice(&self) -> &[K] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [K] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<K> {
+        self.data
+    }
+
+    /// The same matrix stored in `layout`.
+    pub fn to_layout(&self, layout: Layout) -> Self
+    where
+        K: Copy,
+    {
+        if layout == self.layout {
+            return self.clone();
+        }
+        let data = match layout {
+            Layout::RowMajor => (0..self.rows)
+                .flat_map(|i| (0..self.cols).map(move |j| (i, j)))
+                .map(|(i, j)| self[(i, j)])
+                .collect(),
+            Layout::ColMajor => (0..self.cols)
+                .flat_map(|j| (0..self.rows).map(move |i| (i, j)))
+                .map(|(i, j)| self[(i, j)])
+                .collect(),
+        };
+        Matrix {
+            data,
+            rows: self.rows,
+            cols: self.cols,
+            layout,
+        }
+    }
+
+    fn offset(&self, i: usize, j: usize) -> usize {
+        assert!(
+            i < self.rows && j < self.cols,
+            "index ({}, {}) out of bounds for a {}x{} matrix",
+            i,
+            j,
+            self.rows,
+            self.cols
+        );
+        let (row_stride, col_stride) = self.strides();
+        i * row_stride + j * col_stride
+    }
+
+    fn row_range(&self, i: usize) -> std::ops::Range<usize> {
+        assert_eq!(
+            self.layout,
+            Layout::RowMajor,
+            "row slices are only available for row-major matrices"
+        );
+        assert!(
+            i < self.rows,
+            "row {} out of bounds for a {}x{} matrix",
+            i,
+            self.rows,
+            self.cols
+        );
+        i * self.cols..(i + 1) * self.cols
+    }
+
+    /// Applies `f` to every pair of elements at the same position.
+    fn zip_apply<F>(&mut self, other: &Matrix<K>, mut f: F)
+    where
+        K: Copy,
+        F: FnMut(&mut K, K),
+    {
+        if self.layout == other.layout {
+            self.data
+                .iter_mut()
+                .zip(&other.data)
+                .for_each(|(a, b)| f(a, *b));
+        } else {
+            for i in 0..self.rows {
+                for j in 0..self.cols {
+                    f(&mut self[(i, j)], other[(i, j)]);
+                }
+            }
+        }
     }
 
     // MANDATORY -- ex00
     pub fn add_mut(&mut self, other: &Matrix<K>)
     where
-        K: AddAssign + Copy,
+        K: Scalar,
     {
         assert_eq!(self.shape(), other.shape());
-        self.m
-            .e
-            .iter_mut()
-            .zip(&other.m.e)
-            .for_each(|(a, b)| a.add_mut(b));
+        self.zip_apply(other, |a, b| *a += b);
     }
 
     pub fn sub_mut(&mut self, other: &Matrix<K>)
     where
-        K: SubAssign + Copy,
+        K: Scalar,
     {
         assert_eq!(self.shape(), other.shape());
-        self.m
-            .e
-            .iter_mut()
-            .zip(&other.m.e)
-            .for_each(|(a, b)| a.sub_mut(b));
+        self.zip_apply(other, |a, b| *a -= b);
     }
 
     pub fn scl(&mut self, a: K)
     where
-        K: MulAssign + Copy,
+        K: Scalar,
     {
-        self.m.e.iter_mut().for_each(|col| col.scl(a));
+        self.data.iter_mut().for_each(|e| *e *= a);
+    }
+
+    /// Fallible [`Matrix::add_mut`]: fails instead of panicking when the
+    /// shapes differ.
+    pub fn try_add_mut(&mut self, other: &Matrix<K>) -> Result<(), LinalgError>
+    where
+        K: Scalar,
+    {
+        check_shape(self.shape(), other.shape())?;
+        self.add_mut(other);
+        Ok(())
+    }
+
+    /// Fallible [`Matrix::sub_mut`]: fails instead of panicking when the
+    /// shapes differ.
+    pub fn try_sub_mut(&mut self, other: &Matrix<K>) -> Result<(), LinalgError>
+    where
+        K: Scalar,
+    {
+        check_shape(self.shape(), other.shape())?;
+        self.sub_mut(other);
+        Ok(())
     }
     // END of ex00
 
@@ -209,66 +637,251 @@ impl<K> Matrix<K> {
         println!("{}", self);
     }
 
-    pub fn m(&self) -> &Vector<Vector<K>> {
-        &self.m
+    // MANDATORY -- ex07
+    /// Matrix-vector product. Panics if the number of columns does not
+    /// match the size of `vec`.
+    pub fn mul_vec(&self, vec: &Vector<K>) -> Vector<K>
+    where
+        K: Scalar,
+    {
+        self.try_mul_vec(vec).unwrap_or_else(|e| panic!("{}", e))
     }
 
-    pub fn m_mut(&mut self) -> &mut Vector<Vector<K>> {
-        &mut self.m
+    /// Matrix-vector product, or an error when the number of columns does
+    /// not match the size of `vec`.
+    pub fn try_mul_vec(&self, vec: &Vector<K>) -> Result<Vector<K>, LinalgError>
+    where
+        K: Scalar,
+    {
+        self.view().try_mul_vec(vec.view())
     }
 
-    pub fn set_m(&mut self, m: Vector<Vector<K>>) {
-        self.m = m;
+    /// Matrix-matrix product. Panics if the number of columns of `self`
+    /// does not match the number of rows of `mat`.
+    pub fn mul_mat(&self, mat: &Matrix<K>) -> Matrix<K>
+    where
+        K: Scalar,
+    {
+        self.try_mul_mat(mat).unwrap_or_else(|e| panic!("{}", e))
     }
 
-    // MANDATORY -- ex07
-    #[allow(dead_code)]
-    fn mul_vec(&mut self, vec: Vector<K>) -> Vector<K>
+    /// Matrix-matrix product, or an error when the number of columns of
+    /// `self` does not match the number of rows of `mat`.
+    pub fn try_mul_mat(&self, mat: &Matrix<K>) -> Result<Matrix<K>, LinalgError>
     where
-        K: Default + Mul<Output = K> + Copy,
+        K: Scalar,
     {
-        assert!(
-            self.m.size() > 0 && self.m.e[0].size() == vec.size(),
-            "Error"
-        );
-        let mut result = Vector::from(vec![K::default(); self.m.size()]);
-        self.m
-            .e
-            .iter()
-            .zip(result.e.iter_mut())
-            .for_each(|(row, res)| {
-                row.e
-                    .iter()
-                    .zip(vec.e.iter())
-                    .for_each(|(r, c)| *res = *r * *c)
-            });
-        result
+        self.view().try_mul_mat(mat.view())
     }
+}
+// END of ex07
+
+/// Outcome of [`Matrix::eliminate`].
+struct Elimination {
+    /// Column of the pivot of each nonzero row, from top to bottom.
+    pivots: Vec<usize>,
+    /// Number of row swaps performed.
+    swaps: usize,
+}
 
-    #[allow(dead_code)]
-    fn mul_mat(&mut self, mat: Matrix<K>) -> Matrix<K>
+impl<K> Matrix<K> {
+    /// Exchanges rows `a` and `b` in place.
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        for j in 0..self.cols {
+            let (x, y) = (self.offset(a, j), self.offset(b, j));
+            self.data.swap(x, y);
+        }
+    }
+
+    /// Fraction-free Gauss-Jordan elimination (Bareiss/Montante) in place.
+    ///
+    /// Every intermediate value is a minor of the input, so each division is
+    /// exact and integer matrices never leave the integers. On return every
+    /// pivot row holds the same pivot value, which for a full-rank square
+    /// matrix is its determinant up to the sign of the row permutation.
+    fn eliminate(&mut self) -> Elimination
     where
-        K: Default + Clone + Mul<Output = K> + AddAssign + Copy,
+        K: Ring,
     {
-        assert!(
-            self.m.size() > 0 && self.m.e[0].size() == mat.m.size(),
-            "Error"
-        );
-        let mut result = Matrix::from(vec![vec![K::default(); self.m.size()]; mat.m.e[0].size()]);
-        for i in 0..self.m.size() {
-            for j in 0..mat.m.size() {
-                for k in 0..mat.m.e[0].size() {
-                    result[i][k] += self[i][k] * mat[k][j];
+        let (rows, cols) = self.shape();
+        let mut pivots = Vec::new();
+        let mut swaps = 0;
+        let mut prev = K::one();
+        for c in 0..cols {
+            let r = pivots.len();
+            if r == rows {
+                break;
+            }
+            let pivot = (r..rows)
+                .filter(|&i| !self[(i, c)].is_zero())
+                .max_by(|&a, &b| {
+                    let (a, b) = (self[(a, c)].modulus(), self[(b, c)].modulus());
+                    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+                });
+            let Some(p) = pivot else {
+                continue;
+            };
+            if p != r {
+                self.swap_rows(p, r);
+                swaps += 1;
+            }
+            let piv = self[(r, c)];
+            for i in (0..rows).filter(|&i| i != r) {
+                let f = self[(i, c)];
+                for j in 0..cols {
+                    self[(i, j)] = (piv * self[(i, j)] - f * self[(r, j)]) / prev;
                 }
             }
+            prev = piv;
+            pivots.push(c);
+        }
+        Elimination { pivots, swaps }
+    }
+
+    pub(crate) fn check_square(&self) -> Result<(), LinalgError> {
+        if self.rows != self.cols {
+            return Err(LinalgError::NotSquare {
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+        Ok(())
+    }
+
+    /// The identity matrix of the same size as this square matrix.
+    ///
+    /// Panics if the matrix is not square.
+    pub fn identity_matrix(&self) -> Matrix<K>
+    where
+        K: Scalar,
+    {
+        self.check_square().unwrap_or_else(|e| panic!("{}", e));
+        Matrix::identity(self.rows)
+    }
+
+    // MANDATORY -- ex08
+    /// Sum of the diagonal elements. Panics if the matrix is not square.
+    pub fn trace(&self) -> K
+    where
+        K: Scalar,
+    {
+        self.try_trace().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_trace(&self) -> Result<K, LinalgError>
+    where
+        K: Scalar,
+    {
+        self.check_square()?;
+        Ok((0..self.rows).fold(K::zero(), |acc, i| acc + self[(i, i)]))
+    }
+    // END of ex08
+
+    // MANDATORY -- ex09
+    pub fn transpose(&self) -> Matrix<K>
+    where
+        K: Copy,
+    {
+        Matrix::from_fn(self.cols, self.rows, |i, j| self[(j, i)])
+    }
+
+    /// Transpose with every element conjugated, the adjoint `A^H`. Equal
+    /// to [`Matrix::transpose`] for real matrices.
+    pub fn conjugate_transpose(&self) -> Matrix<K>
+    where
+        K: Scalar,
+    {
+        Matrix::from_fn(self.cols, self.rows, |i, j| self[(j, i)].conj())
+    }
+    // END of ex09
+
+    // MANDATORY -- ex10
+    /// Reduced row echelon form.
+    ///
+    /// Elimination is fraction-free, so integer matrices are reduced
+    /// exactly as long as every pivot row is divisible by its pivot; no
+    /// tolerance is applied to floating-point matrices.
+    pub fn row_echelon(&self) -> Matrix<K>
+    where
+        K: Ring,
+    {
+        let mut result = self.clone();
+        let elimination = result.eliminate();
+        for (r, &c) in elimination.pivots.iter().enumerate() {
+            let piv = result[(r, c)];
+            for j in 0..result.cols {
+                result[(r, j)] = result[(r, j)] / piv;
+            }
         }
         result
     }
+    // END of ex10
+
+    // MANDATORY -- ex11
+    /// Panics if the matrix is not square.
+    pub fn determinant(&self) -> K
+    where
+        K: Ring,
+    {
+        self.try_determinant().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Determinant computed by fraction-free elimination, exact for integer
+    /// matrices.
+    pub fn try_determinant(&self) -> Result<K, LinalgError>
+    where
+        K: Ring,
+    {
+        self.check_square()?;
+        let n = self.rows;
+        if n == 0 {
+            return Ok(K::one());
+        }
+        let mut a = self.clone();
+        let elimination = a.eliminate();
+        if elimination.pivots.len() < n {
+            return Ok(K::zero());
+        }
+        let det = a[(n - 1, n - 1)];
+        Ok(if elimination.swaps.is_multiple_of(2) {
+            det
+        } else {
+            -det
+        })
+    }
+    // END of ex11
+
+    // MANDATORY -- ex12
+    /// Inverse through an LU factorization with partial pivoting.
+    ///
+    /// Fails with `NotSquare`, or with `Singular` when a pivot is exactly
+    /// zero or not finite; see
+    /// [`crate::decomposition::Lu::is_singular_with`] for a test with a
+    /// tolerance.
+    pub fn inverse(&self) -> Result<Matrix<K>, LinalgError>
+    where
+        K: Field,
+    {
+        self.lu()?.inverse()
+    }
+    // END of ex12
+
+    // MANDATORY -- ex13
+    /// Number of linearly independent rows, found by exact elimination.
+    /// Floating-point matrices usually want [`Matrix::numerical_rank`].
+    pub fn rank(&self) -> usize
+    where
+        K: Ring,
+    {
+        self.clone().eliminate().pivots.len()
+    }
+    // END of ex13
 }
-// END of ex07
 
 #[cfg(test)]
 mod tests {
+    use num_complex::Complex;
+
     use super::*;
 
     #[test]
@@ -291,556 +904,736 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assign_op_pattern)]
     fn matrix_add() {
         let mut u = Matrix::from([[1., 2.], [3., 4.]]);
         let v = Matrix::from([[7., 4.], [-2., 2.]]);
         u.add_mut(&v);
-        assert_eq!(Vec::from([8.0, 6.0]), u[0].e);
-        assert_eq!(Vec::from([1.0, 6.0]), u[1].e);
+        assert_eq!(Vec::from([8.0, 6.0]), u[0]);
+        assert_eq!(Vec::from([1.0, 6.0]), u[1]);
 
         let mut u = Matrix::from([[1., 2.], [3., 4.]]);
         let v = Matrix::from([[7., 4.], [-2., 2.]]);
         u = u.add(v);
-        assert_eq!(Vec::from([8.0, 6.0]), u[0].e);
-        assert_eq!(Vec::from([1.0, 6.0]), u[1].e);
+        assert_eq!(Vec::from([8.0, 6.0]), u[0]);
+        assert_eq!(Vec::from([1.0, 6.0]), u[1]);
 
         let mut u = Matrix::from([[1., 2.], [3., 4.]]);
         let v = Matrix::from([[7., 4.], [-2., 2.]]);
         u = u + v;
-        assert_eq!(Vec::from([8.0, 6.0]), u[0].e);
-        assert_eq!(Vec::from([1.0, 6.0]), u[1].e);
+        assert_eq!(Vec::from([8.0, 6.0]), u[0]);
+        assert_eq!(Vec::from([1.0, 6.0]), u[1]);
 
         let mut u = Matrix::from([[1., 2.], [3., 4.]]);
         let v = Matrix::from([[7., 4.], [-2., 2.]]);
         u += v;
-        assert_eq!(Vec::from([8.0, 6.0]), u[0].e);
-        assert_eq!(Vec::from([1.0, 6.0]), u[1].e);
+        assert_eq!(Vec::from([8.0, 6.0]), u[0]);
+        assert_eq!(Vec::from([1.0, 6.0]), u[1]);
     }
 
     #[test]
+    #[allow(clippy::assign_op_pattern)]
     fn matrix_sub() {
         let mut u = Matrix::from([[1., 1.], [1., 1.]]);
         let v = Matrix::from([[1., 1.], [1., 1.]]);
         u.sub_mut(&v);
-        assert_eq!(Vec::from([0., 0.]), u[0].e);
-        assert_eq!(Vec::from([0., 0.]), u[1].e);
+        assert_eq!(Vec::from([0., 0.]), u[0]);
+        assert_eq!(Vec::from([0., 0.]), u[1]);
 
         let mut u = Matrix::from([[1., 1.], [1., 1.]]);
         let v = Matrix::from([[1., 1.], [1., 1.]]);
         u = u.sub(v);
-        assert_eq!(Vec::from([0., 0.]), u[0].e);
-        assert_eq!(Vec::from([0., 0.]), u[1].e);
+        assert_eq!(Vec::from([0., 0.]), u[0]);
+        assert_eq!(Vec::from([0., 0.]), u[1]);
 
         let mut u = Matrix::from([[1., 1.], [1., 1.]]);
         let v = Matrix::from([[1., 1.], [1., 1.]]);
         u = u - v;
-        assert_eq!(Vec::from([0., 0.]), u[0].e);
-        assert_eq!(Vec::from([0., 0.]), u[1].e);
+        assert_eq!(Vec::from([0., 0.]), u[0]);
+        assert_eq!(Vec::from([0., 0.]), u[1]);
 
         let mut u = Matrix::from([[1., 1.], [1., 1.]]);
         let v = Matrix::from([[1., 1.], [1., 1.]]);
         u -= v;
-        assert_eq!(Vec::from([0., 0.]), u[0].e);
-        assert_eq!(Vec::from([0., 0.]), u[1].e);
+        assert_eq!(Vec::from([0., 0.]), u[0]);
+        assert_eq!(Vec::from([0., 0.]), u[1]);
+    }
+
+    #[test]
+    fn matrix_checked_constructors() {
+        let ragged = Err(LinalgError::Ragged {
+            row: 1,
+            expected: 2,
+            found: 1,
+        });
+        assert_eq!(ragged, Matrix::try_from_rows(vec![vec![1, 2], vec![3]]));
+        assert_eq!(ragged, Matrix::from_rows_checked(&[&[1, 2][..], &[3]]));
+        assert_eq!(ragged, Matrix::try_from(&[vec![1, 2], vec![3]][..]));
+        assert_eq!(
+            ragged,
+            Matrix::try_from(vec![Vector::from([1, 2]), Vector::from([3])])
+        );
+
+        let u = Matrix::try_from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!((2, 3), u.shape());
+        assert_eq!(
+            Ok(u.clone()),
+            Matrix::from_rows_checked(&[[1, 2, 3], [4, 5, 6]])
+        );
+        assert_eq!(u, Matrix::from([[1, 2, 3], [4, 5, 6]]));
+        assert_eq!(
+            Ok((0, 0)),
+            Matrix::<i32>::try_from_rows(vec![]).map(|m| m.shape())
+        );
     }
 
     #[test]
+    #[should_panic(expected = "ragged rows: row 2 has 3 elements, expected 2")]
+    fn matrix_from_ragged_panics() {
+        let _ = Matrix::from(vec![vec![1., 2.], vec![3., 4.], vec![5., 6., 7.]]);
+    }
+
+    #[test]
+    fn matrix_fn_constructors() {
+        let u = Matrix::from_fn(2, 3, |i, j| i * 10 + j);
+        assert_eq!(Matrix::from([[0, 1, 2], [10, 11, 12]]), u);
+        assert_eq!(Matrix::from([[0., 0.], [0., 0.]]), Matrix::zeros(2, 2));
+        assert_eq!(Matrix::from([[1, 1, 1]]), Matrix::ones(1, 3));
+        assert_eq!(Matrix::from([[7], [7]]), Matrix::filled(2, 1, 7));
+        assert_eq!(
+            Matrix::from([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]),
+            Matrix::identity(3)
+        );
+        assert_eq!((0, 0), Matrix::<f32>::identity(0).shape());
+    }
+
+    #[test]
+    fn matrix_layouts() {
+        let u = Matrix::from([[1, 2, 3], [4, 5, 6]]);
+        let v = Matrix::from_vec(2, 3, vec![1, 4, 2, 5, 3, 6], Layout::ColMajor).unwrap();
+        assert_eq!(Layout::RowMajor, u.layout());
+        assert_eq!((3, 1), u.strides());
+        assert_eq!((1, 2), v.strides());
+        assert_eq!(&[1, 2, 3, 4, 5, 6], u.as_slice());
+        assert_eq!(6, v[(1, 2)]);
+        assert_eq!(u, v);
+        assert_eq!(u.to_string(), v.to_string());
+        assert_eq!(v.as_slice(), u.to_layout(Layout::ColMajor).as_slice());
+        assert_eq!(u.as_slice(), v.to_layout(Layout::RowMajor).as_slice());
+
+        let mut w = v.clone();
+        w.add_mut(&u);
+        w[(0, 0)] = 0;
+        assert_eq!(Matrix::from([[0, 4, 6], [8, 10, 12]]), w);
+        assert_eq!(Layout::ColMajor, w.layout());
+
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 6,
+                found: 5
+            }),
+            Matrix::from_vec(2, 3, vec![0; 5], Layout::RowMajor)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "row slices are only available for row-major matrices")]
+    fn matrix_col_major_row_index_panics() {
+        let u = Matrix::from_vec(2, 2, vec![1, 2, 3, 4], Layout::ColMajor).unwrap();
+        let _ = &u[0];
+    }
+
+    #[test]
+    fn matrix_rows_in_every_layout() {
+        let mut u = Matrix::from_vec(2, 2, vec![1, 2, 3, 4], Layout::ColMajor).unwrap();
+        assert_eq!(u.row(0), Vector::from([1, 3]));
+        u.row_mut(1)[0] = 5;
+        assert_eq!(u.row(1), Vector::from([5, 4]));
+        assert_eq!(u.to_layout(Layout::RowMajor)[1], [5, 4]);
+    }
+
+    #[test]
+    fn matrix_try_ops() {
+        let mut u = Matrix::from([[1., 2.], [3., 4.]]);
+        let v = Matrix::from([[7., 4.], [-2., 2.]]);
+        let w = Matrix::from([[1., 2., 3.]]);
+        assert_eq!(Ok(()), u.try_add_mut(&v));
+        assert_eq!(Vec::from([8.0, 6.0]), u[0]);
+        assert_eq!(Ok(()), u.try_sub_mut(&v));
+        assert_eq!(Vec::from([1.0, 2.0]), u[0]);
+
+        let mismatch = Err(LinalgError::ShapeMismatch {
+            expected: (2, 2),
+            found: (1, 3),
+        });
+        assert_eq!(mismatch, u.try_add_mut(&w));
+        assert_eq!(mismatch, u.try_sub_mut(&w));
+        assert_eq!(Vec::from([3.0, 4.0]), u[1]);
+
+        let x = Vector::from([1., 1.]);
+        assert_eq!(Ok(vec![3., 7.]), u.try_mul_vec(&x).map(|r| r.e));
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 2,
+                found: 3
+            }),
+            u.try_mul_vec(&Vector::from([1., 1., 1.]))
+        );
+    }
+
+    #[test]
+    fn matrix_ops_reuse_buffers() {
+        let u = Matrix::from([[1, 2], [3, 4]]);
+        let v = Matrix::from_vec(2, 2, vec![1, 3, 2, 4], Layout::ColMajor).unwrap();
+        assert_eq!(Matrix::from([[2, 4], [6, 8]]), &u + &v);
+        assert_eq!(Matrix::zeros(2, 2), &u - &v);
+        assert_eq!(Matrix::from([[3, 6], [9, 12]]), &u * 3);
+
+        let buffer = u.as_slice().as_ptr();
+        let w = u + &v;
+        assert_eq!(buffer, w.as_slice().as_ptr());
+        let w = (w - &v) * 2;
+        assert_eq!(buffer, w.as_slice().as_ptr());
+
+        let buffer = v.as_slice().as_ptr();
+        let x = &w - v;
+        assert_eq!(Matrix::from([[1, 2], [3, 4]]), x);
+        assert_eq!(Layout::ColMajor, x.layout());
+        assert_eq!(buffer, x.as_slice().as_ptr());
+        let mut x = &w + x;
+        assert_eq!(buffer, x.as_slice().as_ptr());
+        x -= &w;
+        x += &Matrix::identity(2);
+        assert_eq!(Matrix::from([[2, 2], [3, 5]]), x);
+    }
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)]
     fn matrix_scale() {
         let mut u = Matrix::from([[1., 1.], [1., 1.]]);
         u.scl(42.);
-        assert_eq!(Vec::from([42., 42.]), u[0].e);
-        assert_eq!(Vec::from([42., 42.]), u[1].e);
+        assert_eq!(Vec::from([42., 42.]), u[0]);
+        assert_eq!(Vec::from([42., 42.]), u[1]);
 
         let mut u = Matrix::from([[1., 1.], [1., 1.]]);
         u *= 42.;
-        assert_eq!(Vec::from([42., 42.]), u[0].e);
-        assert_eq!(Vec::from([42., 42.]), u[1].e);
+        assert_eq!(Vec::from([42., 42.]), u[0]);
+        assert_eq!(Vec::from([42., 42.]), u[1]);
 
         let mut u = Matrix::from([[1., 1.], [1., 1.]]);
         u = u * 42.;
-        assert_eq!(Vec::from([42., 42.]), u[0].e);
-        assert_eq!(Vec::from([42., 42.]), u[1].e);
-    }
-
-    // #[test]
-    // fn matrix_mul_vec() {
-    //     let mut u = Matrix::from([[1., 0.], [0., 1.]]);
-    //     let v = Vector::from(&[4., 2.]);
-    //     let result = u.mul_vec(v);
-    //     assert_eq!(result.cols[0], 4.);
-    //     assert_eq!(result.cols[1], 2.);
-    //
-    //     let mut u = Matrix::from([[2., 0.], [0., 2.]]);
-    //     let v = Vector::from(&[4., 2.]);
-    //     let result = u.mul_vec(v);
-    //     assert_eq!(result.cols[0], 8.);
-    //     assert_eq!(result.cols[1], 4.);
-    //
-    //     let mut u = Matrix::from([[2., -2.], [-2., 2.]]);
-    //     let v = Vector::from(&[4., 2.]);
-    //     let result = u.mul_vec(v);
-    //     assert_eq!(result.cols[0], 4.);
-    //     assert_eq!(result.cols[1], -4.);
-    //
-    //     let mut u = Matrix::from([[0., 0.], [0., 0.]]);
-    //     let v = Vector::from(&[4., 2.]);
-    //     let result = u.mul_vec(v);
-    //     assert_eq!(result.cols[0], 0.);
-    //     assert_eq!(result.cols[1], 0.);
-    //
-    //     let mut u = Matrix::from([[1., 1.], [1., 1.]]);
-    //     let v = Vector::from(&[4., 2.]);
-    //     let result = u.mul_vec(v);
-    //     assert_eq!(result.cols[0], 6.);
-    //     assert_eq!(result.cols[1], 6.);
-    //
-    //     let mut u = Matrix::from([[0.5, 0.], [0., 0.5]]);
-    //     let v = Vector::from(&[4., 2.]);
-    //     let result = u.mul_vec(v);
-    //     assert_eq!(result.cols[0], 2.);
-    //     assert_eq!(result.cols[1], 1.);
-    // }
-    //
-    // #[test]
-    // fn matrix_mul_mat() {
-    //     let mut u = Matrix::from([[1., 0.], [0., 1.]]);
-    //     let v = Matrix::from([[1., 0.], [0., 1.]]);
-    //     let result = u.mul_mat(v);
-    //     assert_eq!(result.cols[0], Vec::from([1., 0.]));
-    //     assert_eq!(result.cols[1], Vec::from([0., 1.]));
-    //
-    //     let mut u = Matrix::from([[1., 0.], [0., 1.]]);
-    //     let v = Matrix::from([[2., 1.], [4., 2.]]);
-    //     let result = u.mul_mat(v);
-    //     assert_eq!(result.cols[0], Vec::from([2., 1.]));
-    //     assert_eq!(result.cols[1], Vec::from([4., 2.]));
-    //
-    //     let mut u = Matrix::from([[3., -5.], [6., 8.]]);
-    //     let v = Matrix::from([[2., 1.], [4., 2.]]);
-    //     let result = u.mul_mat(v);
-    //     assert_eq!(result.cols[0], Vec::from([-14., -7.]));
-    //     assert_eq!(result.cols[1], Vec::from([44., 22.]));
-    //
-    //     let mut u = Matrix::from([[0., 4., -2.], [-4., -3., 0.]]);
-    //     let v = Matrix::from([[0., 1.], [1., -1.], [2., 3.]]);
-    //     let result = u.mul_mat(v);
-    //     assert_eq!(result.cols[0], Vec::from([0., -10.]));
-    //     assert_eq!(result.cols[1], Vec::from([-3., -1.]));
-    // }
-    //
-    // #[test]
-    // fn matrix_trace_with_zero() {
-    //     let mut u = Matrix::from([[1., 0.], [0., 1.]]);
-    //     assert_eq!(u.trace(), 2.0);
-    //
-    //     let mut u = Matrix::from([[0., 0.], [0., 0.]]);
-    //     assert_eq!(u.trace(), 0.0);
-    // }
-    //
-    // #[test]
-    // fn matrix_trace_positive() {
-    //     let mut u = Matrix::from([[2., -5., 0.], [4., 3., 7.], [-2., 3., 4.]]);
-    //     assert_eq!(u.trace(), 9.0);
-    //
-    //     let mut u = Matrix::from([[1., 2.], [3., 4.]]);
-    //     assert_eq!(u.trace(), 5.0);
-    //
-    //     let mut u = Matrix::from([[8., -7.], [4., 2.]]);
-    //     assert_eq!(u.trace(), 10.0);
-    // }
-    //
-    // #[test]
-    // fn matrix_trace_negative() {
-    //     let mut u = Matrix::from([[-2., -8., 4.], [1., -23., 4.], [0., 6., 4.]]);
-    //     assert_eq!(u.trace(), -21.0);
-    // }
-    //
-    // #[test]
-    // fn matrix_transpose_zero() {
-    //     let mut u = Matrix::from([[0., 0.], [0., 0.]]);
-    //     let result = u.transpose();
-    //     assert_eq!(result.cols[0], Vec::from([0., 0.]));
-    //     assert_eq!(result.cols[1], Vec::from([0., 0.]));
-    // }
-    //
-    // #[test]
-    // fn matrix_transpose_no_change() {
-    //     let mut u = Matrix::from([[1., 0.], [0., 1.]]);
-    //     let result = u.transpose();
-    //     assert_eq!(result.cols[0], Vec::from([1., 0.]));
-    //     assert_eq!(result.cols[1], Vec::from([0., 1.]));
-    // }
-    //
-    // #[test]
-    // fn matrix_transpose_reverse() {
-    //     let mut u = Matrix::from([[1., 2.], [3., 4.]]);
-    //     let result = u.transpose();
-    //     assert_eq!(result.cols[0], Vec::from([1., 3.]));
-    //     assert_eq!(result.cols[1], Vec::from([2., 4.]));
-    // }
-    //
-    // #[test]
-    // fn matrix_transpose_bin_no_change() {
-    //     let mut u = Matrix::from([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
-    //     let result = u.transpose();
-    //     assert_eq!(result.cols[0], Vec::from([1., 0., 0.]));
-    //     assert_eq!(result.cols[1], Vec::from([0., 1., 0.]));
-    //     assert_eq!(result.cols[2], Vec::from([0., 0., 1.]));
-    // }
-    //
-    // #[test]
-    // fn matrix_transpose_positive() {
-    //     let mut u = Matrix::from([[1., 2., 3.], [4., 5., 6.]]);
-    //     let result = u.transpose();
-    //     assert_eq!(result.cols[0], Vec::from([1., 4.]));
-    //     assert_eq!(result.cols[1], Vec::from([2., 5.]));
-    //     assert_eq!(result.cols[2], Vec::from([3., 6.]));
-    //
-    //     let mut u = Matrix::from([[1., 2.], [3., 4.], [5., 6.]]);
-    //     let result = u.transpose();
-    //     assert_eq!(result.cols[0], Vec::from([1., 3., 5.]));
-    //     assert_eq!(result.cols[1], Vec::from([2., 4., 6.]));
-    // }
-    //
-    // #[test]
-    // fn matrix_rref_basics() {
-    //     let mut u = Matrix::from([[1, -1, 2], [3, 2, 1], [2, -3, -2]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([1, 0, 0]));
-    //     assert_eq!(result.cols[1], Vec::from([0, 1, 0]));
-    //     assert_eq!(result.cols[2], Vec::from([0, 0, 1]));
-    //
-    //     let mut u = Matrix::from([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([1, 0, 0]));
-    //     assert_eq!(result.cols[1], Vec::from([0, 1, 0]));
-    //     assert_eq!(result.cols[2], Vec::from([0, 0, 1]));
-    //
-    //     let mut u = Matrix::from([[1, 2], [3, 4]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([1, 0]));
-    //     assert_eq!(result.cols[1], Vec::from([0, 1]));
-    //
-    //     let mut u = Matrix::from([[1, 2], [2, 4]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([1, 2]));
-    //     assert_eq!(result.cols[1], Vec::from([0, 0]));
-    //
-    //     let mut u = Matrix::from([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([1., 0., 0.]));
-    //     assert_eq!(result.cols[1], Vec::from([0., 1., 0.]));
-    //     assert_eq!(result.cols[2], Vec::from([0., 0., 1.]));
-    //
-    //     let mut u = Matrix::from([[8., 5., -2.], [4., 7., 20.], [7., 6., 1.]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([1., 0., 0.]));
-    //     assert_eq!(result.cols[1], Vec::from([0., 1., 0.]));
-    //     assert_eq!(result.cols[2], Vec::from([0., 0., 1.]));
-    // }
-    //
-    // #[test]
-    // fn matrix_rref_edge_cases() {
-    //     let mut u = Matrix::from([[0, 0], [0, 0]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([0, 0]));
-    //     assert_eq!(result.cols[1], Vec::from([0, 0]));
-    //
-    //     let mut u = Matrix::from([[1, 0], [0, 1]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([1, 0]));
-    //     assert_eq!(result.cols[1], Vec::from([0, 1]));
-    //
-    //     let mut u = Matrix::from([[4., 2.], [2., 1.]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([1., 0.5]));
-    //     assert_eq!(result.cols[1], Vec::from([0., 0.]));
-    //
-    //     let mut u = Matrix::from([[-7, 2], [4, 8]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([1, 0]));
-    //     assert_eq!(result.cols[1], Vec::from([0, 1]));
-    //
-    //     let mut u = Matrix::from([[1, 2], [4, 8]]);
-    //     let result = u.row_echelon();
-    //     assert_eq!(result.cols[0], Vec::from([1, 2]));
-    //     assert_eq!(result.cols[1], Vec::from([0, 0]));
-    // }
-    //
-    // #[test]
-    // fn matrix_determinant_2() {
-    //     let mut u = Matrix::from([[1., -1.], [-1., 1.]]);
-    //     assert_eq!(u.determinant(), 0.);
-    //
-    //     let mut u = Matrix::from([[0, 0], [0, 0]]);
-    //     assert_eq!(u.determinant(), 0);
-    //
-    //     let mut u = Matrix::from([[1, 0], [0, 1]]);
-    //     assert_eq!(u.determinant(), 1);
-    //
-    //     let mut u = Matrix::from([[2, 0], [0, 2]]);
-    //     assert_eq!(u.determinant(), 4);
-    //
-    //     let mut u = Matrix::from([[1, 1], [1, 1]]);
-    //     assert_eq!(u.determinant(), 0);
-    //
-    //     let mut u = Matrix::from([[0, 1], [1, 0]]);
-    //     assert_eq!(u.determinant(), -1);
-    //
-    //     let mut u = Matrix::from([[1, 2], [3, 4]]);
-    //     assert_eq!(u.determinant(), -2);
-    //
-    //     let mut u = Matrix::from([[-7, 5], [4, 6]]);
-    //     assert_eq!(u.determinant(), -62);
-    // }
-    //
-    // #[test]
-    // fn matrix_determinant_3() {
-    //     let mut u = Matrix::from([[2., 0., 0.], [0., 2., 0.], [0., 0., 2.]]);
-    //     assert_eq!(u.determinant(), 8.);
-    //
-    //     let mut u = Matrix::from([[4, 2, 5], [1, 8, 9], [2, 7, 3]]);
-    //     assert_eq!(u.determinant(), -171);
-    //
-    //     let mut u = Matrix::from([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
-    //     assert_eq!(u.determinant(), 1);
-    // }
-    //
-    // #[test]
-    // fn matrix_determinant_4() {
-    //     let mut u = Matrix::from(&[
-    //         &[8., 5., -2., 4.],
-    //         &[4., 2.5, 20., 4.],
-    //         &[8., 5., 1., 4.],
-    //         &[28., -4., 17., 1.],
-    //     ]);
-    //
-    //     assert_eq!(u.determinant(), 1032.);
-    //
-    //     let mut u = Matrix::from(&[
-    //         &[1, 1, 1, -1],
-    //         &[1, 1, -1, 1],
-    //         &[1, -1, 1, 1],
-    //         &[-1, 1, 1, 1],
-    //     ]);
-    //
-    //     assert_eq!(u.determinant(), -16);
-    // }
-    //
-    // #[test]
-    // fn matrix_determinant_0() {
-    //     let mut u = Matrix::from([[1, 4, 2], [1, 4, 2], [3, 9, 5]]);
-    //     assert_eq!(u.determinant(), 0);
-    //
-    //     let mut u = Matrix::from([[1, 4, 2], [0, 0, 0], [3, 9, 5]]);
-    //     assert_eq!(u.determinant(), 0);
-    //
-    //     let mut u = Matrix::from([[1, 4, 2], [3, 9, 5], [3, 9, 5]]);
-    //     assert_eq!(u.determinant(), 0);
-    //
-    //     let mut u = Matrix::from([[1, 4, 2], [2, 8, 4], [3, 9, 5]]);
-    //     assert_eq!(u.determinant(), 0);
-    //
-    //     let mut u = Matrix::from([[4, 4, 2], [2, 8, 1], [6, 12, 3]]);
-    //     assert_eq!(u.determinant(), 0);
-    //
-    //     let mut u = Matrix::from([[4, 4], [4, 4]]);
-    //     assert_eq!(u.determinant(), 0);
-    //
-    //     let mut u = Matrix::from([[1, 2], [2, 4]]);
-    //     assert_eq!(u.determinant(), 0);
-    //
-    //     let mut u = Matrix::from([[1, 2], [0, 0]]);
-    //     assert_eq!(u.determinant(), 0);
-    // }
-    //
-    // #[test]
-    // fn indentity_matrix_basics() {
-    //     let mut u = Matrix::from([[1., 2.], [3., 4.]]);
-    //     let result = u.identity_matrix();
-    //     assert_eq!(result.cols[0], vec![1., 0.]);
-    //     assert_eq!(result.cols[1], vec![0., 1.]);
-    //
-    //     let mut u = Matrix::from([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]]);
-    //     let result = u.identity_matrix();
-    //     assert_eq!(result.cols[0], vec![1., 0., 0.]);
-    //     assert_eq!(result.cols[1], vec![0., 1., 0.]);
-    //     assert_eq!(result.cols[2], vec![0., 0., 1.]);
-    //
-    //     let mut u = Matrix::from(&[
-    //         &[1., 2., 3., 4.],
-    //         &[5., 6., 7., 8.],
-    //         &[8., 9., 10., 11.],
-    //         &[12., 13., 14., 15.],
-    //     ]);
-    //     let result = u.identity_matrix();
-    //     assert_eq!(result.cols[0], vec![1., 0., 0., 0.]);
-    //     assert_eq!(result.cols[1], vec![0., 1., 0., 0.]);
-    //     assert_eq!(result.cols[2], vec![0., 0., 1., 0.]);
-    //     assert_eq!(result.cols[3], vec![0., 0., 0., 1.]);
-    // }
-    //
-    // #[test]
-    // fn inverse_matrix_2x2() {
-    //     let mut u = Matrix::from([[1., 0.], [0., 1.]]);
-    //     let result = u.inverse();
-    //     match result {
-    //         Ok(r) => {
-    //             assert_eq!(r.cols[0], vec![1., 0.]);
-    //             assert_eq!(r.cols[1], vec![0., 1.]);
-    //         }
-    //         Err(_) => {
-    //             assert_eq!(0, 1);
-    //         }
-    //     }
-    //
-    //     let mut u = Matrix::from([[2., 0.], [0., 2.]]);
-    //     let result = u.inverse();
-    //     match result {
-    //         Ok(r) => {
-    //             assert_eq!(r.cols[0], vec![0.5, 0.]);
-    //             assert_eq!(r.cols[1], vec![0., 0.5]);
-    //         }
-    //         Err(_) => {
-    //             assert_eq!(0, 1);
-    //         }
-    //     }
-    //
-    //     let mut u = Matrix::from([[0.5, 0.], [0., 0.5]]);
-    //     let result = u.inverse();
-    //     match result {
-    //         Ok(r) => {
-    //             assert_eq!(r.cols[0], vec![2., 0.]);
-    //             assert_eq!(r.cols[1], vec![0., 2.]);
-    //         }
-    //         Err(_) => {
-    //             assert_eq!(0, 1);
-    //         }
-    //     }
-    //
-    //     let mut u = Matrix::from([[0., 1.], [1., 0.]]);
-    //     let result = u.inverse();
-    //     match result {
-    //         Ok(r) => {
-    //             assert_eq!(r.cols[0], vec![0., 1.]);
-    //             assert_eq!(r.cols[1], vec![1., 0.]);
-    //         }
-    //         Err(_) => {
-    //             assert_eq!(0, 1);
-    //         }
-    //     }
-    //
-    //     let mut u = Matrix::from([[1., 2.], [3., 4.]]);
-    //     let result = u.inverse();
-    //     match result {
-    //         Ok(r) => {
-    //             assert_eq!(r.cols[0], vec![-2., 1.]);
-    //             assert_eq!(r.cols[1], vec![1.5, -0.5]);
-    //         }
-    //         Err(_) => {
-    //             assert_eq!(0, 1);
-    //         }
-    //     }
-    //
-    //     let mut u = Matrix::from([[4., 7.], [2., 6.]]);
-    //     let result = u.inverse();
-    //     match result {
-    //         Ok(r) => {
-    //             assert_eq!(r.cols[0], vec![0.6, -0.7]);
-    //             assert_eq!(r.cols[1], vec![-0.2, 0.4]);
-    //         }
-    //         Err(_) => {
-    //             assert_eq!(0, 1);
-    //         }
-    //     }
-    // }
-    //
-    // #[test]
-    // fn inverse_matrix_3x3() {
-    //     let mut u = Matrix::from([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
-    //     let result = u.inverse();
-    //     match result {
-    //         Ok(r) => {
-    //             assert_eq!(r.cols[0], vec![1., 0., 0.]);
-    //             assert_eq!(r.cols[1], vec![0., 1., 0.]);
-    //             assert_eq!(r.cols[2], vec![0., 0., 1.]);
-    //         }
-    //         Err(_) => {
-    //             assert_eq!(0, 1);
-    //         }
-    //     }
-    //
-    //     let mut u = Matrix::from([[2., 0., 0.], [0., 2., 0.], [0., 0., 2.]]);
-    //     let result = u.inverse();
-    //     match result {
-    //         Ok(r) => {
-    //             assert_eq!(r.cols[0], vec![0.5, 0., 0.]);
-    //             assert_eq!(r.cols[1], vec![0., 0.5, 0.]);
-    //             assert_eq!(r.cols[2], vec![0., 0., 0.5]);
-    //         }
-    //         Err(_) => {
-    //             assert_eq!(0, 1);
-    //         }
-    //     }
-    //
-    //     let mut u = Matrix::from([[8., 5., -2.], [4., 7., 20.], [7., 6., 1.]]);
-    //     let result = u.inverse();
-    //     match result {
-    //         Ok(r) => {
-    //             assert_eq!(r.cols[0], vec![0.649425287, 0.097701149, -0.655172414]);
-    //             assert_eq!(
-    //                 r.cols[1],
-    //                 vec![-0.781609195, -0.126436782, 0.965517241]
-    //             );
-    //             assert_eq!(r.cols[2], vec![0.143678161, 0.07471265, -0.206896552]);
-    //         }
-    //         Err(_) => {
-    //             assert_eq!(0, 1);
-    //         }
-    //     }
-    // }
-    //
-    // #[test]
-    // fn matrix_rank() {
-    //     let mut u = Matrix::from([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
-    //     assert_eq!(u.rank(), 3);
-    //
-    //     let mut u = Matrix::from([[1, 2, 0, 0], [2, 4, 0, 0], [-1, 2, 1, 1]]);
-    //     assert_eq!(u.rank(), 2);
-    //
-    //     let mut u = Matrix::from(&[
-    //         &[8., 5., -2.],
-    //         &[4., 7., 20.],
-    //         &[7., 6., 1.],
-    //         &[21., 18., 7.],
-    //     ]);
-    //     assert_eq!(u.rank(), 3);
-    //
-    //     let mut u = Matrix::from([[0, 0], [0, 0]]);
-    //     assert_eq!(u.rank(), 0);
-    //
-    //     let mut u = Matrix::from([[1, 0], [0, 1]]);
-    //     assert_eq!(u.rank(), 2);
-    //
-    //     let mut u = Matrix::from([[2, 0], [0, 2]]);
-    //     assert_eq!(u.rank(), 2);
-    //
-    //     let mut u = Matrix::from([[1, 1], [1, 1]]);
-    //     assert_eq!(u.rank(), 1);
-    //
-    //     let mut u = Matrix::from([[0, 1], [1, 0]]);
-    //     assert_eq!(u.rank(), 2);
-    //
-    //     let mut u = Matrix::from([[1, 2], [3, 4]]);
-    //     assert_eq!(u.rank(), 2);
-    //
-    //     let mut u = Matrix::from([[-7, 5], [4, 6]]);
-    //     assert_eq!(u.rank(), 2);
-    //
-    //     let mut u = Matrix::from([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
-    //     assert_eq!(u.rank(), 3);
-    //
-    //     let mut u = Matrix::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
-    //     assert_eq!(u.rank(), 2);
-    // }
+        assert_eq!(Vec::from([42., 42.]), u[0]);
+        assert_eq!(Vec::from([42., 42.]), u[1]);
+    }
+
+    #[test]
+    fn matrix_mul_vec() {
+        let u = Matrix::from([[1., 0.], [0., 1.]]);
+        let v = Vector::from([4., 2.]);
+        let result = u.mul_vec(&v);
+        assert_eq!(result[0], 4.);
+        assert_eq!(result[1], 2.);
+
+        let u = Matrix::from([[2., 0.], [0., 2.]]);
+        let v = Vector::from([4., 2.]);
+        let result = u.mul_vec(&v);
+        assert_eq!(result[0], 8.);
+        assert_eq!(result[1], 4.);
+
+        let u = Matrix::from([[2., -2.], [-2., 2.]]);
+        let v = Vector::from([4., 2.]);
+        let result = u.mul_vec(&v);
+        assert_eq!(result[0], 4.);
+        assert_eq!(result[1], -4.);
+
+        let u = Matrix::from([[0., 0.], [0., 0.]]);
+        let v = Vector::from([4., 2.]);
+        let result = u.mul_vec(&v);
+        assert_eq!(result[0], 0.);
+        assert_eq!(result[1], 0.);
+
+        let u = Matrix::from([[1., 1.], [1., 1.]]);
+        let v = Vector::from([4., 2.]);
+        let result = u.mul_vec(&v);
+        assert_eq!(result[0], 6.);
+        assert_eq!(result[1], 6.);
+
+        let u = Matrix::from([[0.5, 0.], [0., 0.5]]);
+        let v = Vector::from([4., 2.]);
+        let result = u.mul_vec(&v);
+        assert_eq!(result[0], 2.);
+        assert_eq!(result[1], 1.);
+    }
+
+    #[test]
+    fn matrix_mul_mat() {
+        let u = Matrix::from([[1., 0.], [0., 1.]]);
+        let v = Matrix::from([[1., 0.], [0., 1.]]);
+        let result = u.mul_mat(&v);
+        assert_eq!(result[0], Vec::from([1., 0.]));
+        assert_eq!(result[1], Vec::from([0., 1.]));
+
+        let u = Matrix::from([[1., 0.], [0., 1.]]);
+        let v = Matrix::from([[2., 1.], [4., 2.]]);
+        let result = u.mul_mat(&v);
+        assert_eq!(result[0], Vec::from([2., 1.]));
+        assert_eq!(result[1], Vec::from([4., 2.]));
+
+        let u = Matrix::from([[3., -5.], [6., 8.]]);
+        let v = Matrix::from([[2., 1.], [4., 2.]]);
+        let result = u.mul_mat(&v);
+        assert_eq!(result[0], Vec::from([-14., -7.]));
+        assert_eq!(result[1], Vec::from([44., 22.]));
+
+        let u = Matrix::from([[0., 4., -2.], [-4., -3., 0.]]);
+        let v = Matrix::from([[0., 1.], [1., -1.], [2., 3.]]);
+        let result = u.mul_mat(&v);
+        assert_eq!(result[0], Vec::from([0., -10.]));
+        assert_eq!(result[1], Vec::from([-3., -1.]));
+    }
+
+    #[test]
+    fn matrix_mul_operators() {
+        let u = Matrix::from([[1, 2, 3], [4, 5, 6]]);
+        let v = Matrix::from([[1, 0], [0, 1], [1, 1]]);
+        let x = Vector::from([1, 0, -1]);
+        assert_eq!(Matrix::from([[4, 5], [10, 11]]), &u * &v);
+        assert_eq!(Matrix::from([[1, 2, 3], [4, 5, 6], [5, 7, 9]]), &v * &u);
+        assert_eq!(vec![-2, -2], (&u * &x).e);
+        assert_eq!(vec![-2, -2], (u.clone() * x).e);
+        assert_eq!(Matrix::from([[4, 5], [10, 11]]), u.clone() * v.clone());
+
+        let col_major = v.to_layout(Layout::ColMajor);
+        assert_eq!(Matrix::from([[4, 5], [10, 11]]), &u * &col_major);
+
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            }),
+            u.try_mul_mat(&Matrix::zeros(2, 2))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension mismatch: expected 3, found 2")]
+    fn matrix_mul_vec_mismatch_panics() {
+        let u = Matrix::from([[1, 2, 3], [4, 5, 6]]);
+        let _ = &u * &Vector::from([1, 2]);
+    }
+
+    #[test]
+    fn matrix_trace_with_zero() {
+        let u = Matrix::from([[1., 0.], [0., 1.]]);
+        assert_eq!(u.trace(), 2.0);
+
+        let u = Matrix::from([[0., 0.], [0., 0.]]);
+        assert_eq!(u.trace(), 0.0);
+    }
+
+    #[test]
+    fn matrix_trace_positive() {
+        let u = Matrix::from([[2., -5., 0.], [4., 3., 7.], [-2., 3., 4.]]);
+        assert_eq!(u.trace(), 9.0);
+
+        let u = Matrix::from([[1., 2.], [3., 4.]]);
+        assert_eq!(u.trace(), 5.0);
+
+        let u = Matrix::from([[8., -7.], [4., 2.]]);
+        assert_eq!(u.trace(), 10.0);
+    }
+
+    #[test]
+    fn matrix_trace_negative() {
+        let u = Matrix::from([[-2., -8., 4.], [1., -23., 4.], [0., 6., 4.]]);
+        assert_eq!(u.trace(), -21.0);
+    }
+
+    #[test]
+    fn matrix_transpose_zero() {
+        let u = Matrix::from([[0., 0.], [0., 0.]]);
+        let result = u.transpose();
+        assert_eq!(result[0], Vec::from([0., 0.]));
+        assert_eq!(result[1], Vec::from([0., 0.]));
+    }
+
+    #[test]
+    fn matrix_transpose_no_change() {
+        let u = Matrix::from([[1., 0.], [0., 1.]]);
+        let result = u.transpose();
+        assert_eq!(result[0], Vec::from([1., 0.]));
+        assert_eq!(result[1], Vec::from([0., 1.]));
+    }
+
+    #[test]
+    fn matrix_transpose_reverse() {
+        let u = Matrix::from([[1., 2.], [3., 4.]]);
+        let result = u.transpose();
+        assert_eq!(result[0], Vec::from([1., 3.]));
+        assert_eq!(result[1], Vec::from([2., 4.]));
+    }
+
+    #[test]
+    fn matrix_transpose_bin_no_change() {
+        let u = Matrix::from([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
+        let result = u.transpose();
+        assert_eq!(result[0], Vec::from([1., 0., 0.]));
+        assert_eq!(result[1], Vec::from([0., 1., 0.]));
+        assert_eq!(result[2], Vec::from([0., 0., 1.]));
+    }
+
+    #[test]
+    fn matrix_transpose_positive() {
+        let u = Matrix::from([[1., 2., 3.], [4., 5., 6.]]);
+        let result = u.transpose();
+        assert_eq!(result[0], Vec::from([1., 4.]));
+        assert_eq!(result[1], Vec::from([2., 5.]));
+        assert_eq!(result[2], Vec::from([3., 6.]));
+
+        let u = Matrix::from([[1., 2.], [3., 4.], [5., 6.]]);
+        let result = u.transpose();
+        assert_eq!(result[0], Vec::from([1., 3., 5.]));
+        assert_eq!(result[1], Vec::from([2., 4., 6.]));
+    }
+
+    #[test]
+    fn matrix_rref_basics() {
+        let u = Matrix::from([[1, -1, 2], [3, 2, 1], [2, -3, -2]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([1, 0, 0]));
+        assert_eq!(result[1], Vec::from([0, 1, 0]));
+        assert_eq!(result[2], Vec::from([0, 0, 1]));
+
+        let u = Matrix::from([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([1, 0, 0]));
+        assert_eq!(result[1], Vec::from([0, 1, 0]));
+        assert_eq!(result[2], Vec::from([0, 0, 1]));
+
+        let u = Matrix::from([[1, 2], [3, 4]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([1, 0]));
+        assert_eq!(result[1], Vec::from([0, 1]));
+
+        let u = Matrix::from([[1, 2], [2, 4]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([1, 2]));
+        assert_eq!(result[1], Vec::from([0, 0]));
+
+        let u = Matrix::from([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([1., 0., -1.]));
+        assert_eq!(result[1], Vec::from([0., 1., 2.]));
+        assert_eq!(result[2], Vec::from([0., 0., 0.]));
+
+        let u = Matrix::from([[8., 5., -2.], [4., 7., 20.], [7., 6., 1.]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([1., 0., 0.]));
+        assert_eq!(result[1], Vec::from([0., 1., 0.]));
+        assert_eq!(result[2], Vec::from([0., 0., 1.]));
+    }
+
+    #[test]
+    fn matrix_rref_edge_cases() {
+        let u = Matrix::from([[0, 0], [0, 0]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([0, 0]));
+        assert_eq!(result[1], Vec::from([0, 0]));
+
+        let u = Matrix::from([[1, 0], [0, 1]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([1, 0]));
+        assert_eq!(result[1], Vec::from([0, 1]));
+
+        let u = Matrix::from([[4., 2.], [2., 1.]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([1., 0.5]));
+        assert_eq!(result[1], Vec::from([0., 0.]));
+
+        let u = Matrix::from([[-7, 2], [4, 8]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([1, 0]));
+        assert_eq!(result[1], Vec::from([0, 1]));
+
+        let u = Matrix::from([[1, 2], [4, 8]]);
+        let result = u.row_echelon();
+        assert_eq!(result[0], Vec::from([1, 2]));
+        assert_eq!(result[1], Vec::from([0, 0]));
+    }
+
+    #[test]
+    fn matrix_determinant_2() {
+        let u = Matrix::from([[1., -1.], [-1., 1.]]);
+        assert_eq!(u.determinant(), 0.);
+
+        let u = Matrix::from([[0, 0], [0, 0]]);
+        assert_eq!(u.determinant(), 0);
+
+        let u = Matrix::from([[1, 0], [0, 1]]);
+        assert_eq!(u.determinant(), 1);
+
+        let u = Matrix::from([[2, 0], [0, 2]]);
+        assert_eq!(u.determinant(), 4);
+
+        let u = Matrix::from([[1, 1], [1, 1]]);
+        assert_eq!(u.determinant(), 0);
+
+        let u = Matrix::from([[0, 1], [1, 0]]);
+        assert_eq!(u.determinant(), -1);
+
+        let u = Matrix::from([[1, 2], [3, 4]]);
+        assert_eq!(u.determinant(), -2);
+
+        let u = Matrix::from([[-7, 5], [4, 6]]);
+        assert_eq!(u.determinant(), -62);
+    }
+
+    #[test]
+    fn matrix_determinant_3() {
+        let u = Matrix::from([[2., 0., 0.], [0., 2., 0.], [0., 0., 2.]]);
+        assert_eq!(u.determinant(), 8.);
+
+        let u = Matrix::from([[4, 2, 5], [1, 8, 9], [2, 7, 3]]);
+        assert_eq!(u.determinant(), -171);
+
+        let u = Matrix::from([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+        assert_eq!(u.determinant(), 1);
+    }
+
+    #[test]
+    fn matrix_determinant_4() {
+        let u = Matrix::from([
+            [8., 5., -2., 4.],
+            [4., 2.5, 20., 4.],
+            [8., 5., 1., 4.],
+            [28., -4., 17., 1.],
+        ]);
+
+        assert_eq!(u.determinant(), 1032.);
+
+        let u = Matrix::from([[1, 1, 1, -1], [1, 1, -1, 1], [1, -1, 1, 1], [-1, 1, 1, 1]]);
+
+        assert_eq!(u.determinant(), -16);
+    }
+
+    #[test]
+    fn matrix_determinant_0() {
+        let u = Matrix::from([[1, 4, 2], [1, 4, 2], [3, 9, 5]]);
+        assert_eq!(u.determinant(), 0);
+
+        let u = Matrix::from([[1, 4, 2], [0, 0, 0], [3, 9, 5]]);
+        assert_eq!(u.determinant(), 0);
+
+        let u = Matrix::from([[1, 4, 2], [3, 9, 5], [3, 9, 5]]);
+        assert_eq!(u.determinant(), 0);
+
+        let u = Matrix::from([[1, 4, 2], [2, 8, 4], [3, 9, 5]]);
+        assert_eq!(u.determinant(), 0);
+
+        let u = Matrix::from([[4, 4, 2], [2, 8, 1], [6, 12, 3]]);
+        assert_eq!(u.determinant(), 0);
+
+        let u = Matrix::from([[4, 4], [4, 4]]);
+        assert_eq!(u.determinant(), 0);
+
+        let u = Matrix::from([[1, 2], [2, 4]]);
+        assert_eq!(u.determinant(), 0);
+
+        let u = Matrix::from([[1, 2], [0, 0]]);
+        assert_eq!(u.determinant(), 0);
+    }
+
+    #[test]
+    fn indentity_matrix_basics() {
+        let u = Matrix::from([[1., 2.], [3., 4.]]);
+        let result = u.identity_matrix();
+        assert_eq!(result[0], vec![1., 0.]);
+        assert_eq!(result[1], vec![0., 1.]);
+
+        let u = Matrix::from([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]]);
+        let result = u.identity_matrix();
+        assert_eq!(result[0], vec![1., 0., 0.]);
+        assert_eq!(result[1], vec![0., 1., 0.]);
+        assert_eq!(result[2], vec![0., 0., 1.]);
+
+        let u = Matrix::from([
+            [1., 2., 3., 4.],
+            [5., 6., 7., 8.],
+            [8., 9., 10., 11.],
+            [12., 13., 14., 15.],
+        ]);
+        let result = u.identity_matrix();
+        assert_eq!(result[0], vec![1., 0., 0., 0.]);
+        assert_eq!(result[1], vec![0., 1., 0., 0.]);
+        assert_eq!(result[2], vec![0., 0., 1., 0.]);
+        assert_eq!(result[3], vec![0., 0., 0., 1.]);
+    }
+
+    fn assert_approx<const R: usize, const C: usize>(
+        expected: [[f64; C]; R],
+        actual: &Matrix<f64>,
+    ) {
+        assert_eq!((R, C), actual.shape());
+        for (i, row) in expected.iter().enumerate() {
+            for (j, e) in row.iter().enumerate() {
+                assert!(
+                    (actual[(i, j)] - e).abs() < 1e-8,
+                    "({}, {}): expected {}, found {}",
+                    i,
+                    j,
+                    e,
+                    actual[(i, j)]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_matrix_2x2() {
+        let u = Matrix::from([[1., 0.], [0., 1.]]);
+        assert_approx([[1., 0.], [0., 1.]], &u.inverse().unwrap());
+
+        let u = Matrix::from([[2., 0.], [0., 2.]]);
+        assert_approx([[0.5, 0.], [0., 0.5]], &u.inverse().unwrap());
+
+        let u = Matrix::from([[0.5, 0.], [0., 0.5]]);
+        assert_approx([[2., 0.], [0., 2.]], &u.inverse().unwrap());
+
+        let u = Matrix::from([[0., 1.], [1., 0.]]);
+        assert_approx([[0., 1.], [1., 0.]], &u.inverse().unwrap());
+
+        let u = Matrix::from([[1., 2.], [3., 4.]]);
+        assert_approx([[-2., 1.], [1.5, -0.5]], &u.inverse().unwrap());
+
+        let u = Matrix::from([[4., 7.], [2., 6.]]);
+        assert_approx([[0.6, -0.7], [-0.2, 0.4]], &u.inverse().unwrap());
+    }
+
+    #[test]
+    fn inverse_matrix_3x3() {
+        let u = Matrix::from([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
+        assert_approx(
+            [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &u.inverse().unwrap(),
+        );
+
+        let u = Matrix::from([[2., 0., 0.], [0., 2., 0.], [0., 0., 2.]]);
+        assert_approx(
+            [[0.5, 0., 0.], [0., 0.5, 0.], [0., 0., 0.5]],
+            &u.inverse().unwrap(),
+        );
+
+        let u = Matrix::from([[8., 5., -2.], [4., 7., 20.], [7., 6., 1.]]);
+        assert_approx(
+            [
+                [0.649425287, 0.097701149, -0.655172414],
+                [-0.781609195, -0.126436782, 0.965517241],
+                [0.143678161, 0.074712644, -0.206896552],
+            ],
+            &u.inverse().unwrap(),
+        );
+    }
+
+    #[test]
+    fn inverse_matrix_errors() {
+        let u = Matrix::from([[1., 2.], [2., 4.]]);
+        assert_eq!(Err(LinalgError::Singular), u.inverse());
+
+        // Rounding leaves a tiny nonzero pivot; only a tolerance sees it.
+        let u = Matrix::from([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]]);
+        assert!(u.lu().unwrap().is_singular_with(3. * f64::EPSILON));
+
+        let u = Matrix::from([[1., 2., 3.]]);
+        assert_eq!(
+            Err(LinalgError::NotSquare { rows: 1, cols: 3 }),
+            u.inverse()
+        );
+    }
+
+    #[test]
+    fn complex_matrices() {
+        let c = Complex::new;
+        let u = Matrix::from([[c(1., 1.), c(2., 0.)], [c(3., 0.), c(4., -1.)]]);
+        assert_eq!(
+            Matrix::from([[c(1., -1.), c(3., 0.)], [c(2., 0.), c(4., 1.)]]),
+            u.conjugate_transpose()
+        );
+        assert_eq!(c(5., 0.), u.trace());
+        // (1 + i)(4 - i) - 6 = -1 + 3i
+        assert_eq!(c(-1., 3.), u.determinant());
+        assert_eq!(2, u.rank());
+
+        let inv = u.inverse().unwrap();
+        let id = &u * &inv;
+        for i in 0..2 {
+            for j in 0..2 {
+                let expected = if i == j { c(1., 0.) } else { c(0., 0.) };
+                assert!((id[(i, j)] - expected).norm() < 1e-12);
+            }
+        }
+
+        let singular = Matrix::from([[c(1., 1.), c(2., 2.)], [c(0., 1.), c(0., 2.)]]);
+        assert_eq!(c(0., 0.), singular.determinant());
+        assert_eq!(Err(LinalgError::Singular), singular.inverse());
+        assert_eq!(1, singular.rank());
+    }
+
+    #[test]
+    fn matrix_square_errors() {
+        let u = Matrix::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(
+            Err(LinalgError::NotSquare { rows: 2, cols: 3 }),
+            u.try_trace()
+        );
+        assert_eq!(
+            Err(LinalgError::NotSquare { rows: 2, cols: 3 }),
+            u.try_determinant()
+        );
+        assert_eq!(Ok(1), Matrix::<i32>::zeros(0, 0).try_determinant());
+    }
+
+    #[test]
+    fn matrix_rank() {
+        let u = Matrix::from([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
+        assert_eq!(u.rank(), 3);
+
+        let u = Matrix::from([[1, 2, 0, 0], [2, 4, 0, 0], [-1, 2, 1, 1]]);
+        assert_eq!(u.rank(), 2);
+
+        let u = Matrix::from([[8., 5., -2.], [4., 7., 20.], [7., 6., 1.], [21., 18., 7.]]);
+        assert_eq!(u.rank(), 3);
+
+        let u = Matrix::from([[0, 0], [0, 0]]);
+        assert_eq!(u.rank(), 0);
+
+        let u = Matrix::from([[1, 0], [0, 1]]);
+        assert_eq!(u.rank(), 2);
+
+        let u = Matrix::from([[2, 0], [0, 2]]);
+        assert_eq!(u.rank(), 2);
+
+        let u = Matrix::from([[1, 1], [1, 1]]);
+        assert_eq!(u.rank(), 1);
+
+        let u = Matrix::from([[0, 1], [1, 0]]);
+        assert_eq!(u.rank(), 2);
+
+        let u = Matrix::from([[1, 2], [3, 4]]);
+        assert_eq!(u.rank(), 2);
+
+        let u = Matrix::from([[-7, 5], [4, 6]]);
+        assert_eq!(u.rank(), 2);
+
+        let u = Matrix::from([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+        assert_eq!(u.rank(), 3);
+
+        let u = Matrix::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(u.rank(), 2);
+
+        // Rounding leaves exact elimination a spurious third pivot.
+        let u = Matrix::from([[0.1, 0.2, 0.3], [0.4, 0.5, 0.6], [0.7, 0.8, 0.9]]);
+        assert_eq!(u.rank(), 3);
+        assert_eq!(u.numerical_rank(None), Ok(2));
+        assert_eq!(u.numerical_rank(Some(1e-20)), Ok(3));
+    }
 }
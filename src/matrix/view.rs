@@ -0,0 +1,907 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, RangeBounds, Sub, SubAssign};
+
+use crate::error::{check_dimension, check_shape, LinalgError};
+use crate::scalar::Scalar;
+use crate::vector::{resolve_range, Vector, VectorView, VectorViewMut};
+
+use super::{gemm, Matrix};
+
+/// Borrowed `rows x cols` window into a matrix: element `(i, j)` sits
+/// `i * row_stride + j * col_stride` elements past element `(0, 0)`.
+///
+/// Slicing and transposing a view only adjusts its shape and strides, so
+/// blocks, rows, columns and diagonals are reached without copying.
+pub struct MatrixView<'a, K> {
+    ptr: *const K,
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+    marker: PhantomData<&'a K>,
+}
+
+/// Mutable counterpart of [`MatrixView`]. It can be split into disjoint
+/// mutable blocks with [`MatrixViewMut::split_at_row`] and
+/// [`MatrixViewMut::split_at_col`].
+pub struct MatrixViewMut<'a, K> {
+    ptr: *mut K,
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+    marker: PhantomData<&'a mut K>,
+}
+
+// The views behave like `&[K]` and `&mut [K]`.
+unsafe impl<K: Sync> Send for MatrixView<'_, K> {}
+unsafe impl<K: Sync> Sync for MatrixView<'_, K> {}
+unsafe impl<K: Send> Send for MatrixViewMut<'_, K> {}
+unsafe impl<K: Sync> Sync for MatrixViewMut<'_, K> {}
+
+impl<K> Clone for MatrixView<'_, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for MatrixView<'_, K> {}
+
+/// Fails with `DimensionMismatch` unless a buffer of `len` elements holds
+/// every element of a `rows x cols` view with the given strides.
+fn check_span(
+    len: usize,
+    rows: usize,
+    cols: usize,
+    (row_stride, col_stride): (usize, usize),
+) -> Result<(), LinalgError> {
+    if rows == 0 || cols == 0 {
+        return Ok(());
+    }
+    let needed = (rows - 1) * row_stride + (cols - 1) * col_stride + 1;
+    if needed > len {
+        return Err(LinalgError::DimensionMismatch {
+            expected: needed,
+            found: len,
+        });
+    }
+    Ok(())
+}
+
+/// Whether the strides give every element its own location. This accepts
+/// the layouts where one dimension nests inside the other's stride, which
+/// covers every view that slicing a matrix can produce.
+fn strides_are_disjoint(
+    rows: usize,
+    cols: usize,
+    (row_stride, col_stride): (usize, usize),
+) -> bool {
+    if rows <= 1 || cols <= 1 {
+        let (n, stride) = if rows <= 1 {
+            (cols, col_stride)
+        } else {
+            (rows, row_stride)
+        };
+        return n <= 1 || stride > 0;
+    }
+    let (inner, outer) = if row_stride <= col_stride {
+        ((row_stride, rows), col_stride)
+    } else {
+        ((col_stride, cols), row_stride)
+    };
+    inner.0 > 0 && inner.0 * (inner.1 - 1) < outer
+}
+
+impl<'a, K> MatrixView<'a, K> {
+    /// # Safety
+    ///
+    /// Every element `(i, j)` in the shape must be valid for reads during
+    /// `'a`, and not written through anything else.
+    pub(crate) unsafe fn from_raw_parts(
+        ptr: *const K,
+        rows: usize,
+        cols: usize,
+        (row_stride, col_stride): (usize, usize),
+    ) -> Self {
+        MatrixView {
+            ptr,
+            rows,
+            cols,
+            row_stride,
+            col_stride,
+            marker: PhantomData,
+        }
+    }
+
+    /// Views `data` as a `rows x cols` matrix with the given
+    /// `(row_stride, col_stride)`, failing with `DimensionMismatch` if
+    /// `data` is too short. A row-major matrix with leading dimension `ld`
+    /// has strides `(ld, 1)`, a column-major one `(1, ld)`.
+    pub fn from_slice(
+        data: &'a [K],
+        rows: usize,
+        cols: usize,
+        strides: (usize, usize),
+    ) -> Result<Self, LinalgError> {
+        check_span(data.len(), rows, cols, strides)?;
+        // SAFETY: every element lies within `data`, borrowed for `'a`.
+        Ok(unsafe { MatrixView::from_raw_parts(data.as_ptr(), rows, cols, strides) })
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Distance in the underlying buffer between `(i, j)` and `(i + 1, j)`,
+    /// and between `(i, j)` and `(i, j + 1)`.
+    pub fn strides(&self) -> (usize, usize) {
+        (self.row_stride, self.col_stride)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<&'a K> {
+        // SAFETY: in bounds, see `from_raw_parts`.
+        (i < self.rows && j < self.cols)
+            .then(|| unsafe { &*self.ptr.wrapping_add(self.offset(i, j)) })
+    }
+
+    fn offset(&self, i: usize, j: usize) -> usize {
+        i * self.row_stride + j * self.col_stride
+    }
+
+    /// Rows `range`, with all the columns.
+    pub fn slice_rows(self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = resolve_range(range, self.rows);
+        MatrixView {
+            ptr: self.ptr.wrapping_add(start * self.row_stride),
+            rows: end - start,
+            ..self
+        }
+    }
+
+    /// Columns `range`, with all the rows.
+    pub fn slice_cols(self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = resolve_range(range, self.cols);
+        MatrixView {
+            ptr: self.ptr.wrapping_add(start * self.col_stride),
+            cols: end - start,
+            ..self
+        }
+    }
+
+    /// The block at the intersection of `rows` and `cols`.
+    pub fn submatrix(self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> Self {
+        self.slice_rows(rows).slice_cols(cols)
+    }
+
+    /// Row `i`, panicking if it is out of bounds.
+    pub fn row(self, i: usize) -> VectorView<'a, K> {
+        let row = self.slice_rows(i..i + 1);
+        // SAFETY: the elements of the row are elements of `self`.
+        unsafe { VectorView::from_raw_parts(row.ptr, row.cols, row.col_stride) }
+    }
+
+    /// Column `j`, panicking if it is out of bounds.
+    pub fn column(self, j: usize) -> VectorView<'a, K> {
+        let column = self.slice_cols(j..j + 1);
+        // SAFETY: the elements of the column are elements of `self`.
+        unsafe { VectorView::from_raw_parts(column.ptr, column.rows, column.row_stride) }
+    }
+
+    /// The main diagonal, of length `min(rows, cols)`.
+    pub fn diagonal(self) -> VectorView<'a, K> {
+        let len = self.rows.min(self.cols);
+        // SAFETY: element `k` is `(k, k)`, which is in bounds.
+        unsafe { VectorView::from_raw_parts(self.ptr, len, self.row_stride + self.col_stride) }
+    }
+
+    /// The transpose, sharing the elements of `self`.
+    pub fn transpose_view(self) -> Self {
+        MatrixView {
+            rows: self.cols,
+            cols: self.rows,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+            ..self
+        }
+    }
+
+    /// Copies the elements into an owned, row-major matrix.
+    pub fn to_matrix(&self) -> Matrix<K>
+    where
+        K: Copy,
+    {
+        Matrix::from_fn(self.rows, self.cols, |i, j| self[(i, j)])
+    }
+
+    /// Panics if the number of columns does not match the size of `x`.
+    pub fn mul_vec(&self, x: VectorView<K>) -> Vector<K>
+    where
+        K: Scalar,
+    {
+        self.try_mul_vec(x).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_mul_vec(&self, x: VectorView<K>) -> Result<Vector<K>, LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.cols, x.size())?;
+        Ok(Vector::from(
+            (0..self.rows)
+                .map(|i| self.row(i).dot(x))
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    /// Panics if the number of columns of `self` does not match the number
+    /// of rows of `other`.
+    pub fn mul_mat(&self, other: MatrixView<K>) -> Matrix<K>
+    where
+        K: Scalar,
+    {
+        self.try_mul_mat(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_mul_mat(&self, other: MatrixView<K>) -> Result<Matrix<K>, LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.cols, other.rows)?;
+        let mut result = Matrix::zeros(self.rows, other.cols);
+        gemm(K::one(), *self, other, K::zero(), result.view_mut());
+        Ok(result)
+    }
+}
+
+impl<'a, K> MatrixViewMut<'a, K> {
+    /// # Safety
+    ///
+    /// Every element `(i, j)` in the shape must be distinct and valid for
+    /// reads and writes during `'a`, and not accessed through anything
+    /// else.
+    pub(crate) unsafe fn from_raw_parts(
+        ptr: *mut K,
+        rows: usize,
+        cols: usize,
+        (row_stride, col_stride): (usize, usize),
+    ) -> Self {
+        MatrixViewMut {
+            ptr,
+            rows,
+            cols,
+            row_stride,
+            col_stride,
+            marker: PhantomData,
+        }
+    }
+
+    /// Mutable [`MatrixView::from_slice`].
+    ///
+    /// # Panics
+    ///
+    /// If the strides make two elements share a location.
+    pub fn from_slice(
+        data: &'a mut [K],
+        rows: usize,
+        cols: usize,
+        strides: (usize, usize),
+    ) -> Result<Self, LinalgError> {
+        assert!(
+            strides_are_disjoint(rows, cols, strides),
+            "the strides of a mutable view must not make elements overlap"
+        );
+        check_span(data.len(), rows, cols, strides)?;
+        // SAFETY: the elements are distinct and lie within `data`, borrowed
+        // mutably for `'a`.
+        Ok(unsafe { MatrixViewMut::from_raw_parts(data.as_mut_ptr(), rows, cols, strides) })
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn strides(&self) -> (usize, usize) {
+        (self.row_stride, self.col_stride)
+    }
+
+    /// A read-only view of the same elements.
+    pub fn as_view(&self) -> MatrixView<'_, K> {
+        // SAFETY: borrows `self`, so nothing writes during the new lifetime.
+        unsafe { MatrixView::from_raw_parts(self.ptr, self.rows, self.cols, self.strides()) }
+    }
+
+    /// A shorter-lived mutable view of the same elements.
+    pub fn reborrow(&mut self) -> MatrixViewMut<'_, K> {
+        // SAFETY: borrows `self` mutably.
+        unsafe { MatrixViewMut::from_raw_parts(self.ptr, self.rows, self.cols, self.strides()) }
+    }
+
+    /// Turns the view into a read-only one for the rest of `'a`.
+    pub fn into_view(self) -> MatrixView<'a, K> {
+        // SAFETY: `self` is consumed.
+        unsafe { MatrixView::from_raw_parts(self.ptr, self.rows, self.cols, self.strides()) }
+    }
+
+    pub fn slice_rows(self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = resolve_range(range, self.rows);
+        MatrixViewMut {
+            ptr: self.ptr.wrapping_add(start * self.row_stride),
+            rows: end - start,
+            ..self
+        }
+    }
+
+    pub fn slice_cols(self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = resolve_range(range, self.cols);
+        MatrixViewMut {
+            ptr: self.ptr.wrapping_add(start * self.col_stride),
+            cols: end - start,
+            ..self
+        }
+    }
+
+    pub fn submatrix(self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> Self {
+        self.slice_rows(rows).slice_cols(cols)
+    }
+
+    /// Splits the view into rows `..k` and rows `k..`, which can be
+    /// modified independently.
+    pub fn split_at_row(self, k: usize) -> (Self, Self) {
+        assert!(
+            k <= self.rows,
+            "row {} out of bounds for {} rows",
+            k,
+            self.rows
+        );
+        // SAFETY: the two halves share no element.
+        let (top, bottom) = unsafe {
+            (
+                MatrixViewMut::from_raw_parts(self.ptr, k, self.cols, self.strides()),
+                MatrixViewMut::from_raw_parts(
+                    self.ptr.wrapping_add(k * self.row_stride),
+                    self.rows - k,
+                    self.cols,
+                    self.strides(),
+                ),
+            )
+        };
+        (top, bottom)
+    }
+
+    /// Splits the view into columns `..k` and columns `k..`, which can be
+    /// modified independently.
+    pub fn split_at_col(self, k: usize) -> (Self, Self) {
+        let (left, right) = self.transpose_view().split_at_row(k);
+        (left.transpose_view(), right.transpose_view())
+    }
+
+    pub fn row(self, i: usize) -> VectorViewMut<'a, K> {
+        let row = self.slice_rows(i..i + 1);
+        // SAFETY: the elements of the row are elements of `self`.
+        unsafe { VectorViewMut::from_raw_parts(row.ptr, row.cols, row.col_stride) }
+    }
+
+    pub fn column(self, j: usize) -> VectorViewMut<'a, K> {
+        let column = self.slice_cols(j..j + 1);
+        // SAFETY: the elements of the column are elements of `self`.
+        unsafe { VectorViewMut::from_raw_parts(column.ptr, column.rows, column.row_stride) }
+    }
+
+    pub fn diagonal(self) -> VectorViewMut<'a, K> {
+        let len = self.rows.min(self.cols);
+        let stride = self.row_stride + self.col_stride;
+        // SAFETY: element `k` is `(k, k)`, which is in bounds.
+        unsafe { VectorViewMut::from_raw_parts(self.ptr, len, stride) }
+    }
+
+    pub fn transpose_view(self) -> Self {
+        MatrixViewMut {
+            rows: self.cols,
+            cols: self.rows,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+            ..self
+        }
+    }
+
+    pub fn fill(&mut self, value: K)
+    where
+        K: Copy,
+    {
+        self.for_each(|e| *e = value);
+    }
+
+    /// Overwrites the elements with those of `other`. Panics if the shapes
+    /// differ.
+    pub fn copy_from(&mut self, other: MatrixView<K>)
+    where
+        K: Copy,
+    {
+        self.zip_apply(other, |a, b| *a = b);
+    }
+
+    /// Panics if the shapes differ.
+    pub fn add_mut(&mut self, other: MatrixView<K>)
+    where
+        K: Scalar,
+    {
+        self.zip_apply(other, |a, b| *a += b);
+    }
+
+    /// Panics if the shapes differ.
+    pub fn sub_mut(&mut self, other: MatrixView<K>)
+    where
+        K: Scalar,
+    {
+        self.zip_apply(other, |a, b| *a -= b);
+    }
+
+    pub fn try_add_mut(&mut self, other: MatrixView<K>) -> Result<(), LinalgError>
+    where
+        K: Scalar,
+    {
+        check_shape(self.shape(), other.shape())?;
+        self.add_mut(other);
+        Ok(())
+    }
+
+    pub fn try_sub_mut(&mut self, other: MatrixView<K>) -> Result<(), LinalgError>
+    where
+        K: Scalar,
+    {
+        check_shape(self.shape(), other.shape())?;
+        self.sub_mut(other);
+        Ok(())
+    }
+
+    pub fn scl(&mut self, a: K)
+    where
+        K: Scalar,
+    {
+        self.for_each(|e| *e *= a);
+    }
+
+    fn for_each<F: FnMut(&mut K)>(&mut self, mut f: F) {
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                f(&mut self[(i, j)]);
+            }
+        }
+    }
+
+    fn zip_apply<F>(&mut self, other: MatrixView<K>, mut f: F)
+    where
+        K: Copy,
+        F: FnMut(&mut K, K),
+    {
+        check_shape(self.shape(), other.shape()).unwrap_or_else(|e| panic!("{}", e));
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                f(&mut self[(i, j)], other[(i, j)]);
+            }
+        }
+    }
+}
+
+fn assert_in_bounds(i: usize, j: usize, rows: usize, cols: usize) {
+    assert!(
+        i < rows && j < cols,
+        "{}",
+        LinalgError::OutOfBounds {
+            index: (i, j),
+            shape: (rows, cols)
+        }
+    );
+}
+
+impl<K> Index<(usize, usize)> for MatrixView<'_, K> {
+    type Output = K;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert_in_bounds(i, j, self.rows, self.cols);
+        // SAFETY: in bounds.
+        unsafe { &*self.ptr.wrapping_add(self.offset(i, j)) }
+    }
+}
+
+impl<K> Index<(usize, usize)> for MatrixViewMut<'_, K> {
+    type Output = K;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert_in_bounds(i, j, self.rows, self.cols);
+        // SAFETY: in bounds.
+        unsafe {
+            &*self
+                .ptr
+                .wrapping_add(i * self.row_stride + j * self.col_stride)
+        }
+    }
+}
+
+impl<K> IndexMut<(usize, usize)> for MatrixViewMut<'_, K> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert_in_bounds(i, j, self.rows, self.cols);
+        // SAFETY: in bounds, and borrowed mutably through `self`.
+        unsafe {
+            &mut *self
+                .ptr
+                .wrapping_add(i * self.row_stride + j * self.col_stride)
+        }
+    }
+}
+
+/// Lists the rows.
+impl<K: Debug> Debug for MatrixView<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries((0..self.rows).map(|i| self.row(i)))
+            .finish()
+    }
+}
+
+impl<K: Debug> Debug for MatrixViewMut<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_view().fmt(f)
+    }
+}
+
+impl<K: PartialEq> PartialEq for MatrixView<'_, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.shape() == other.shape() && (0..self.rows).all(|i| self.row(i) == other.row(i))
+    }
+}
+
+impl<K: PartialEq> PartialEq<Matrix<K>> for MatrixView<'_, K> {
+    fn eq(&self, other: &Matrix<K>) -> bool {
+        *self == other.view()
+    }
+}
+
+impl<'a, K> From<&'a Matrix<K>> for MatrixView<'a, K> {
+    fn from(m: &'a Matrix<K>) -> Self {
+        m.view()
+    }
+}
+
+impl<'a, K> From<&'a mut Matrix<K>> for MatrixViewMut<'a, K> {
+    fn from(m: &'a mut Matrix<K>) -> Self {
+        m.view_mut()
+    }
+}
+
+impl<K> Matrix<K> {
+    /// A view of the whole matrix.
+    pub fn view(&self) -> MatrixView<'_, K> {
+        // SAFETY: the elements are those of `self.data`, borrowed.
+        unsafe {
+            MatrixView::from_raw_parts(self.data.as_ptr(), self.rows, self.cols, self.strides())
+        }
+    }
+
+    pub fn view_mut(&mut self) -> MatrixViewMut<'_, K> {
+        let strides = self.strides();
+        // SAFETY: the elements are those of `self.data`, borrowed mutably.
+        unsafe {
+            MatrixViewMut::from_raw_parts(self.data.as_mut_ptr(), self.rows, self.cols, strides)
+        }
+    }
+
+    /// Rows `range` as a view; `m.slice_rows(1..3)` borrows rows 1 and 2.
+    pub fn slice_rows(&self, range: impl RangeBounds<usize>) -> MatrixView<'_, K> {
+        self.view().slice_rows(range)
+    }
+
+    /// Columns `range` as a view.
+    pub fn slice_cols(&self, range: impl RangeBounds<usize>) -> MatrixView<'_, K> {
+        self.view().slice_cols(range)
+    }
+
+    pub fn submatrix(
+        &self,
+        rows: impl RangeBounds<usize>,
+        cols: impl RangeBounds<usize>,
+    ) -> MatrixView<'_, K> {
+        self.view().submatrix(rows, cols)
+    }
+
+    pub fn row(&self, i: usize) -> VectorView<'_, K> {
+        self.view().row(i)
+    }
+
+    pub fn column(&self, j: usize) -> VectorView<'_, K> {
+        self.view().column(j)
+    }
+
+    pub fn diagonal(&self) -> VectorView<'_, K> {
+        self.view().diagonal()
+    }
+
+    /// The transpose as a view, without copying; see [`Matrix::transpose`]
+    /// for an owned one.
+    pub fn transpose_view(&self) -> MatrixView<'_, K> {
+        self.view().transpose_view()
+    }
+
+    pub fn slice_rows_mut(&mut self, range: impl RangeBounds<usize>) -> MatrixViewMut<'_, K> {
+        self.view_mut().slice_rows(range)
+    }
+
+    pub fn slice_cols_mut(&mut self, range: impl RangeBounds<usize>) -> MatrixViewMut<'_, K> {
+        self.view_mut().slice_cols(range)
+    }
+
+    pub fn submatrix_mut(
+        &mut self,
+        rows: impl RangeBounds<usize>,
+        cols: impl RangeBounds<usize>,
+    ) -> MatrixViewMut<'_, K> {
+        self.view_mut().submatrix(rows, cols)
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> VectorViewMut<'_, K> {
+        self.view_mut().row(i)
+    }
+
+    pub fn column_mut(&mut self, j: usize) -> VectorViewMut<'_, K> {
+        self.view_mut().column(j)
+    }
+
+    pub fn diagonal_mut(&mut self) -> VectorViewMut<'_, K> {
+        self.view_mut().diagonal()
+    }
+}
+
+impl<K: Scalar> Add for MatrixView<'_, K> {
+    type Output = Matrix<K>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self.to_matrix();
+        result += rhs;
+        result
+    }
+}
+
+impl<K: Scalar> Sub for MatrixView<'_, K> {
+    type Output = Matrix<K>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut result = self.to_matrix();
+        result -= rhs;
+        result
+    }
+}
+
+impl<K: Scalar> Mul<K> for MatrixView<'_, K> {
+    type Output = Matrix<K>;
+
+    fn mul(self, rhs: K) -> Self::Output {
+        let mut result = self.to_matrix();
+        result.scl(rhs);
+        result
+    }
+}
+
+impl<'b, K: Scalar> Mul<MatrixView<'b, K>> for MatrixView<'_, K> {
+    type Output = Matrix<K>;
+
+    fn mul(self, rhs: MatrixView<'b, K>) -> Self::Output {
+        self.mul_mat(rhs)
+    }
+}
+
+impl<'b, K: Scalar> Mul<VectorView<'b, K>> for MatrixView<'_, K> {
+    type Output = Vector<K>;
+
+    fn mul(self, rhs: VectorView<'b, K>) -> Self::Output {
+        self.mul_vec(rhs)
+    }
+}
+
+impl<K: Scalar> Mul<&Vector<K>> for MatrixView<'_, K> {
+    type Output = Vector<K>;
+
+    fn mul(self, rhs: &Vector<K>) -> Self::Output {
+        self.mul_vec(rhs.view())
+    }
+}
+
+impl<K: Scalar> Mul<&Matrix<K>> for MatrixView<'_, K> {
+    type Output = Matrix<K>;
+
+    fn mul(self, rhs: &Matrix<K>) -> Self::Output {
+        self.mul_mat(rhs.view())
+    }
+}
+
+impl<'b, K: Scalar> Mul<MatrixView<'b, K>> for &Matrix<K> {
+    type Output = Matrix<K>;
+
+    fn mul(self, rhs: MatrixView<'b, K>) -> Self::Output {
+        self.view().mul_mat(rhs)
+    }
+}
+
+impl<'b, K: Scalar> Mul<VectorView<'b, K>> for &Matrix<K> {
+    type Output = Vector<K>;
+
+    fn mul(self, rhs: VectorView<'b, K>) -> Self::Output {
+        self.view().mul_vec(rhs)
+    }
+}
+
+impl<K: Scalar> AddAssign<MatrixView<'_, K>> for Matrix<K> {
+    fn add_assign(&mut self, rhs: MatrixView<K>) {
+        self.view_mut().add_mut(rhs);
+    }
+}
+
+impl<K: Scalar> SubAssign<MatrixView<'_, K>> for Matrix<K> {
+    fn sub_assign(&mut self, rhs: MatrixView<K>) {
+        self.view_mut().sub_mut(rhs);
+    }
+}
+
+impl<K: Scalar> AddAssign<MatrixView<'_, K>> for MatrixViewMut<'_, K> {
+    fn add_assign(&mut self, rhs: MatrixView<K>) {
+        self.add_mut(rhs);
+    }
+}
+
+impl<K: Scalar> SubAssign<MatrixView<'_, K>> for MatrixViewMut<'_, K> {
+    fn sub_assign(&mut self, rhs: MatrixView<K>) {
+        self.sub_mut(rhs);
+    }
+}
+
+impl<K: Scalar> MulAssign<K> for MatrixViewMut<'_, K> {
+    fn mul_assign(&mut self, rhs: K) {
+        self.scl(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Layout;
+    use super::*;
+
+    fn sample() -> Matrix<i32> {
+        Matrix::from([[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]])
+    }
+
+    #[test]
+    fn slicing() {
+        let m = sample();
+        assert_eq!(m.slice_rows(1..3), Matrix::from([[4, 5, 6], [7, 8, 9]]));
+        assert_eq!(m.slice_cols(2..), Matrix::from([[3], [6], [9], [12]]));
+        assert_eq!(m.submatrix(2.., ..2), Matrix::from([[7, 8], [10, 11]]));
+        assert_eq!(m.row(1), Vector::from([4, 5, 6]));
+        assert_eq!(m.column(1), Vector::from([2, 5, 8, 11]));
+        assert_eq!(m.diagonal(), Vector::from([1, 5, 9]));
+        assert_eq!(m.transpose_view(), m.transpose());
+        assert_eq!(m.transpose_view().diagonal(), m.diagonal());
+        assert_eq!(
+            m.submatrix(1.., 1..).transpose_view().row(0),
+            Vector::from([5, 8, 11])
+        );
+        assert_eq!(m.submatrix(1..3, 1..3).diagonal(), Vector::from([5, 9]));
+        assert_eq!((0, 3), m.slice_rows(4..).shape());
+        assert_eq!(Some(&12), m.view().get(3, 2));
+        assert_eq!(None, m.view().get(4, 0));
+        assert_eq!("[[1, 2, 3], [4, 5, 6]]", format!("{:?}", m.slice_rows(..2)));
+    }
+
+    #[test]
+    fn col_major_views() {
+        let m = sample().to_layout(Layout::ColMajor);
+        assert_eq!((1, 4), m.view().strides());
+        assert_eq!(m.column(2), Vector::from([3, 6, 9, 12]));
+        assert_eq!(1, m.column(2).stride());
+        assert_eq!(m.submatrix(1..3, 1..3), Matrix::from([[5, 6], [8, 9]]));
+        assert_eq!(m.transpose_view(), sample().transpose());
+    }
+
+    #[test]
+    fn from_slices() {
+        // A 2x2 block with leading dimension 3.
+        let data = [1., 2., 0., 3., 4.];
+        let a = MatrixView::from_slice(&data, 2, 2, (3, 1)).unwrap();
+        assert_eq!(a, Matrix::from([[1., 2.], [3., 4.]]));
+        let b = MatrixView::from_slice(&data, 2, 2, (1, 3)).unwrap();
+        assert_eq!(a.transpose_view(), b);
+        assert!(MatrixView::from_slice(&data, 2, 3, (3, 1)).is_err());
+        assert!(MatrixView::from_slice(&data[..0], 0, 3, (3, 1)).is_ok());
+
+        let mut data = [0; 6];
+        let mut c = MatrixViewMut::from_slice(&mut data, 3, 2, (1, 3)).unwrap();
+        c[(2, 1)] = 1;
+        assert_eq!([0, 0, 0, 0, 0, 1], data);
+    }
+
+    #[test]
+    #[should_panic(expected = "the strides of a mutable view must not make elements overlap")]
+    fn overlapping_mutable_view() {
+        let mut data = [0; 6];
+        let _ = MatrixViewMut::from_slice(&mut data, 2, 3, (2, 1));
+    }
+
+    #[test]
+    fn writing_through_views() {
+        let mut m = sample();
+        m.row_mut(0).fill(0);
+        m.column_mut(2).scl(-1);
+        m.diagonal_mut()[1] = 50;
+        let mut block = m.submatrix_mut(2.., ..2);
+        block *= 2;
+        block += Matrix::identity(2).view();
+        assert_eq!(
+            m,
+            Matrix::from([[0, 0, 0], [4, 50, -6], [15, 16, -9], [20, 23, -12]])
+        );
+
+        // Block update of the trailing 2x2 block by the leading rows, in
+        // place: A22 -= A21 A12.
+        let mut m = Matrix::from([[2, 1, 1], [4, 3, 3], [8, 7, 9]]);
+        let (top, mut bottom) = m.view_mut().split_at_row(1);
+        let (a21, mut a22) = bottom.reborrow().split_at_col(1);
+        let a12 = top.into_view().slice_cols(1..);
+        a22 -= (a21.as_view() * a12).view();
+        assert_eq!(m, Matrix::from([[2, 1, 1], [4, -1, -1], [8, -1, 1]]));
+
+        let mut m = Matrix::from([[1, 2], [3, 4]]);
+        let (mut left, right) = m.view_mut().split_at_col(1);
+        left.copy_from(right.as_view());
+        assert_eq!(m, Matrix::from([[2, 2], [4, 4]]));
+        assert!(m
+            .view_mut()
+            .try_add_mut(Matrix::<i32>::zeros(1, 2).view())
+            .is_err());
+    }
+
+    #[test]
+    fn view_arithmetic() {
+        let m = sample();
+        let a = m.slice_rows(..2);
+        let b = m.slice_rows(2..);
+        assert_eq!(Matrix::from([[8, 10, 12], [14, 16, 18]]), a + b);
+        assert_eq!(Matrix::from([[6, 6, 6], [6, 6, 6]]), b - a);
+        assert_eq!(Matrix::from([[2, 4, 6], [8, 10, 12]]), a * 2);
+        assert_eq!(
+            m.slice_rows(..2).to_matrix().mul_mat(&m.transpose()),
+            a * m.transpose_view()
+        );
+        assert_eq!(
+            Vector::from([14, 32]),
+            a * m.slice_rows(0..1).transpose_view().column(0)
+        );
+        let x = Vector::from([1, 0, -1]);
+        assert_eq!(Vector::from([-2, -2]), a * &x);
+        let e2 = Vector::from([0, 0, 1]);
+        assert_eq!(Vector::from([3, 6, 9, 12]), &m * e2.view());
+        assert!(a.try_mul_vec(m.column(0)).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "index (2, 0) out of bounds for a 2x3 matrix")]
+    fn view_index_out_of_bounds() {
+        let m = sample();
+        let _ = m.slice_rows(..2)[(2, 0)];
+    }
+}
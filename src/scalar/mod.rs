@@ -0,0 +1,293 @@
+//! Algebraic traits describing what each operation needs from its scalars.
+//!
+//! - [`Scalar`]: addition, subtraction and multiplication, plus conjugate and
+//!   modulus. Enough for sums, scaling, products and traces.
+//! - [`Ring`]: adds negation and an exact division, which fraction-free
+//!   elimination (determinant, rank, row echelon form) relies on.
+//! - [`Field`]: every nonzero element is invertible and the modulus is a
+//!   [`RealField`]. Needed for norms, inverses and factorizations.
+//! - [`RealField`]: a field ordered like the reals; `f32` and `f64`.
+
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num_complex::Complex;
+use num_traits::{Float, NumAssign, One, Zero};
+
+/// Element type of [`Vector`](crate::Vector) and [`Matrix`](crate::Matrix).
+///
+/// Every scalar has a complex conjugate and a modulus of type
+/// [`Scalar::Real`]; both are trivial for real numbers, which are their own
+/// conjugate. Scalars are `Send + Sync` so that matrices can be shared
+/// between threads, and `'static` so that `f32` and `f64` can be told apart
+/// and given SIMD kernels.
+pub trait Scalar:
+    'static
+    + Copy
+    + Send
+    + Sync
+    + PartialEq
+    + Debug
+    + Zero
+    + One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    /// Type of the modulus and of the real part.
+    type Real: Scalar + PartialOrd;
+
+    /// Complex conjugate.
+    fn conj(self) -> Self;
+
+    /// Real part.
+    fn re(self) -> Self::Real;
+
+    /// Absolute value for real numbers, `|z|` for complex ones.
+    fn modulus(self) -> Self::Real;
+
+    /// `|z|^2`, which avoids the square root of [`Scalar::modulus`].
+    fn modulus_squared(self) -> Self::Real;
+}
+
+/// A [`Scalar`] with negation and division.
+///
+/// Division only has to be exact when the divisor divides the dividend, as
+/// it does for integers.
+pub trait Ring: Scalar + Neg<Output = Self> + Div<Output = Self> {}
+
+/// A [`Ring`] in which every nonzero element has an inverse and whose
+/// modulus is real-valued.
+pub trait Field: Ring<Real: RealField> + DivAssign {
+    /// Embeds a real number, `x + 0i` for complex fields.
+    fn from_real(re: Self::Real) -> Self;
+}
+
+/// An ordered field such as `f32` or `f64`, with the usual floating-point
+/// functions. Every `RealField` is a [`Field`] that is its own real part.
+pub trait RealField: Ring<Real = Self> + Float + NumAssign {}
+
+impl<T> Field for T
+where
+    T: RealField,
+{
+    fn from_real(re: Self::Real) -> Self {
+        re
+    }
+}
+
+macro_rules! impl_real_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            type Real = $t;
+
+            fn conj(self) -> Self {
+                self
+            }
+
+            fn re(self) -> Self::Real {
+                self
+            }
+
+            fn modulus(self) -> Self::Real {
+                self.abs()
+            }
+
+            fn modulus_squared(self) -> Self::Real {
+                self * self
+            }
+        }
+
+        impl Ring for $t {}
+    )*};
+}
+
+impl_real_scalar!(i32, i64, f32, f64);
+
+impl RealField for f32 {}
+impl RealField for f64 {}
+
+impl<T> Scalar for Complex<T>
+where
+    T: RealField,
+{
+    type Real = T;
+
+    fn conj(self) -> Self {
+        Complex::conj(&self)
+    }
+
+    fn re(self) -> Self::Real {
+        self.re
+    }
+
+    fn modulus(self) -> Self::Real {
+        self.norm()
+    }
+
+    fn modulus_squared(self) -> Self::Real {
+        self.norm_sqr()
+    }
+}
+
+impl<T> Ring for Complex<T> where T: RealField {}
+
+impl<T> Field for Complex<T>
+where
+    T: RealField,
+{
+    fn from_real(re: Self::Real) -> Self {
+        Complex::new(re, T::zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn real_scalars() {
+        assert_eq!(-3, (-3).conj());
+        assert_eq!(3, (-3).modulus());
+        assert_eq!(9, (-3).modulus_squared());
+        assert_eq!(2.5, (-2.5f64).modulus());
+        assert_eq!(-2.5, (-2.5f32).re());
+        assert_eq!(1.5, f64::from_real(1.5));
+    }
+
+    #[test]
+    fn complex_scalars() {
+        let z = Complex::new(3.0f64, -4.0);
+        assert_eq!(Complex::new(3.0, 4.0), z.conj());
+        assert_eq!(3.0, z.re());
+        assert_eq!(5.0, z.modulus());
+        assert_eq!(25.0, z.modulus_squared());
+        assert_eq!(Complex::new(2.0, 0.0), Complex::from_real(2.0));
+    }
+
+    /// Integers modulo 7, a user-defined ring that is not a float.
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    struct Mod7(u8);
+
+    impl Zero for Mod7 {
+        fn zero() -> Self {
+            Mod7(0)
+        }
+
+        fn is_zero(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    impl One for Mod7 {
+        fn one() -> Self {
+            Mod7(1)
+        }
+    }
+
+    impl Add for Mod7 {
+        type Output = Self;
+
+        fn add(self, rhs: Self) -> Self {
+            Mod7((self.0 + rhs.0) % 7)
+        }
+    }
+
+    impl Sub for Mod7 {
+        type Output = Self;
+
+        fn sub(self, rhs: Self) -> Self {
+            Mod7((self.0 + 7 - rhs.0) % 7)
+        }
+    }
+
+    impl Mul for Mod7 {
+        type Output = Self;
+
+        fn mul(self, rhs: Self) -> Self {
+            Mod7(self.0 * rhs.0 % 7)
+        }
+    }
+
+    impl Neg for Mod7 {
+        type Output = Self;
+
+        fn neg(self) -> Self {
+            Mod7(0) - self
+        }
+    }
+
+    impl Div for Mod7 {
+        type Output = Self;
+
+        fn div(self, rhs: Self) -> Self {
+            const INVERSES: [u8; 7] = [0, 1, 4, 5, 2, 3, 6];
+            Mod7(self.0 * INVERSES[rhs.0 as usize] % 7)
+        }
+    }
+
+    impl AddAssign for Mod7 {
+        fn add_assign(&mut self, rhs: Self) {
+            *self = *self + rhs;
+        }
+    }
+
+    impl SubAssign for Mod7 {
+        fn sub_assign(&mut self, rhs: Self) {
+            *self = *self - rhs;
+        }
+    }
+
+    impl MulAssign for Mod7 {
+        fn mul_assign(&mut self, rhs: Self) {
+            *self = *self * rhs;
+        }
+    }
+
+    impl Scalar for Mod7 {
+        type Real = Mod7;
+
+        fn conj(self) -> Self {
+            self
+        }
+
+        fn re(self) -> Self::Real {
+            self
+        }
+
+        fn modulus(self) -> Self::Real {
+            self
+        }
+
+        fn modulus_squared(self) -> Self::Real {
+            self * self
+        }
+    }
+
+    impl Ring for Mod7 {}
+
+    #[test]
+    fn user_defined_ring() {
+        use crate::{Matrix, Vector};
+
+        let m = Matrix::from([[Mod7(1), Mod7(2)], [Mod7(3), Mod7(4)]]);
+        // 1 * 4 - 2 * 3 = -2 = 5 (mod 7)
+        assert_eq!(Mod7(5), m.determinant());
+        assert_eq!(2, m.rank());
+        assert_eq!(Mod7(5), m.trace());
+
+        let singular = Matrix::from([[Mod7(1), Mod7(2)], [Mod7(2), Mod7(4)]]);
+        assert_eq!(Mod7(0), singular.determinant());
+        assert_eq!(1, singular.rank());
+
+        let u = Vector::from([Mod7(3), Mod7(5)]);
+        assert_eq!(Mod7(6), u.dot(&u));
+        assert_eq!(
+            Vector::from([Mod7(6), Mod7(0)]),
+            m.mul_vec(&Vector::from([Mod7(2), Mod7(2)]))
+        );
+    }
+}
@@ -0,0 +1,699 @@
+//! SIMD kernels for the `f32` and `f64` dot product, axpy, norms and the
+//! micro-kernel of the matrix product.
+//!
+//! The instruction set is picked once, at the first call, from what the CPU
+//! reports: AVX-512, AVX2 with FMA or SSE2 on x86, NEON on AArch64, and a
+//! scalar loop anywhere else. The entry points take slices of any
+//! [`Scalar`] and only run for `f32` and `f64`, returning `None` otherwise,
+//! so that contiguous vectors and views use them and keep their generic
+//! loops for every other element type.
+//!
+//! The kernels split the sums into several partial sums, which rounds
+//! differently from a sequential loop. Against the scalar kernels of this
+//! module, for slices of length `n`:
+//!
+//! - `axpy` and `max_abs` give the same bits;
+//! - `sum_abs` and `sum_squares` differ by at most `2 n` ulps, since every
+//!   term is non-negative;
+//! - `dot` differs by at most `2 n` ulps of `|x|.|y|`, the dot product of
+//!   the moduli, which is the result itself when no terms cancel.
+
+use std::any::{Any, TypeId};
+use std::slice;
+use std::sync::OnceLock;
+
+use num_traits::Float;
+
+use crate::scalar::{Field, Scalar};
+
+#[cfg(target_arch = "aarch64")]
+mod neon;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86;
+
+/// An instruction set the kernels are written for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Level {
+    Scalar,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Sse2,
+    /// AVX2 with FMA.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Avx2,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Avx512,
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+}
+
+impl Level {
+    /// Every level the CPU supports, from the slowest.
+    pub(crate) fn supported() -> Vec<Level> {
+        let mut levels = vec![Level::Scalar];
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse2") {
+                levels.push(Level::Sse2);
+            }
+            if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+                levels.push(Level::Avx2);
+            }
+            if is_x86_feature_detected!("avx512f") {
+                levels.push(Level::Avx512);
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            levels.push(Level::Neon);
+        }
+        levels
+    }
+
+    /// The fastest supported level, detected once.
+    pub(crate) fn detected() -> Level {
+        static LEVEL: OnceLock<Level> = OnceLock::new();
+        *LEVEL.get_or_init(|| *Level::supported().last().unwrap())
+    }
+}
+
+/// The kernels of one instruction set for elements of type `T`.
+///
+/// Slices given together must have the same length.
+trait Kernels<T> {
+    /// # Safety
+    ///
+    /// The CPU must support the instruction set, as reported by
+    /// [`Level::supported`]; the same holds for every method.
+    unsafe fn dot(x: &[T], y: &[T]) -> T;
+    /// `y += a * x`, rounding the product and the sum separately.
+    unsafe fn axpy(a: T, x: &[T], y: &mut [T]);
+    unsafe fn sum_abs(x: &[T]) -> T;
+    unsafe fn sum_squares(x: &[T]) -> T;
+    /// The largest `|x[i]|`, or zero for an empty slice. The first NaN, with
+    /// its sign cleared, wins.
+    unsafe fn max_abs(x: &[T]) -> T;
+
+    /// Rows of the register block of [`Kernels::gemm_tile`].
+    const MR: usize;
+    /// Columns of the register block of [`Kernels::gemm_tile`].
+    const NR: usize;
+    /// Writes to `ab` the row-major `MR x NR` product of `a`, `kc`
+    /// consecutive columns of `MR` elements, and `b`, `kc` consecutive rows
+    /// of `NR` elements.
+    unsafe fn gemm_tile(a: &[T], b: &[T], ab: &mut [T]);
+}
+
+/// Implements [`Kernels`] for an instruction set from its vector
+/// intrinsics. `$lanes` is the number of elements per vector, and every
+/// operation is a function taking and returning vectors, with
+/// `fmadd(a, b, c) = a * b + c`. The GEMM register block is `$mr` rows by
+/// two vectors, sized so that its accumulators fit in the registers.
+macro_rules! kernels {
+    (
+        $isa:ty, $feature:literal, $t:ty, $lanes:literal, $mr:literal, {
+            zero: $zero:path,
+            splat: $splat:path,
+            load: $load:path,
+            store: $store:path,
+            add: $add:path,
+            mul: $mul:path,
+            fmadd: $fmadd:path,
+            abs: $abs:path,
+            max: $max:path $(,)?
+        }
+    ) => {
+        impl $crate::simd::Kernels<$t> for $isa {
+            #[target_feature(enable = $feature)]
+            unsafe fn dot(x: &[$t], y: &[$t]) -> $t {
+                let n = x.len().min(y.len());
+                let (px, py) = (x.as_ptr(), y.as_ptr());
+                // Four accumulators hide the latency of the FMA.
+                let mut acc = [$zero(); 4];
+                let mut i = 0;
+                while i + 4 * $lanes <= n {
+                    for (k, acc) in acc.iter_mut().enumerate() {
+                        let j = i + k * $lanes;
+                        *acc = $fmadd($load(px.add(j)), $load(py.add(j)), *acc);
+                    }
+                    i += 4 * $lanes;
+                }
+                while i + $lanes <= n {
+                    acc[0] = $fmadd($load(px.add(i)), $load(py.add(i)), acc[0]);
+                    i += $lanes;
+                }
+                let mut lanes = [0.; $lanes];
+                $store(
+                    lanes.as_mut_ptr(),
+                    $add($add(acc[0], acc[1]), $add(acc[2], acc[3])),
+                );
+                let mut sum: $t = lanes.iter().sum();
+                for (a, b) in x[i..n].iter().zip(&y[i..n]) {
+                    sum += a * b;
+                }
+                sum
+            }
+
+            #[target_feature(enable = $feature)]
+            unsafe fn axpy(a: $t, x: &[$t], y: &mut [$t]) {
+                let n = x.len().min(y.len());
+                let (px, py) = (x.as_ptr(), y.as_mut_ptr());
+                let va = $splat(a);
+                let mut i = 0;
+                while i + $lanes <= n {
+                    let v = $add($load(py.add(i)), $mul(va, $load(px.add(i))));
+                    $store(py.add(i), v);
+                    i += $lanes;
+                }
+                for (y, x) in y[i..n].iter_mut().zip(&x[i..n]) {
+                    *y += a * x;
+                }
+            }
+
+            #[target_feature(enable = $feature)]
+            unsafe fn sum_abs(x: &[$t]) -> $t {
+                let px = x.as_ptr();
+                let mut acc = [$zero(); 4];
+                let mut i = 0;
+                while i + 4 * $lanes <= x.len() {
+                    for (k, acc) in acc.iter_mut().enumerate() {
+                        *acc = $add($abs($load(px.add(i + k * $lanes))), *acc);
+                    }
+                    i += 4 * $lanes;
+                }
+                while i + $lanes <= x.len() {
+                    acc[0] = $add($abs($load(px.add(i))), acc[0]);
+                    i += $lanes;
+                }
+                let mut lanes = [0.; $lanes];
+                $store(
+                    lanes.as_mut_ptr(),
+                    $add($add(acc[0], acc[1]), $add(acc[2], acc[3])),
+                );
+                let mut sum: $t = lanes.iter().sum();
+                for e in &x[i..] {
+                    sum += e.abs();
+                }
+                sum
+            }
+
+            #[target_feature(enable = $feature)]
+            unsafe fn sum_squares(x: &[$t]) -> $t {
+                let px = x.as_ptr();
+                let mut acc = [$zero(); 4];
+                let mut i = 0;
+                while i + 4 * $lanes <= x.len() {
+                    for (k, acc) in acc.iter_mut().enumerate() {
+                        let v = $load(px.add(i + k * $lanes));
+                        *acc = $fmadd(v, v, *acc);
+                    }
+                    i += 4 * $lanes;
+                }
+                while i + $lanes <= x.len() {
+                    let v = $load(px.add(i));
+                    acc[0] = $fmadd(v, v, acc[0]);
+                    i += $lanes;
+                }
+                let mut lanes = [0.; $lanes];
+                $store(
+                    lanes.as_mut_ptr(),
+                    $add($add(acc[0], acc[1]), $add(acc[2], acc[3])),
+                );
+                let mut sum: $t = lanes.iter().sum();
+                for e in &x[i..] {
+                    sum += e * e;
+                }
+                sum
+            }
+
+            #[target_feature(enable = $feature)]
+            unsafe fn max_abs(x: &[$t]) -> $t {
+                let px = x.as_ptr();
+                let (mut acc, mut probe) = ($zero(), $zero());
+                let mut i = 0;
+                while i + $lanes <= x.len() {
+                    // `max` drops NaNs, but `v * 0` is NaN exactly for NaN
+                    // and infinite elements, so a NaN in `probe` sends the
+                    // rare slices with either to a scalar scan for NaNs.
+                    let v = $load(px.add(i));
+                    acc = $max($abs(v), acc);
+                    probe = $fmadd(v, $zero(), probe);
+                    i += $lanes;
+                }
+                let mut lanes = [0.; $lanes];
+                $store(lanes.as_mut_ptr(), probe);
+                if lanes.iter().any(|e| e.is_nan()) {
+                    if let Some(nan) = x[..i].iter().find(|e| e.is_nan()) {
+                        return nan.abs();
+                    }
+                }
+                $store(lanes.as_mut_ptr(), acc);
+                let mut max: $t = lanes.iter().fold(0., |m, &e| m.max(e));
+                for e in &x[i..] {
+                    if e.is_nan() {
+                        return e.abs();
+                    }
+                    max = max.max(e.abs());
+                }
+                max
+            }
+
+            const MR: usize = $mr;
+            const NR: usize = 2 * $lanes;
+
+            #[target_feature(enable = $feature)]
+            unsafe fn gemm_tile(a: &[$t], b: &[$t], ab: &mut [$t]) {
+                let kc = a.len() / $mr;
+                debug_assert!(b.len() >= kc * 2 * $lanes && ab.len() >= $mr * 2 * $lanes);
+                let (pa, pb, pab) = (a.as_ptr(), b.as_ptr(), ab.as_mut_ptr());
+                let mut acc = [[$zero(); 2]; $mr];
+                for p in 0..kc {
+                    let b0 = $load(pb.add(2 * $lanes * p));
+                    let b1 = $load(pb.add(2 * $lanes * p + $lanes));
+                    for (i, acc) in acc.iter_mut().enumerate() {
+                        let ai = $splat(*pa.add($mr * p + i));
+                        acc[0] = $fmadd(ai, b0, acc[0]);
+                        acc[1] = $fmadd(ai, b1, acc[1]);
+                    }
+                }
+                for (i, acc) in acc.iter().enumerate() {
+                    $store(pab.add(2 * $lanes * i), acc[0]);
+                    $store(pab.add(2 * $lanes * i + $lanes), acc[1]);
+                }
+            }
+        }
+    };
+}
+
+use kernels;
+
+/// The scalar loops, which every other level is checked against.
+struct Fallback;
+
+impl<T: Float> Kernels<T> for Fallback {
+    unsafe fn dot(x: &[T], y: &[T]) -> T {
+        x.iter().zip(y).fold(T::zero(), |acc, (&a, &b)| acc + a * b)
+    }
+
+    unsafe fn axpy(a: T, x: &[T], y: &mut [T]) {
+        y.iter_mut().zip(x).for_each(|(y, &x)| *y = *y + a * x);
+    }
+
+    unsafe fn sum_abs(x: &[T]) -> T {
+        x.iter().fold(T::zero(), |acc, e| acc + e.abs())
+    }
+
+    unsafe fn sum_squares(x: &[T]) -> T {
+        x.iter().fold(T::zero(), |acc, &e| acc + e * e)
+    }
+
+    unsafe fn max_abs(x: &[T]) -> T {
+        let mut max = T::zero();
+        for e in x {
+            if e.is_nan() {
+                return e.abs();
+            }
+            max = max.max(e.abs());
+        }
+        max
+    }
+
+    const MR: usize = 4;
+    const NR: usize = 4;
+
+    unsafe fn gemm_tile(a: &[T], b: &[T], ab: &mut [T]) {
+        ab[..16].fill(T::zero());
+        for (a, b) in a.chunks_exact(4).zip(b.chunks_exact(4)) {
+            for (i, &a) in a.iter().enumerate() {
+                for (ab, &b) in ab[4 * i..4 * i + 4].iter_mut().zip(b) {
+                    *ab = *ab + a * b;
+                }
+            }
+        }
+    }
+}
+
+/// The GEMM micro-kernel of one level, with its register block.
+#[derive(Clone, Copy)]
+pub(crate) struct TileKernel<T> {
+    mr: usize,
+    nr: usize,
+    tile: unsafe fn(&[T], &[T], &mut [T]),
+}
+
+impl<T> TileKernel<T> {
+    /// Only ever called with levels the CPU supports.
+    fn of<I: Kernels<T>>() -> Self {
+        TileKernel {
+            mr: I::MR,
+            nr: I::NR,
+            tile: I::gemm_tile,
+        }
+    }
+
+    pub(crate) fn mr(&self) -> usize {
+        self.mr
+    }
+
+    pub(crate) fn nr(&self) -> usize {
+        self.nr
+    }
+
+    /// See [`Kernels::gemm_tile`]. Panics if a slice is too short.
+    pub(crate) fn run(&self, a: &[T], b: &[T], ab: &mut [T]) {
+        let kc = a.len() / self.mr;
+        assert!(b.len() >= kc * self.nr && ab.len() >= self.mr * self.nr);
+        // SAFETY: the kernel is one of a level the CPU supports.
+        unsafe { (self.tile)(a, b, ab) }
+    }
+}
+
+/// `f32` and `f64`, with their kernels at every level.
+pub(crate) trait Simd: Float + 'static {
+    fn dot_at(level: Level, x: &[Self], y: &[Self]) -> Self;
+    fn axpy_at(level: Level, a: Self, x: &[Self], y: &mut [Self]);
+    fn sum_abs_at(level: Level, x: &[Self]) -> Self;
+    fn sum_squares_at(level: Level, x: &[Self]) -> Self;
+    fn max_abs_at(level: Level, x: &[Self]) -> Self;
+    fn tile_kernel_at(level: Level) -> TileKernel<Self>;
+}
+
+/// Calls a [`Kernels`] method of `$t` at a [`Level`].
+macro_rules! dispatch {
+    ($level:expr, $t:ty, $f:ident($($arg:expr),*)) => {
+        // SAFETY: a `Level` is only ever one the CPU supports.
+        unsafe {
+            match $level {
+                Level::Scalar => <Fallback as Kernels<$t>>::$f($($arg),*),
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Level::Sse2 => <x86::Sse2 as Kernels<$t>>::$f($($arg),*),
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Level::Avx2 => <x86::Avx2 as Kernels<$t>>::$f($($arg),*),
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Level::Avx512 => <x86::Avx512 as Kernels<$t>>::$f($($arg),*),
+                #[cfg(target_arch = "aarch64")]
+                Level::Neon => <neon::Neon as Kernels<$t>>::$f($($arg),*),
+            }
+        }
+    };
+}
+
+macro_rules! impl_simd {
+    ($($t:ty),*) => {$(
+        impl Simd for $t {
+            fn dot_at(level: Level, x: &[Self], y: &[Self]) -> Self {
+                dispatch!(level, $t, dot(x, y))
+            }
+
+            fn axpy_at(level: Level, a: Self, x: &[Self], y: &mut [Self]) {
+                dispatch!(level, $t, axpy(a, x, y))
+            }
+
+            fn sum_abs_at(level: Level, x: &[Self]) -> Self {
+                dispatch!(level, $t, sum_abs(x))
+            }
+
+            fn sum_squares_at(level: Level, x: &[Self]) -> Self {
+                dispatch!(level, $t, sum_squares(x))
+            }
+
+            fn max_abs_at(level: Level, x: &[Self]) -> Self {
+                dispatch!(level, $t, max_abs(x))
+            }
+
+            fn tile_kernel_at(level: Level) -> TileKernel<Self> {
+                match level {
+                    Level::Scalar => TileKernel::of::<Fallback>(),
+                    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                    Level::Sse2 => TileKernel::of::<x86::Sse2>(),
+                    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                    Level::Avx2 => TileKernel::of::<x86::Avx2>(),
+                    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                    Level::Avx512 => TileKernel::of::<x86::Avx512>(),
+                    #[cfg(target_arch = "aarch64")]
+                    Level::Neon => TileKernel::of::<neon::Neon>(),
+                }
+            }
+        }
+    )*};
+}
+
+impl_simd!(f32, f64);
+
+/// `x` as a slice of `T`, when `K` is `T`.
+fn cast<K: 'static, T: 'static>(x: &[K]) -> Option<&[T]> {
+    // SAFETY: `K` and `T` are the same type.
+    (TypeId::of::<K>() == TypeId::of::<T>())
+        .then(|| unsafe { slice::from_raw_parts(x.as_ptr().cast(), x.len()) })
+}
+
+fn cast_mut<K: 'static, T: 'static>(x: &mut [K]) -> Option<&mut [T]> {
+    // SAFETY: `K` and `T` are the same type.
+    (TypeId::of::<K>() == TypeId::of::<T>())
+        .then(|| unsafe { slice::from_raw_parts_mut(x.as_mut_ptr().cast(), x.len()) })
+}
+
+/// `v` as a `K`, when `T` is `K`.
+fn value<T: 'static, K: Copy + 'static>(v: T) -> Option<K> {
+    (&v as &dyn Any).downcast_ref().copied()
+}
+
+/// `x[0] * y[0] + x[1] * y[1] + ...` for slices of the same length.
+pub(crate) fn dot<K: Scalar>(x: &[K], y: &[K]) -> Option<K> {
+    fn run<K: Scalar, T: Simd>(x: &[K], y: &[K]) -> Option<K> {
+        value(T::dot_at(Level::detected(), cast::<K, T>(x)?, cast(y)?))
+    }
+    run::<K, f64>(x, y).or_else(|| run::<K, f32>(x, y))
+}
+
+/// `y[i] += a * x[i]` for slices of the same length. Returns whether it
+/// ran.
+pub(crate) fn axpy<K: Scalar>(a: K, x: &[K], y: &mut [K]) -> bool {
+    fn run<K: Scalar, T: Simd>(a: K, x: &[K], y: &mut [K]) -> Option<()> {
+        T::axpy_at(Level::detected(), value(a)?, cast::<K, T>(x)?, cast_mut(y)?);
+        Some(())
+    }
+    run::<K, f64>(a, x, y)
+        .or_else(|| run::<K, f32>(a, x, y))
+        .is_some()
+}
+
+/// The sum of the moduli.
+pub(crate) fn norm_1<K: Field>(x: &[K]) -> Option<K::Real> {
+    fn run<K: Field, T: Simd>(x: &[K]) -> Option<K::Real> {
+        value(T::sum_abs_at(Level::detected(), cast::<K, T>(x)?))
+    }
+    run::<K, f64>(x).or_else(|| run::<K, f32>(x))
+}
+
+/// The largest modulus, or NaN if there is one.
+pub(crate) fn norm_inf<K: Field>(x: &[K]) -> Option<K::Real> {
+    fn run<K: Field, T: Simd>(x: &[K]) -> Option<K::Real> {
+        value(T::max_abs_at(Level::detected(), cast::<K, T>(x)?))
+    }
+    run::<K, f64>(x).or_else(|| run::<K, f32>(x))
+}
+
+/// The GEMM micro-kernel of the detected level.
+pub(crate) fn tile_kernel<K: Scalar>() -> Option<TileKernel<K>> {
+    fn run<K: Scalar, T: Simd>() -> Option<TileKernel<K>> {
+        value(T::tile_kernel_at(Level::detected()))
+    }
+    run::<K, f64>().or_else(run::<K, f32>)
+}
+
+/// The Euclidean norm as the root of the plain sum of squares. Also `None`
+/// when that sum overflows, is NaN, or is so small that squares may have
+/// lost bits to underflow: the caller then needs a scaled algorithm.
+pub(crate) fn norm<K: Field>(x: &[K]) -> Option<K::Real> {
+    fn run<K: Field, T: Simd>(x: &[K]) -> Option<K::Real> {
+        let s = T::sum_squares_at(Level::detected(), cast::<K, T>(x)?);
+        (s.is_finite() && s >= T::min_positive_value() / T::epsilon())
+            .then(|| s.sqrt())
+            .and_then(value)
+    }
+    run::<K, f64>(x).or_else(|| run::<K, f32>(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use super::*;
+
+    /// Floats mapped to integers that count representable values, with
+    /// both zeros at zero.
+    trait Ordered: Simd + Debug {
+        fn ordered(self) -> i64;
+    }
+
+    impl Ordered for f32 {
+        fn ordered(self) -> i64 {
+            let bits = self.to_bits() as i32;
+            (if bits < 0 { i32::MIN - bits } else { bits }) as i64
+        }
+    }
+
+    impl Ordered for f64 {
+        fn ordered(self) -> i64 {
+            let bits = self.to_bits() as i64;
+            if bits < 0 {
+                i64::MIN - bits
+            } else {
+                bits
+            }
+        }
+    }
+
+    /// Distance in representable values between two floats.
+    fn ulps<T: Ordered>(a: T, b: T) -> u64 {
+        a.ordered().abs_diff(b.ordered())
+    }
+
+    /// Deterministic values in `[-1, 1)`.
+    fn values<T: Float>(n: usize, seed: u64) -> Vec<T> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                T::from((state >> 11) as f64 / (1u64 << 52) as f64 - 1.).unwrap()
+            })
+            .collect()
+    }
+
+    /// Lengths around every vector and unrolling width.
+    const LENGTHS: [usize; 12] = [0, 1, 3, 4, 7, 8, 15, 16, 17, 63, 64, 1001];
+
+    fn agree<T: Ordered>() {
+        for level in Level::supported() {
+            for n in LENGTHS {
+                let x = values::<T>(n, 1);
+                let y = values::<T>(n, 2);
+                let bound = 2 * n as u64;
+
+                let abs_x: Vec<T> = x.iter().map(|e| e.abs()).collect();
+                let abs_y: Vec<T> = y.iter().map(|e| e.abs()).collect();
+                let scale = T::dot_at(Level::Scalar, &abs_x, &abs_y);
+                let error = (T::dot_at(level, &x, &y) - T::dot_at(Level::Scalar, &x, &y)).abs();
+                assert!(
+                    error <= T::from(bound).unwrap() * T::epsilon() * scale,
+                    "dot, {:?}, n = {}",
+                    level,
+                    n
+                );
+
+                let mut expected = y.clone();
+                let mut actual = y.clone();
+                T::axpy_at(Level::Scalar, T::from(0.7).unwrap(), &x, &mut expected);
+                T::axpy_at(level, T::from(0.7).unwrap(), &x, &mut actual);
+                assert_eq!(expected, actual, "axpy, {:?}, n = {}", level, n);
+
+                for (name, f) in [
+                    ("sum_abs", T::sum_abs_at as fn(Level, &[T]) -> T),
+                    ("sum_squares", T::sum_squares_at),
+                ] {
+                    let diff = ulps(f(level, &x), f(Level::Scalar, &x));
+                    assert!(diff <= bound, "{}, {:?}, n = {}", name, level, n);
+                }
+                assert_eq!(
+                    T::max_abs_at(Level::Scalar, &x),
+                    T::max_abs_at(level, &x),
+                    "max_abs, {:?}, n = {}",
+                    level,
+                    n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn levels_agree_f64() {
+        agree::<f64>();
+    }
+
+    #[test]
+    fn levels_agree_f32() {
+        agree::<f32>();
+    }
+
+    #[test]
+    fn exact_cases() {
+        let x: Vec<f64> = (1..=20).map(f64::from).collect();
+        for level in Level::supported() {
+            assert_eq!(2870., f64::dot_at(level, &x, &x), "{:?}", level);
+            assert_eq!(210., f64::sum_abs_at(level, &x), "{:?}", level);
+            assert_eq!(20., f64::max_abs_at(level, &x), "{:?}", level);
+            assert_eq!(0., f32::max_abs_at(level, &[]), "{:?}", level);
+        }
+    }
+
+    #[test]
+    fn gemm_tiles() {
+        // Small integers, so every level computes the exact product.
+        let kc = 5;
+        for level in Level::supported() {
+            let kernel = f64::tile_kernel_at(level);
+            let (mr, nr) = (kernel.mr(), kernel.nr());
+            let a: Vec<f64> = (0..mr * kc).map(|i| (i % 7) as f64 - 3.).collect();
+            let b: Vec<f64> = (0..kc * nr).map(|j| (j % 5) as f64 - 2.).collect();
+            let mut ab = vec![f64::NAN; mr * nr];
+            kernel.run(&a, &b, &mut ab);
+            for i in 0..mr {
+                for j in 0..nr {
+                    let expected: f64 = (0..kc).map(|p| a[p * mr + i] * b[p * nr + j]).sum();
+                    assert_eq!(expected, ab[i * nr + j], "{:?}, ({}, {})", level, i, j);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn max_abs_propagates_nan() {
+        for n in [3, 37] {
+            let mut x = vec![1f32; n];
+            x[n - 2] = f32::INFINITY;
+            for level in Level::supported() {
+                assert_eq!(f32::INFINITY, f32::max_abs_at(level, &x), "{:?}", level);
+            }
+            x[n / 4] = -4.;
+            x[n - 1] = -f32::NAN;
+            for level in Level::supported() {
+                let max = f32::max_abs_at(level, &x);
+                assert!(max.is_nan() && max.is_sign_positive(), "{:?}", level);
+            }
+        }
+    }
+
+    #[test]
+    fn only_floats_dispatch() {
+        use num_complex::Complex;
+
+        assert_eq!(Some(11.), dot(&[1f32, 2.], &[3., 4.]));
+        assert_eq!(None, dot(&[1, 2], &[3, 4]));
+        let mut y = [1., 1.];
+        assert!(axpy(2f64, &[1., 2.], &mut y));
+        assert_eq!([3., 5.], y);
+        assert!(!axpy(2, &[1, 2], &mut [1, 1]));
+        let z = [Complex::new(3f64, 4.)];
+        assert_eq!(None, norm_1(&z));
+        assert_eq!(None, norm_inf(&z));
+    }
+
+    #[test]
+    fn norm_falls_back() {
+        assert_eq!(Some(5.), norm(&[3f64, 4.]));
+        assert_eq!(None, norm(&[1e200f64, 1e200]));
+        assert_eq!(None, norm(&[1e-200f64, 1e-200]));
+        assert_eq!(None, norm::<f32>(&[]));
+        assert!(norm(&[f64::NAN, 1.]).is_none());
+    }
+
+    #[test]
+    fn ulps_counts_steps() {
+        assert_eq!(0, ulps(1f64, 1.));
+        assert_eq!(1, ulps(1f64, 1. + f64::EPSILON));
+        assert_eq!(2, ulps(1f32, 1. - f32::EPSILON));
+        assert_eq!(2, ulps(f64::from_bits(1), -f64::from_bits(1)));
+    }
+}
@@ -0,0 +1,56 @@
+use std::arch::aarch64::*;
+
+use super::kernels;
+
+pub(crate) struct Neon;
+
+// Adapters to the operand order of the kernels. `vmaxnmq` ignores NaNs
+// like `f64::max`; `max_abs` looks for them separately.
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn zero_f64() -> float64x2_t {
+    vdupq_n_f64(0.)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn zero_f32() -> float32x4_t {
+    vdupq_n_f32(0.)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn fmadd_f64(a: float64x2_t, b: float64x2_t, c: float64x2_t) -> float64x2_t {
+    vfmaq_f64(c, a, b)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn fmadd_f32(a: float32x4_t, b: float32x4_t, c: float32x4_t) -> float32x4_t {
+    vfmaq_f32(c, a, b)
+}
+
+kernels!(Neon, "neon", f64, 2, 8, {
+    zero: zero_f64,
+    splat: vdupq_n_f64,
+    load: vld1q_f64,
+    store: vst1q_f64,
+    add: vaddq_f64,
+    mul: vmulq_f64,
+    fmadd: fmadd_f64,
+    abs: vabsq_f64,
+    max: vmaxnmq_f64,
+});
+
+kernels!(Neon, "neon", f32, 4, 8, {
+    zero: zero_f32,
+    splat: vdupq_n_f32,
+    load: vld1q_f32,
+    store: vst1q_f32,
+    add: vaddq_f32,
+    mul: vmulq_f32,
+    fmadd: fmadd_f32,
+    abs: vabsq_f32,
+    max: vmaxnmq_f32,
+});
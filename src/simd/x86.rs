@@ -0,0 +1,121 @@
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use super::kernels;
+
+pub(crate) struct Sse2;
+pub(crate) struct Avx2;
+pub(crate) struct Avx512;
+
+// Operations some instruction sets lack, built from the ones they have.
+// The absolute value clears the sign bit.
+
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn fmadd_pd(a: __m128d, b: __m128d, c: __m128d) -> __m128d {
+    _mm_add_pd(_mm_mul_pd(a, b), c)
+}
+
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn fmadd_ps(a: __m128, b: __m128, c: __m128) -> __m128 {
+    _mm_add_ps(_mm_mul_ps(a, b), c)
+}
+
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn abs_pd(v: __m128d) -> __m128d {
+    _mm_andnot_pd(_mm_set1_pd(-0.), v)
+}
+
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn abs_ps(v: __m128) -> __m128 {
+    _mm_andnot_ps(_mm_set1_ps(-0.), v)
+}
+
+#[inline]
+#[target_feature(enable = "avx")]
+unsafe fn abs256_pd(v: __m256d) -> __m256d {
+    _mm256_andnot_pd(_mm256_set1_pd(-0.), v)
+}
+
+#[inline]
+#[target_feature(enable = "avx")]
+unsafe fn abs256_ps(v: __m256) -> __m256 {
+    _mm256_andnot_ps(_mm256_set1_ps(-0.), v)
+}
+
+kernels!(Sse2, "sse2", f64, 2, 6, {
+    zero: _mm_setzero_pd,
+    splat: _mm_set1_pd,
+    load: _mm_loadu_pd,
+    store: _mm_storeu_pd,
+    add: _mm_add_pd,
+    mul: _mm_mul_pd,
+    fmadd: fmadd_pd,
+    abs: abs_pd,
+    max: _mm_max_pd,
+});
+
+kernels!(Sse2, "sse2", f32, 4, 6, {
+    zero: _mm_setzero_ps,
+    splat: _mm_set1_ps,
+    load: _mm_loadu_ps,
+    store: _mm_storeu_ps,
+    add: _mm_add_ps,
+    mul: _mm_mul_ps,
+    fmadd: fmadd_ps,
+    abs: abs_ps,
+    max: _mm_max_ps,
+});
+
+kernels!(Avx2, "avx2,fma", f64, 4, 6, {
+    zero: _mm256_setzero_pd,
+    splat: _mm256_set1_pd,
+    load: _mm256_loadu_pd,
+    store: _mm256_storeu_pd,
+    add: _mm256_add_pd,
+    mul: _mm256_mul_pd,
+    fmadd: _mm256_fmadd_pd,
+    abs: abs256_pd,
+    max: _mm256_max_pd,
+});
+
+kernels!(Avx2, "avx2,fma", f32, 8, 6, {
+    zero: _mm256_setzero_ps,
+    splat: _mm256_set1_ps,
+    load: _mm256_loadu_ps,
+    store: _mm256_storeu_ps,
+    add: _mm256_add_ps,
+    mul: _mm256_mul_ps,
+    fmadd: _mm256_fmadd_ps,
+    abs: abs256_ps,
+    max: _mm256_max_ps,
+});
+
+kernels!(Avx512, "avx512f", f64, 8, 8, {
+    zero: _mm512_setzero_pd,
+    splat: _mm512_set1_pd,
+    load: _mm512_loadu_pd,
+    store: _mm512_storeu_pd,
+    add: _mm512_add_pd,
+    mul: _mm512_mul_pd,
+    fmadd: _mm512_fmadd_pd,
+    abs: _mm512_abs_pd,
+    max: _mm512_max_pd,
+});
+
+kernels!(Avx512, "avx512f", f32, 16, 8, {
+    zero: _mm512_setzero_ps,
+    splat: _mm512_set1_ps,
+    load: _mm512_loadu_ps,
+    store: _mm512_storeu_ps,
+    add: _mm512_add_ps,
+    mul: _mm512_mul_ps,
+    fmadd: _mm512_fmadd_ps,
+    abs: _mm512_abs_ps,
+    max: _mm512_max_ps,
+});
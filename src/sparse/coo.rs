@@ -0,0 +1,188 @@
+use crate::error::LinalgError;
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+
+use super::{CscMatrix, CsrMatrix};
+
+/// Sparse matrix in coordinate (triplet) format, for assembly.
+///
+/// Entries can be pushed in any order and the same position may be pushed
+/// several times: duplicates are summed when converting to another format.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CooMatrix<K> {
+    rows: usize,
+    cols: usize,
+    row_indices: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<K>,
+}
+
+impl<K: Scalar> CooMatrix<K> {
+    /// An empty `rows x cols` matrix.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        CooMatrix {
+            rows,
+            cols,
+            row_indices: Vec::new(),
+            col_indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Builds a matrix from `(row, col, value)` triplets, failing with
+    /// `OutOfBounds` for an index outside the shape.
+    pub fn try_from_triplets<I>(rows: usize, cols: usize, triplets: I) -> Result<Self, LinalgError>
+    where
+        I: IntoIterator<Item = (usize, usize, K)>,
+    {
+        let mut coo = CooMatrix::new(rows, cols);
+        for (i, j, value) in triplets {
+            coo.try_push(i, j, value)?;
+        }
+        Ok(coo)
+    }
+
+    /// Panicking version of [`CooMatrix::try_from_triplets`].
+    pub fn from_triplets<I>(rows: usize, cols: usize, triplets: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, K)>,
+    {
+        Self::try_from_triplets(rows, cols, triplets).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Adds `value` at `(i, j)`, failing with `OutOfBounds` outside the
+    /// shape.
+    pub fn try_push(&mut self, i: usize, j: usize, value: K) -> Result<(), LinalgError> {
+        if i >= self.rows || j >= self.cols {
+            return Err(LinalgError::OutOfBounds {
+                index: (i, j),
+                shape: self.shape(),
+            });
+        }
+        self.row_indices.push(i);
+        self.col_indices.push(j);
+        self.values.push(value);
+        Ok(())
+    }
+
+    /// Panicking version of [`CooMatrix::try_push`].
+    pub fn push(&mut self, i: usize, j: usize, value: K) {
+        self.try_push(i, j, value)
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// Number of stored triplets, duplicates included.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The stored `(row, col, value)` triplets, in insertion order.
+    pub fn triplets(&self) -> impl Iterator<Item = (usize, usize, K)> + '_ {
+        self.row_indices
+            .iter()
+            .zip(&self.col_indices)
+            .zip(&self.values)
+            .map(|((&i, &j), &v)| (i, j, v))
+    }
+
+    /// The transpose, swapping the indices of every triplet.
+    pub fn transpose(&self) -> Self {
+        CooMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            row_indices: self.col_indices.clone(),
+            col_indices: self.row_indices.clone(),
+            values: self.values.clone(),
+        }
+    }
+
+    /// Compressed sparse row form, with duplicates summed.
+    pub fn to_csr(&self) -> CsrMatrix<K> {
+        CsrMatrix::from(self)
+    }
+
+    /// Compressed sparse column form, with duplicates summed.
+    pub fn to_csc(&self) -> CscMatrix<K> {
+        CscMatrix::from(self)
+    }
+
+    /// Dense copy, with duplicates summed.
+    pub fn to_dense(&self) -> Matrix<K> {
+        let mut dense = Matrix::zeros(self.rows, self.cols);
+        for (i, j, v) in self.triplets() {
+            dense[(i, j)] += v;
+        }
+        dense
+    }
+}
+
+/// Keeps the nonzero elements.
+impl<K: Scalar> From<&Matrix<K>> for CooMatrix<K> {
+    fn from(dense: &Matrix<K>) -> Self {
+        let (rows, cols) = dense.shape();
+        let mut coo = CooMatrix::new(rows, cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                if !dense[(i, j)].is_zero() {
+                    coo.push(i, j, dense[(i, j)]);
+                }
+            }
+        }
+        coo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assembly() {
+        let mut coo = CooMatrix::new(2, 3);
+        coo.push(0, 0, 1.);
+        coo.push(1, 2, 2.);
+        coo.push(0, 0, 3.);
+        assert_eq!(3, coo.nnz());
+        assert_eq!(Matrix::from([[4., 0., 0.], [0., 0., 2.]]), coo.to_dense());
+        assert_eq!(
+            Matrix::from([[4., 0.], [0., 0.], [0., 2.]]),
+            coo.transpose().to_dense()
+        );
+        assert_eq!(
+            vec![(0, 0, 1.), (1, 2, 2.), (0, 0, 3.)],
+            coo.triplets().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(
+            Err(LinalgError::OutOfBounds {
+                index: (2, 0),
+                shape: (2, 2)
+            }),
+            CooMatrix::try_from_triplets(2, 2, [(0, 0, 1), (2, 0, 1)])
+        );
+        let mut coo = CooMatrix::new(1, 1);
+        assert!(coo.try_push(0, 1, 1).is_err());
+        assert_eq!(0, coo.nnz());
+    }
+
+    #[test]
+    #[should_panic(expected = "index (0, 5) out of bounds for a 2x2 matrix")]
+    fn push_out_of_bounds() {
+        CooMatrix::new(2, 2).push(0, 5, 1.);
+    }
+
+    #[test]
+    fn from_dense() {
+        let dense = Matrix::from([[0, 1], [2, 0]]);
+        let coo = CooMatrix::from(&dense);
+        assert_eq!(2, coo.nnz());
+        assert_eq!(dense, coo.to_dense());
+    }
+}
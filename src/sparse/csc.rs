@@ -0,0 +1,340 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::error::{check_dimension, LinalgError};
+use crate::iterative::LinearOperator;
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use crate::vector::Vector;
+
+use super::{CooMatrix, CsrMatrix};
+
+/// Sparse matrix in compressed sparse column format.
+///
+/// The row indices of column `j` are
+/// `row_indices()[col_ptr()[j]..col_ptr()[j + 1]]`, strictly increasing,
+/// with the matching elements in `values()`.
+/// These are exactly the arrays of the CSR form of the transpose, which is
+/// how the matrix is stored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CscMatrix<K> {
+    transpose: CsrMatrix<K>,
+}
+
+impl<K: Scalar> CscMatrix<K> {
+    /// The `rows x cols` matrix with no stored elements.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        CscMatrix {
+            transpose: CsrMatrix::zeros(cols, rows),
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        CscMatrix {
+            transpose: CsrMatrix::identity(n),
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows(), self.cols())
+    }
+
+    pub fn rows(&self) -> usize {
+        self.transpose.cols()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.transpose.rows()
+    }
+
+    /// Number of stored elements.
+    pub fn nnz(&self) -> usize {
+        self.transpose.nnz()
+    }
+
+    pub fn col_ptr(&self) -> &[usize] {
+        self.transpose.row_ptr()
+    }
+
+    pub fn row_indices(&self) -> &[usize] {
+        self.transpose.col_indices()
+    }
+
+    pub fn values(&self) -> &[K] {
+        self.transpose.values()
+    }
+
+    pub fn values_mut(&mut self) -> &mut [K] {
+        self.transpose.values_mut()
+    }
+
+    /// The stored `(row, value)` pairs of column `j`, by increasing row.
+    pub fn col(&self, j: usize) -> impl Iterator<Item = (usize, K)> + '_ {
+        self.transpose.row(j)
+    }
+
+    /// Element `(i, j)`, zero when not stored. Panics outside the shape.
+    pub fn get(&self, i: usize, j: usize) -> K {
+        assert!(
+            i < self.rows() && j < self.cols(),
+            "{}",
+            LinalgError::OutOfBounds {
+                index: (i, j),
+                shape: self.shape()
+            }
+        );
+        self.transpose.get(j, i)
+    }
+
+    pub fn to_dense(&self) -> Matrix<K> {
+        let mut dense = Matrix::zeros(self.rows(), self.cols());
+        for j in 0..self.cols() {
+            for (i, v) in self.col(j) {
+                dense[(i, j)] = v;
+            }
+        }
+        dense
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<K> {
+        self.transpose.to_coo().transpose()
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<K> {
+        self.transpose.transpose()
+    }
+
+    /// The transpose in CSR form, a copy of the arrays of `self`; see
+    /// [`CscMatrix::into_transpose`] to avoid it.
+    pub fn transpose(&self) -> CsrMatrix<K> {
+        self.transpose.clone()
+    }
+
+    /// Consumes the matrix into the CSR form of its transpose, without
+    /// copying.
+    pub fn into_transpose(self) -> CsrMatrix<K> {
+        self.transpose
+    }
+
+    /// Sparse matrix-vector product, or an error when the number of columns
+    /// does not match the size of `x`.
+    pub fn try_mul_vec(&self, x: &Vector<K>) -> Result<Vector<K>, LinalgError> {
+        check_dimension(self.cols(), x.size())?;
+        let mut y = vec![K::zero(); self.rows()];
+        for (j, &xj) in x.e.iter().enumerate() {
+            for (i, v) in self.col(j) {
+                y[i] += v * xj;
+            }
+        }
+        Ok(Vector::from(y))
+    }
+
+    /// Panicking version of [`CscMatrix::try_mul_vec`].
+    pub fn mul_vec(&self, x: &Vector<K>) -> Vector<K> {
+        self.try_mul_vec(x).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Sparse matrix product, computed as `(B^T A^T)^T`, or an error when the
+    /// inner dimensions differ.
+    pub fn try_mul_mat(&self, other: &CscMatrix<K>) -> Result<CscMatrix<K>, LinalgError> {
+        check_dimension(self.cols(), other.rows())?;
+        Ok(CscMatrix {
+            transpose: other.transpose.try_mul_mat(&self.transpose)?,
+        })
+    }
+
+    /// Panicking version of [`CscMatrix::try_mul_mat`].
+    pub fn mul_mat(&self, other: &CscMatrix<K>) -> CscMatrix<K> {
+        self.try_mul_mat(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Element-wise sum, or an error when the shapes differ.
+    pub fn try_add(&self, other: &CscMatrix<K>) -> Result<CscMatrix<K>, LinalgError> {
+        Ok(CscMatrix {
+            transpose: self
+                .transpose
+                .try_add(&other.transpose)
+                .map_err(transpose_error)?,
+        })
+    }
+
+    /// Element-wise difference, or an error when the shapes differ.
+    pub fn try_sub(&self, other: &CscMatrix<K>) -> Result<CscMatrix<K>, LinalgError> {
+        Ok(CscMatrix {
+            transpose: self
+                .transpose
+                .try_sub(&other.transpose)
+                .map_err(transpose_error)?,
+        })
+    }
+
+    /// Element-wise (Hadamard) product, or an error when the shapes differ.
+    /// Only positions stored in both operands are kept.
+    pub fn try_component_mul(&self, other: &CscMatrix<K>) -> Result<CscMatrix<K>, LinalgError> {
+        Ok(CscMatrix {
+            transpose: self
+                .transpose
+                .try_component_mul(&other.transpose)
+                .map_err(transpose_error)?,
+        })
+    }
+
+    /// Panicking version of [`CscMatrix::try_component_mul`].
+    pub fn component_mul(&self, other: &CscMatrix<K>) -> CscMatrix<K> {
+        self.try_component_mul(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Scales every element by `a`.
+    pub fn scl(&mut self, a: K) {
+        self.transpose.scl(a);
+    }
+}
+
+/// Reports a shape mismatch between transposes in terms of the original
+/// shapes.
+fn transpose_error(e: LinalgError) -> LinalgError {
+    match e {
+        LinalgError::ShapeMismatch { expected, found } => LinalgError::ShapeMismatch {
+            expected: (expected.1, expected.0),
+            found: (found.1, found.0),
+        },
+        e => e,
+    }
+}
+
+/// Sorts each column and sums duplicates, in `O(nnz log nnz)`.
+impl<K: Scalar> From<&CooMatrix<K>> for CscMatrix<K> {
+    fn from(coo: &CooMatrix<K>) -> Self {
+        CscMatrix {
+            transpose: CsrMatrix::from(&coo.transpose()),
+        }
+    }
+}
+
+/// Keeps the nonzero elements.
+impl<K: Scalar> From<&Matrix<K>> for CscMatrix<K> {
+    fn from(dense: &Matrix<K>) -> Self {
+        CscMatrix::from(&CooMatrix::from(dense))
+    }
+}
+
+impl<K: Scalar> From<&CsrMatrix<K>> for CscMatrix<K> {
+    fn from(csr: &CsrMatrix<K>) -> Self {
+        CscMatrix {
+            transpose: csr.transpose(),
+        }
+    }
+}
+
+impl<K: Scalar> LinearOperator<K> for CscMatrix<K> {
+    fn apply(&self, x: &Vector<K>) -> Vector<K> {
+        self.mul_vec(x)
+    }
+}
+
+impl<K: Scalar> Add for &CscMatrix<K> {
+    type Output = CscMatrix<K>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.try_add(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<K: Scalar> Sub for &CscMatrix<K> {
+    type Output = CscMatrix<K>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.try_sub(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<K: Scalar> Mul for &CscMatrix<K> {
+    type Output = CscMatrix<K>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.mul_mat(rhs)
+    }
+}
+
+impl<K: Scalar> Mul<&Vector<K>> for &CscMatrix<K> {
+    type Output = Vector<K>;
+
+    fn mul(self, rhs: &Vector<K>) -> Self::Output {
+        self.mul_vec(rhs)
+    }
+}
+
+impl<K: Scalar> Mul<K> for CscMatrix<K> {
+    type Output = CscMatrix<K>;
+
+    fn mul(mut self, rhs: K) -> Self::Output {
+        self.scl(rhs);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Matrix<i32> {
+        Matrix::from([[1, 0, 2, 0], [0, 0, 0, 0], [3, 4, 0, 5]])
+    }
+
+    #[test]
+    fn structure() {
+        let csc = CscMatrix::from(&sample());
+        assert_eq!((3, 4), csc.shape());
+        assert_eq!(&[0, 2, 3, 4, 5], csc.col_ptr());
+        assert_eq!(&[0, 2, 2, 0, 2], csc.row_indices());
+        assert_eq!(&[1, 3, 4, 2, 5], csc.values());
+        assert_eq!(sample(), csc.to_dense());
+        assert_eq!(2, csc.get(0, 2));
+        assert_eq!(0, csc.get(1, 2));
+        assert_eq!(csc, csc.to_coo().to_csc());
+        assert_eq!(csc.to_csr(), CsrMatrix::from(&sample()));
+        assert_eq!(csc, CscMatrix::from(&csc.to_csr()));
+        assert_eq!(sample().transpose(), csc.transpose().to_dense());
+    }
+
+    #[test]
+    fn products() {
+        let a = CscMatrix::from(&sample());
+        let x = Vector::from([1, 2, 3, 4]);
+        assert_eq!(sample().mul_vec(&x), &a * &x);
+        assert!(a.try_mul_vec(&Vector::from([1, 2])).is_err());
+
+        let b = CscMatrix::from(&sample().transpose());
+        assert_eq!(
+            sample().mul_mat(&sample().transpose()),
+            (&a * &b).to_dense()
+        );
+        assert_eq!(a, &CscMatrix::identity(3) * &a);
+        assert!(a.try_mul_mat(&a).is_err());
+    }
+
+    #[test]
+    fn elementwise() {
+        let a = CscMatrix::from(&sample());
+        let b = CscMatrix::from(&Matrix::from([[1, 1, 0, 0], [0, 2, 0, 0], [-3, 0, 0, 1]]));
+        assert_eq!(
+            Matrix::from([[2, 1, 2, 0], [0, 2, 0, 0], [0, 4, 0, 6]]),
+            (&a + &b).to_dense()
+        );
+        assert_eq!(
+            Matrix::from([[0, -1, 2, 0], [0, -2, 0, 0], [6, 4, 0, 4]]),
+            (&a - &b).to_dense()
+        );
+        assert_eq!(
+            Matrix::from([[1, 0, 0, 0], [0, 0, 0, 0], [-9, 0, 0, 5]]),
+            a.component_mul(&b).to_dense()
+        );
+        assert_eq!(
+            Err(LinalgError::ShapeMismatch {
+                expected: (3, 4),
+                found: (4, 3)
+            }),
+            a.try_add(&CscMatrix::zeros(4, 3))
+        );
+    }
+}
@@ -0,0 +1,468 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::error::{check_dimension, check_shape, LinalgError};
+use crate::iterative::{LinearOperator, RowAccess};
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use crate::vector::Vector;
+
+use super::CooMatrix;
+
+/// Sparse matrix in compressed sparse row format.
+///
+/// The column indices of row `i` are
+/// `col_indices()[row_ptr()[i]..row_ptr()[i + 1]]`, strictly increasing,
+/// with the matching elements in `values()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsrMatrix<K> {
+    rows: usize,
+    cols: usize,
+    row_ptr: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<K>,
+}
+
+impl<K: Scalar> CsrMatrix<K> {
+    /// The `rows x cols` matrix with no stored elements.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        CsrMatrix {
+            rows,
+            cols,
+            row_ptr: vec![0; rows + 1],
+            col_indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        CsrMatrix {
+            rows: n,
+            cols: n,
+            row_ptr: (0..=n).collect(),
+            col_indices: (0..n).collect(),
+            values: vec![K::one(); n],
+        }
+    }
+
+    /// Assembles a matrix from its compressed arrays, which must already
+    /// satisfy the invariants of the format.
+    pub(crate) fn from_parts(
+        rows: usize,
+        cols: usize,
+        row_ptr: Vec<usize>,
+        col_indices: Vec<usize>,
+        values: Vec<K>,
+    ) -> Self {
+        debug_assert_eq!(rows + 1, row_ptr.len());
+        debug_assert_eq!(col_indices.len(), values.len());
+        debug_assert_eq!(Some(&values.len()), row_ptr.last());
+        CsrMatrix {
+            rows,
+            cols,
+            row_ptr,
+            col_indices,
+            values,
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Number of stored elements.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn row_ptr(&self) -> &[usize] {
+        &self.row_ptr
+    }
+
+    pub fn col_indices(&self) -> &[usize] {
+        &self.col_indices
+    }
+
+    pub fn values(&self) -> &[K] {
+        &self.values
+    }
+
+    pub fn values_mut(&mut self) -> &mut [K] {
+        &mut self.values
+    }
+
+    /// The stored `(col, value)` pairs of row `i`, by increasing column.
+    pub fn row(&self, i: usize) -> impl Iterator<Item = (usize, K)> + '_ {
+        let range = self.row_ptr[i]..self.row_ptr[i + 1];
+        self.col_indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter().copied())
+    }
+
+    /// Element `(i, j)`, zero when not stored. Panics outside the shape.
+    pub fn get(&self, i: usize, j: usize) -> K {
+        assert!(
+            i < self.rows && j < self.cols,
+            "{}",
+            LinalgError::OutOfBounds {
+                index: (i, j),
+                shape: self.shape()
+            }
+        );
+        let range = self.row_ptr[i]..self.row_ptr[i + 1];
+        match self.col_indices[range.clone()].binary_search(&j) {
+            Ok(k) => self.values[range.start + k],
+            Err(_) => K::zero(),
+        }
+    }
+
+    pub fn to_dense(&self) -> Matrix<K> {
+        let mut dense = Matrix::zeros(self.rows, self.cols);
+        for i in 0..self.rows {
+            for (j, v) in self.row(i) {
+                dense[(i, j)] = v;
+            }
+        }
+        dense
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<K> {
+        CooMatrix::from_triplets(
+            self.rows,
+            self.cols,
+            (0..self.rows).flat_map(|i| self.row(i).map(move |(j, v)| (i, j, v))),
+        )
+    }
+
+    /// The transpose, in `O(nnz + cols)`.
+    pub fn transpose(&self) -> Self {
+        let mut row_ptr = vec![0; self.cols + 1];
+        for &j in &self.col_indices {
+            row_ptr[j + 1] += 1;
+        }
+        for j in 0..self.cols {
+            row_ptr[j + 1] += row_ptr[j];
+        }
+        let mut next = row_ptr.clone();
+        let mut col_indices = vec![0; self.nnz()];
+        let mut values = vec![K::zero(); self.nnz()];
+        // Rows are visited in order, so each transposed row comes out
+        // sorted.
+        for i in 0..self.rows {
+            for (j, v) in self.row(i) {
+                col_indices[next[j]] = i;
+                values[next[j]] = v;
+                next[j] += 1;
+            }
+        }
+        CsrMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            row_ptr,
+            col_indices,
+            values,
+        }
+    }
+
+    /// Sparse matrix-vector product, or an error when the number of columns
+    /// does not match the size of `x`.
+    pub fn try_mul_vec(&self, x: &Vector<K>) -> Result<Vector<K>, LinalgError> {
+        check_dimension(self.cols, x.size())?;
+        Ok(Vector::from(
+            (0..self.rows)
+                .map(|i| self.row(i).fold(K::zero(), |s, (j, v)| s + v * x[j]))
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    /// Panicking version of [`CsrMatrix::try_mul_vec`].
+    pub fn mul_vec(&self, x: &Vector<K>) -> Vector<K> {
+        self.try_mul_vec(x).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Sparse matrix product by Gustavson's row-by-row algorithm, or an
+    /// error when the inner dimensions differ.
+    pub fn try_mul_mat(&self, other: &CsrMatrix<K>) -> Result<CsrMatrix<K>, LinalgError> {
+        check_dimension(self.cols, other.rows)?;
+        let mut product = CsrMatrix::zeros(self.rows, other.cols);
+        product.row_ptr.clear();
+        product.row_ptr.push(0);
+        // Dense accumulator for one row of the product, with the columns
+        // touched so far.
+        let mut acc = vec![K::zero(); other.cols];
+        let mut touched = vec![false; other.cols];
+        let mut pattern = Vec::new();
+        for i in 0..self.rows {
+            for (k, a) in self.row(i) {
+                for (j, b) in other.row(k) {
+                    if !touched[j] {
+                        touched[j] = true;
+                        pattern.push(j);
+                    }
+                    acc[j] += a * b;
+                }
+            }
+            pattern.sort_unstable();
+            for &j in &pattern {
+                product.col_indices.push(j);
+                product.values.push(acc[j]);
+                acc[j] = K::zero();
+                touched[j] = false;
+            }
+            pattern.clear();
+            product.row_ptr.push(product.nnz());
+        }
+        Ok(product)
+    }
+
+    /// Panicking version of [`CsrMatrix::try_mul_mat`].
+    pub fn mul_mat(&self, other: &CsrMatrix<K>) -> CsrMatrix<K> {
+        self.try_mul_mat(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Element-wise sum, or an error when the shapes differ.
+    pub fn try_add(&self, other: &CsrMatrix<K>) -> Result<CsrMatrix<K>, LinalgError> {
+        self.merge(other, true, |a, b| a + b)
+    }
+
+    /// Element-wise difference, or an error when the shapes differ.
+    pub fn try_sub(&self, other: &CsrMatrix<K>) -> Result<CsrMatrix<K>, LinalgError> {
+        self.merge(other, true, |a, b| a - b)
+    }
+
+    /// Element-wise (Hadamard) product, or an error when the shapes differ.
+    /// Only positions stored in both operands are kept.
+    pub fn try_component_mul(&self, other: &CsrMatrix<K>) -> Result<CsrMatrix<K>, LinalgError> {
+        self.merge(other, false, |a, b| a * b)
+    }
+
+    /// Panicking version of [`CsrMatrix::try_component_mul`].
+    pub fn component_mul(&self, other: &CsrMatrix<K>) -> CsrMatrix<K> {
+        self.try_component_mul(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Scales every element by `a`.
+    pub fn scl(&mut self, a: K) {
+        for v in &mut self.values {
+            *v *= a;
+        }
+    }
+
+    /// Combines two matrices of the same shape row by row, over the union
+    /// or the intersection of their patterns, a missing element counting
+    /// as zero.
+    fn merge<F>(&self, other: &CsrMatrix<K>, union: bool, f: F) -> Result<CsrMatrix<K>, LinalgError>
+    where
+        F: Fn(K, K) -> K,
+    {
+        check_shape(self.shape(), other.shape())?;
+        let mut result = CsrMatrix::zeros(self.rows, self.cols);
+        let zero = K::zero();
+        for i in 0..self.rows {
+            let mut a = self.row(i).peekable();
+            let mut b = other.row(i).peekable();
+            loop {
+                let (j, v, both) = match (a.peek().copied(), b.peek().copied()) {
+                    (Some((ja, va)), Some((jb, vb))) if ja == jb => {
+                        a.next();
+                        b.next();
+                        (ja, f(va, vb), true)
+                    }
+                    (Some((ja, va)), Some((jb, _))) if ja < jb => {
+                        a.next();
+                        (ja, f(va, zero), false)
+                    }
+                    (Some((ja, va)), None) => {
+                        a.next();
+                        (ja, f(va, zero), false)
+                    }
+                    (_, Some((jb, vb))) => {
+                        b.next();
+                        (jb, f(zero, vb), false)
+                    }
+                    (None, None) => break,
+                };
+                if union || both {
+                    result.col_indices.push(j);
+                    result.values.push(v);
+                }
+            }
+            result.row_ptr[i + 1] = result.nnz();
+        }
+        Ok(result)
+    }
+}
+
+/// Sorts each row and sums duplicates, in `O(nnz log nnz)`.
+impl<K: Scalar> From<&CooMatrix<K>> for CsrMatrix<K> {
+    fn from(coo: &CooMatrix<K>) -> Self {
+        let (rows, cols) = coo.shape();
+        let mut entries: Vec<(usize, usize, K)> = coo.triplets().collect();
+        entries.sort_by_key(|&(i, j, _)| (i, j));
+        let mut csr = CsrMatrix::zeros(rows, cols);
+        let mut last = None;
+        for (i, j, v) in entries {
+            if last == Some((i, j)) {
+                *csr.values.last_mut().unwrap() += v;
+            } else {
+                csr.col_indices.push(j);
+                csr.values.push(v);
+                csr.row_ptr[i + 1] += 1;
+                last = Some((i, j));
+            }
+        }
+        for i in 0..rows {
+            csr.row_ptr[i + 1] += csr.row_ptr[i];
+        }
+        csr
+    }
+}
+
+/// Keeps the nonzero elements.
+impl<K: Scalar> From<&Matrix<K>> for CsrMatrix<K> {
+    fn from(dense: &Matrix<K>) -> Self {
+        CsrMatrix::from(&CooMatrix::from(dense))
+    }
+}
+
+impl<K: Scalar> LinearOperator<K> for CsrMatrix<K> {
+    fn apply(&self, x: &Vector<K>) -> Vector<K> {
+        self.mul_vec(x)
+    }
+}
+
+impl<K: Scalar> RowAccess<K> for CsrMatrix<K> {
+    fn shape(&self) -> (usize, usize) {
+        self.shape()
+    }
+
+    fn row_entries(&self, i: usize) -> impl Iterator<Item = (usize, K)> + '_ {
+        self.row(i)
+    }
+}
+
+impl<K: Scalar> Add for &CsrMatrix<K> {
+    type Output = CsrMatrix<K>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.try_add(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<K: Scalar> Sub for &CsrMatrix<K> {
+    type Output = CsrMatrix<K>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.try_sub(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<K: Scalar> Mul for &CsrMatrix<K> {
+    type Output = CsrMatrix<K>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.mul_mat(rhs)
+    }
+}
+
+impl<K: Scalar> Mul<&Vector<K>> for &CsrMatrix<K> {
+    type Output = Vector<K>;
+
+    fn mul(self, rhs: &Vector<K>) -> Self::Output {
+        self.mul_vec(rhs)
+    }
+}
+
+impl<K: Scalar> Mul<K> for CsrMatrix<K> {
+    type Output = CsrMatrix<K>;
+
+    fn mul(mut self, rhs: K) -> Self::Output {
+        self.scl(rhs);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Matrix<i32> {
+        Matrix::from([[1, 0, 2, 0], [0, 0, 0, 0], [3, 4, 0, 5]])
+    }
+
+    #[test]
+    fn structure() {
+        let csr = CsrMatrix::from(&sample());
+        assert_eq!(&[0, 2, 2, 5], csr.row_ptr());
+        assert_eq!(&[0, 2, 0, 1, 3], csr.col_indices());
+        assert_eq!(&[1, 2, 3, 4, 5], csr.values());
+        assert_eq!(sample(), csr.to_dense());
+        assert_eq!(4, csr.get(2, 1));
+        assert_eq!(0, csr.get(1, 1));
+        assert_eq!(csr, csr.to_coo().to_csr());
+        assert_eq!(sample().transpose(), csr.transpose().to_dense());
+        assert_eq!(csr, csr.transpose().transpose());
+    }
+
+    #[test]
+    fn duplicates_are_summed() {
+        let coo = CooMatrix::from_triplets(2, 2, [(1, 1, 1.), (0, 1, 2.), (1, 1, 3.), (1, 0, 1.)]);
+        let csr = coo.to_csr();
+        assert_eq!(3, csr.nnz());
+        assert_eq!(Matrix::from([[0., 2.], [1., 4.]]), csr.to_dense());
+    }
+
+    #[test]
+    fn products() {
+        let a = CsrMatrix::from(&sample());
+        let x = Vector::from([1, 2, 3, 4]);
+        assert_eq!(sample().mul_vec(&x), &a * &x);
+        assert!(a.try_mul_vec(&Vector::from([1, 2])).is_err());
+
+        let b = a.transpose();
+        assert_eq!(
+            sample().mul_mat(&sample().transpose()),
+            (&a * &b).to_dense()
+        );
+        assert_eq!(
+            sample().transpose().mul_mat(&sample()),
+            (&b * &a).to_dense()
+        );
+        assert_eq!(a, &CsrMatrix::identity(3) * &a);
+        assert!(a.try_mul_mat(&a).is_err());
+    }
+
+    #[test]
+    fn elementwise() {
+        let a = CsrMatrix::from(&sample());
+        let b = CsrMatrix::from(&Matrix::from([[1, 1, 0, 0], [0, 2, 0, 0], [-3, 0, 0, 1]]));
+        assert_eq!(
+            Matrix::from([[2, 1, 2, 0], [0, 2, 0, 0], [0, 4, 0, 6]]),
+            (&a + &b).to_dense()
+        );
+        assert_eq!(
+            Matrix::from([[0, -1, 2, 0], [0, -2, 0, 0], [6, 4, 0, 4]]),
+            (&a - &b).to_dense()
+        );
+        let product = a.component_mul(&b);
+        assert_eq!(3, product.nnz());
+        assert_eq!(
+            Matrix::from([[1, 0, 0, 0], [0, 0, 0, 0], [-9, 0, 0, 5]]),
+            product.to_dense()
+        );
+        assert_eq!(
+            Matrix::from([[2, 0, 4, 0], [0, 0, 0, 0], [6, 8, 0, 10]]),
+            (a.clone() * 2).to_dense()
+        );
+        assert!(a.try_add(&CsrMatrix::zeros(2, 2)).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+//! Sparse matrices, storing only their nonzero elements.
+//!
+//! [`CooMatrix`] collects `(row, col, value)` triplets in any order and is
+//! meant for assembly; it then converts to [`CsrMatrix`] or [`CscMatrix`],
+//! the compressed row and column formats used for arithmetic. All three
+//! convert to and from a dense [`Matrix`](crate::Matrix), and the
+//! compressed ones implement [`LinearOperator`](crate::iterative::LinearOperator)
+//! so they can be handed to the iterative solvers directly.
+
+mod coo;
+mod csc;
+mod csr;
+
+pub use coo::CooMatrix;
+pub use csc::CscMatrix;
+pub use csr::CsrMatrix;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterative::{cg, tests::laplacian, IterativeOptions};
+    use crate::vector::Vector;
+
+    #[test]
+    fn solve_sparse_system() {
+        let n = 100;
+        let mut coo = CooMatrix::new(n, n);
+        for i in 0..n {
+            coo.push(i, i, 2.);
+            if i > 0 {
+                coo.push(i, i - 1, -1.);
+                coo.push(i - 1, i, -1.);
+            }
+        }
+        let a = coo.to_csr();
+        assert_eq!(3 * n - 2, a.nnz());
+        assert_eq!(laplacian(n), a.to_dense());
+        let b = Vector::from(vec![1.; n]);
+        let x = cg(&a, &b, IterativeOptions::default().tol(1e-10)).unwrap();
+        let csc = coo.to_csc();
+        let y = cg(&csc, &b, IterativeOptions::default().tol(1e-10)).unwrap();
+        assert_eq!(x.iterations(), y.iterations());
+        let mut r = b.clone();
+        r.sub_mut(&a.mul_vec(x.solution()));
+        assert!(r.norm() <= 1e-9 * b.norm());
+    }
+}
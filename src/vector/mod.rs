@@ -1,13 +1,21 @@
 use core::fmt;
-use num_complex::Complex;
-use num_traits::Float;
+use num_traits::{Float, One, Zero};
+
+use crate::error::{check_dimension, LinalgError};
+use crate::scalar::{Field, Scalar};
+use crate::simd;
+
+mod view;
+
+pub(crate) use view::resolve_range;
+pub use view::{VectorView, VectorViewMut};
 
 use std::{
     fmt::{Debug, Display},
     ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign},
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Vector<K> {
     pub e: Vec<K>,
 }
@@ -47,25 +55,31 @@ where
 
 impl<K: Debug> Display for Vector<K> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Ok(for (i, col) in self.e.iter().enumerate() {
+        for (i, col) in self.e.iter().enumerate() {
             write!(f, "[{:?}]", col)?;
             if i < self.e.len() - 1 {
-                write!(f, "\n")?;
+                writeln!(f)?;
             }
-        })
+        }
+        Ok(())
     }
 }
 
-impl<K> AddAssign for Vector<K>
+fn assert_same_size<K>(u: &Vector<K>, v: &Vector<K>, op: &str) {
+    assert_eq!(
+        u.e.len(),
+        v.e.len(),
+        "Vectors must be the same length for {}",
+        op
+    );
+}
+
+impl<K> AddAssign<&Vector<K>> for Vector<K>
 where
-    K: AddAssign + Copy,
+    K: Scalar,
 {
-    fn add_assign(&mut self, rhs: Vector<K>) {
-        assert_eq!(
-            self.e.len(),
-            rhs.e.len(),
-            "Vectors must be the same length for addition"
-        );
+    fn add_assign(&mut self, rhs: &Vector<K>) {
+        assert_same_size(self, rhs, "addition");
         self.e
             .iter_mut()
             .zip(rhs.e.iter())
@@ -73,16 +87,21 @@ where
     }
 }
 
-impl<K> SubAssign for Vector<K>
+impl<K> AddAssign for Vector<K>
 where
-    K: SubAssign + Copy,
+    K: Scalar,
 {
-    fn sub_assign(&mut self, rhs: Vector<K>) {
-        assert_eq!(
-            self.e.len(),
-            rhs.e.len(),
-            "Vectors must be the same length for subtraction"
-        );
+    fn add_assign(&mut self, rhs: Vector<K>) {
+        *self += &rhs;
+    }
+}
+
+impl<K> SubAssign<&Vector<K>> for Vector<K>
+where
+    K: Scalar,
+{
+    fn sub_assign(&mut self, rhs: &Vector<K>) {
+        assert_same_size(self, rhs, "subtraction");
         self.e
             .iter_mut()
             .zip(rhs.e.iter())
@@ -90,66 +109,157 @@ where
     }
 }
 
+impl<K> SubAssign for Vector<K>
+where
+    K: Scalar,
+{
+    fn sub_assign(&mut self, rhs: Vector<K>) {
+        *self -= &rhs;
+    }
+}
+
 impl<K> MulAssign<K> for Vector<K>
 where
-    K: MulAssign + Copy,
+    K: Scalar,
 {
     fn mul_assign(&mut self, rhs: K) {
-        let scalar = K::from(rhs);
-        self.e.iter_mut().for_each(|e| *e *= scalar);
+        self.e.iter_mut().for_each(|e| *e *= rhs);
     }
 }
 
+// The by-value operators reuse the buffer of an owned operand; only
+// `&u + &v`, `&u - &v` and `&u * a` allocate, once.
+
 impl<K> Add for Vector<K>
 where
-    K: AddAssign + Add<Output = K> + Copy,
+    K: Scalar,
 {
     type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += &rhs;
+        self
+    }
+}
+
+impl<K> Add<&Vector<K>> for Vector<K>
+where
+    K: Scalar,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: &Vector<K>) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<K> Add<Vector<K>> for &Vector<K>
+where
+    K: Scalar,
+{
+    type Output = Vector<K>;
+
+    fn add(self, mut rhs: Vector<K>) -> Self::Output {
+        rhs += self;
+        rhs
+    }
+}
+
+impl<K> Add for &Vector<K>
+where
+    K: Scalar,
+{
+    type Output = Vector<K>;
+
     fn add(self, rhs: Self) -> Self::Output {
-        assert_eq!(
-            self.e.len(),
-            rhs.e.len(),
-            "Vectors must be the same length for addition"
-        );
-        let mut vec = self.clone();
-        for (p1, p2) in vec.e.iter_mut().zip(rhs.e.iter()) {
-            *p1 += *p2;
-        }
-        vec
+        assert_same_size(self, rhs, "addition");
+        self.e
+            .iter()
+            .zip(&rhs.e)
+            .map(|(&a, &b)| a + b)
+            .collect::<Vec<_>>()
+            .into()
     }
 }
 
 impl<K> Sub for Vector<K>
 where
-    K: SubAssign + Copy,
+    K: Scalar,
 {
     type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= &rhs;
+        self
+    }
+}
+
+impl<K> Sub<&Vector<K>> for Vector<K>
+where
+    K: Scalar,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: &Vector<K>) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<K> Sub<Vector<K>> for &Vector<K>
+where
+    K: Scalar,
+{
+    type Output = Vector<K>;
+
+    fn sub(self, mut rhs: Vector<K>) -> Self::Output {
+        assert_same_size(self, &rhs, "subtraction");
+        rhs.e
+            .iter_mut()
+            .zip(&self.e)
+            .for_each(|(b, &a)| *b = a - *b);
+        rhs
+    }
+}
+
+impl<K> Sub for &Vector<K>
+where
+    K: Scalar,
+{
+    type Output = Vector<K>;
+
     fn sub(self, rhs: Self) -> Self::Output {
-        assert_eq!(
-            self.e.len(),
-            rhs.e.len(),
-            "Vectors must be the same length for subtraction"
-        );
-        let mut vec = self.clone();
-        for (p1, p2) in vec.e.iter_mut().zip(rhs.e.iter()) {
-            *p1 -= *p2;
-        }
-        vec
+        assert_same_size(self, rhs, "subtraction");
+        self.e
+            .iter()
+            .zip(&rhs.e)
+            .map(|(&a, &b)| a - b)
+            .collect::<Vec<_>>()
+            .into()
     }
 }
 
 impl<K> Mul<K> for Vector<K>
 where
-    K: MulAssign + Copy, // or whatever bounds you need
+    K: Scalar,
 {
     type Output = Self;
 
+    fn mul(mut self, rhs: K) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<K> Mul<K> for &Vector<K>
+where
+    K: Scalar,
+{
+    type Output = Vector<K>;
+
     fn mul(self, rhs: K) -> Self::Output {
-        let mut vec = self.clone();
-        for e in &mut vec.e {
-            *e *= rhs;
-        }
-        vec
+        self.e.iter().map(|&e| e * rhs).collect::<Vec<_>>().into()
     }
 }
 
@@ -201,85 +311,261 @@ impl<K> Vector<K> {
     // MANDATORY -- ex00
     pub fn add_mut(&mut self, v: &Vector<K>)
     where
-        K: AddAssign + Copy,
+        K: Scalar,
     {
-        *self += v.clone();
+        *self += v;
     }
 
     pub fn sub_mut(&mut self, v: &Vector<K>)
     where
-        K: SubAssign + Copy,
+        K: Scalar,
     {
-        *self -= v.clone();
+        *self -= v;
     }
 
     pub fn scl(&mut self, a: K)
     where
-        K: MulAssign + Copy,
+        K: Scalar,
     {
         *self *= a;
     }
+
+    /// Fallible [`Vector::add_mut`]: fails instead of panicking when the
+    /// sizes differ.
+    pub fn try_add_mut(&mut self, v: &Vector<K>) -> Result<(), LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.size(), v.size())?;
+        self.add_mut(v);
+        Ok(())
+    }
+
+    /// Fallible [`Vector::sub_mut`]: fails instead of panicking when the
+    /// sizes differ.
+    pub fn try_sub_mut(&mut self, v: &Vector<K>) -> Result<(), LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.size(), v.size())?;
+        self.sub_mut(v);
+        Ok(())
+    }
+
+    /// Returns `self + v`, or an error when the sizes differ.
+    pub fn try_add(&self, v: &Vector<K>) -> Result<Vector<K>, LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.size(), v.size())?;
+        Ok(self + v)
+    }
+
+    /// Returns `self - v`, or an error when the sizes differ.
+    pub fn try_sub(&self, v: &Vector<K>) -> Result<Vector<K>, LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.size(), v.size())?;
+        Ok(self - v)
+    }
     // END of ex00
 
     // MANDATORY -- ex03
     pub fn dot(&self, v: &Vector<K>) -> K
     where
-        K: Default + Mul<Output = K> + AddAssign + Copy,
+        K: Scalar,
     {
         assert_eq!(self.size(), v.size(), "Vectors are not the same size");
-        let mut value = K::default();
+        if let Some(value) = simd::dot(&self.e, &v.e) {
+            return value;
+        }
+        let mut value = K::zero();
         self.e
             .iter()
             .zip(v.e.iter())
             .for_each(|(e1, e2)| value += *e1 * *e2);
         value
     }
+
+    /// Fallible [`Vector::dot`]: fails instead of panicking when the sizes
+    /// differ.
+    pub fn try_dot(&self, v: &Vector<K>) -> Result<K, LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.size(), v.size())?;
+        Ok(self.dot(v))
+    }
+
+    /// Hermitian inner product `conj(self[0]) * v[0] + conj(self[1]) * v[1] + ...`,
+    /// which is [`Vector::dot`] for real vectors.
+    ///
+    /// Panics if the sizes differ.
+    pub fn dotc(&self, v: &Vector<K>) -> K
+    where
+        K: Scalar,
+    {
+        self.try_dotc(v).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_dotc(&self, v: &Vector<K>) -> Result<K, LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.size(), v.size())?;
+        let mut value = K::zero();
+        self.e
+            .iter()
+            .zip(v.e.iter())
+            .for_each(|(e1, e2)| value += e1.conj() * *e2);
+        Ok(value)
+    }
+
+    /// Complex conjugate of every element.
+    pub fn conj(&self) -> Vector<K>
+    where
+        K: Scalar,
+    {
+        self.e.iter().map(|e| e.conj()).collect::<Vec<_>>().into()
+    }
     // END of ex03
 
     // MANDATORY -- ex04
     // NOTE -- Norms always return real numbers, evenfor complex-valued vectors.
-    pub fn norm_1(&mut self) -> f32
+    /// Taxicab norm, the sum of the moduli.
+    pub fn norm_1(&self) -> K::Real
     where
-        K: Float,
-        f32: AddAssign<K>,
+        K: Field,
     {
-        let mut result = f32::default();
-        for p in &self.e {
-            result += p.abs();
-        }
-        result
+        simd::norm_1(&self.e).unwrap_or_else(|| norm_1(&self.e))
     }
 
-    pub fn norm(&mut self) -> f32
+    /// Euclidean norm.
+    ///
+    /// Accumulates the sum of squares relative to the largest modulus seen so
+    /// far, like LAPACK's `nrm2`, so it neither overflows nor underflows when
+    /// the squares of the elements would.
+    pub fn norm(&self) -> K::Real
     where
-        K: Float,
-        f32: AddAssign<K>,
+        K: Field,
     {
-        let mut result = f32::default();
-        for p in &self.e {
-            result += p.powf(K::from(2.0).unwrap());
-        }
-        result.sqrt()
+        simd::norm(&self.e).unwrap_or_else(|| norm_2(&self.e))
     }
 
-    pub fn norm_inf(&mut self) -> f32
+    /// Supremum norm, the largest modulus; zero for an empty vector and NaN
+    /// if an element is NaN.
+    pub fn norm_inf(&self) -> K::Real
     where
-        K: Float,
-        f32: AddAssign<K> + PartialOrd<K> + Copy,
+        K: Field,
     {
-        let mut result = f32::min_value();
-        for p in &self.e {
-            if result < p.abs() {
-                result = p.abs().to_f32().unwrap();
-            }
-        }
-        result
+        simd::norm_inf(&self.e).unwrap_or_else(|| norm_inf(&self.e))
+    }
+
+    /// The `p`-norm `(|x0|^p + |x1|^p + ...)^(1/p)`, scaled by the largest
+    /// modulus to avoid overflow. `p = inf` gives [`Vector::norm_inf`].
+    ///
+    /// Panics if `p` is less than one or NaN.
+    pub fn norm_p(&self, p: K::Real) -> K::Real
+    where
+        K: Field,
+    {
+        norm_p(&self.e, p)
     }
     // END of ex04
 }
 
+// Norm kernels shared by every vector type.
+
+pub(crate) fn norm_1<'a, K: Field + 'a>(x: impl IntoIterator<Item = &'a K>) -> K::Real {
+    let mut result = K::Real::zero();
+    for p in x {
+        result += p.modulus();
+    }
+    result
+}
+
+pub(crate) fn norm_2<'a, K: Field + 'a>(x: impl IntoIterator<Item = &'a K>) -> K::Real {
+    let mut scale = K::Real::zero();
+    let mut ssq = K::Real::one();
+    // Infinite moduli stay out of the scaling, where `inf / inf` would turn
+    // them into NaN, and win over any sum without a NaN at the end.
+    let mut infinite = false;
+    for p in x {
+        let a = p.modulus();
+        if a.is_zero() {
+            continue;
+        }
+        if a.is_infinite() {
+            infinite = true;
+            continue;
+        }
+        if scale < a {
+            let r = scale / a;
+            ssq = K::Real::one() + ssq * r * r;
+            scale = a;
+        } else {
+            let r = a / scale;
+            ssq += r * r;
+        }
+    }
+    let norm = scale * ssq.sqrt();
+    if infinite && !norm.is_nan() {
+        K::Real::infinity()
+    } else {
+        norm
+    }
+}
+
+pub(crate) fn norm_inf<'a, K: Field + 'a>(x: impl IntoIterator<Item = &'a K>) -> K::Real {
+    let mut result = K::Real::zero();
+    for p in x {
+        let a = p.modulus();
+        if a.is_nan() {
+            return a;
+        }
+        result = result.max(a);
+    }
+    result
+}
+
+pub(crate) fn norm_p<'a, K, I>(x: I, p: K::Real) -> K::Real
+where
+    K: Field + 'a,
+    I: IntoIterator<Item = &'a K> + Clone,
+{
+    let one = K::Real::one();
+    assert!(p >= one, "p-norms are only defined for p >= 1");
+    if p == one {
+        return norm_1(x);
+    }
+    if p == one + one {
+        return norm_2(x);
+    }
+    let scale = norm_inf(x.clone());
+    if p.is_infinite() || scale.is_zero() {
+        return scale;
+    }
+    let mut sum = K::Real::zero();
+    let mut infinite = false;
+    for e in x {
+        let a = e.modulus();
+        if a.is_infinite() {
+            infinite = true;
+            continue;
+        }
+        sum += (a / scale).powf(p);
+    }
+    if infinite && !sum.is_nan() {
+        return K::Real::infinity();
+    }
+    scale * sum.powf(one / p)
+}
+
 #[cfg(test)]
 mod tests {
+    use num_complex::Complex;
+
     use super::*;
 
     #[test]
@@ -295,6 +581,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assign_op_pattern)]
     fn vector_add() {
         let mut u = Vector::from([1., 2.]);
         let v = Vector::from([7., 4.]);
@@ -318,6 +605,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assign_op_pattern)]
     fn vector_sub() {
         let mut u = Vector::from([1., 2.]);
         let v = Vector::from([7., 4.]);
@@ -341,6 +629,43 @@ mod tests {
     }
 
     #[test]
+    fn vector_ops_reuse_buffers() {
+        let u = Vector::from([1., 2.]);
+        let v = Vector::from([7., 4.]);
+        assert_eq!(Vector::from([8., 6.]), &u + &v);
+        assert_eq!(Vector::from([-6., -2.]), &u - &v);
+        assert_eq!(Vector::from([2., 4.]), &u * 2.);
+
+        let buffer = u.e.as_ptr();
+        let w = u + &v;
+        assert_eq!(buffer, w.e.as_ptr());
+        let w = w - v;
+        assert_eq!(buffer, w.e.as_ptr());
+
+        let v = Vector::from([7., 4.]);
+        let buffer = v.e.as_ptr();
+        let x = &w - v;
+        assert_eq!(Vector::from([-6., -2.]), x);
+        assert_eq!(buffer, x.e.as_ptr());
+        let x = &w + x;
+        assert_eq!(Vector::from([-5., 0.]), x);
+        assert_eq!(buffer, x.e.as_ptr());
+
+        let mut y = x * 2.;
+        assert_eq!(buffer, y.e.as_ptr());
+        y += &w;
+        y -= &Vector::from([1., 1.]);
+        assert_eq!(Vector::from([-10., 1.]), y);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vectors must be the same length for subtraction")]
+    fn vector_ref_sub_mismatch_panics() {
+        let _ = &Vector::from([1.]) - &Vector::from([1., 2.]);
+    }
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)]
     fn vector_scale() {
         let mut u = Vector::from([1., 1.]);
         u.scl(42.);
@@ -395,59 +720,188 @@ mod tests {
         assert_eq!(10, u.dot(&v));
     }
 
+    #[test]
+    fn vector_try_ops() {
+        let mut u = Vector::from([1., 2.]);
+        let v = Vector::from([7., 4.]);
+        let w = Vector::from([1., 2., 3.]);
+        assert_eq!(Ok(vec![8., 6.]), u.try_add(&v).map(|r| r.e));
+        assert_eq!(Ok(vec![-6., -2.]), u.try_sub(&v).map(|r| r.e));
+        assert_eq!(Ok(15.), u.try_dot(&v));
+        assert_eq!(Ok(()), u.try_add_mut(&v));
+        assert_eq!(vec![8., 6.], u.e);
+
+        let mismatch = Err(LinalgError::DimensionMismatch {
+            expected: 2,
+            found: 3,
+        });
+        assert_eq!(mismatch, u.try_add_mut(&w));
+        assert_eq!(mismatch, u.try_sub_mut(&w));
+        assert!(u.try_dot(&w).is_err());
+        assert!(u.try_add(&w).is_err());
+        assert!(u.try_sub(&w).is_err());
+        assert_eq!(vec![8., 6.], u.e);
+    }
+
+    #[test]
+    fn complex_vectors() {
+        let u = Vector::from([Complex::new(1., 1.), Complex::new(0., 2.)]);
+        let v = Vector::from([Complex::new(2., 0.), Complex::new(1., -1.)]);
+        // conj(1 + i) * 2 + conj(2i) * (1 - i) = (2 - 2i) + (-2 - 2i)
+        assert_eq!(Complex::new(0., -4.), u.dotc(&v));
+        assert_eq!(Complex::new(6., 0.), u.dotc(&u));
+        assert_eq!(Complex::new(4., 4.), u.dot(&v));
+        assert_eq!(
+            vec![Complex::new(1., -1.), Complex::new(0., -2.)],
+            u.conj().e
+        );
+        assert!(u.try_dotc(&Vector::from([Complex::new(1., 0.)])).is_err());
+
+        let u = Vector::from([Complex::new(3., 4.), Complex::new(0., -12.)]);
+        assert_eq!(17., u.norm_1());
+        assert_eq!(13., u.norm());
+        assert_eq!(12., u.norm_inf());
+
+        let u = Vector::from([Complex::new(0f32, 0.)]);
+        assert_eq!(0., u.norm());
+    }
+
     #[test]
     fn norms_test_basics() {
-        let mut u = Vector::from([0., 0., 0.]);
+        let u = Vector::from([0., 0., 0.]);
         assert_eq!(u.norm_1(), 0.0);
         assert_eq!(u.norm(), 0.0);
         assert_eq!(u.norm_inf(), 0.0);
 
-        let mut u = Vector::from([1., 2., 3.]);
+        let u = Vector::from([1f32, 2., 3.]);
         assert_eq!(u.norm_1(), 6.0);
-        assert_eq!(u.norm(), 3.7416575);
+        assert!((u.norm() - 3.741_657_5).abs() <= f32::EPSILON * 4.);
         assert_eq!(u.norm_inf(), 3.);
 
-        let mut u = Vector::from([-1., -2.]);
+        let u = Vector::from([-1f32, -2.]);
         assert_eq!(u.norm_1(), 3.0);
-        assert_eq!(u.norm(), 2.236067977);
+        assert!((u.norm() - 2.236_068).abs() <= f32::EPSILON * 4.);
         assert_eq!(u.norm_inf(), 2.);
     }
 
     #[test]
     fn norms_test_hards() {
-        let mut u = Vector::from([0.]);
+        let u = Vector::from([0.]);
         assert_eq!(u.norm_1(), 0.);
         assert_eq!(u.norm(), 0.);
         assert_eq!(u.norm_inf(), 0.);
 
-        let mut u = Vector::from([1.]);
+        let u = Vector::from([1.]);
         assert_eq!(u.norm_1(), 1.);
         assert_eq!(u.norm(), 1.);
         assert_eq!(u.norm_inf(), 1.);
 
-        let mut u = Vector::from([0., 0.]);
+        let u = Vector::from([0., 0.]);
         assert_eq!(u.norm_1(), 0.);
         assert_eq!(u.norm(), 0.);
         assert_eq!(u.norm_inf(), 0.);
 
-        let mut u = Vector::from([1., 0.]);
+        let u = Vector::from([1., 0.]);
         assert_eq!(u.norm_1(), 1.);
         assert_eq!(u.norm(), 1.);
         assert_eq!(u.norm_inf(), 1.);
 
-        let mut u = Vector::from([2., 1.]);
+        let u = Vector::from([2f32, 1.]);
         assert_eq!(u.norm_1(), 3.);
-        assert_eq!(u.norm(), 2.236067977);
+        assert!((u.norm() - 2.236_068).abs() <= f32::EPSILON * 4.);
         assert_eq!(u.norm_inf(), 2.);
 
-        let mut u = Vector::from([4., 2.]);
+        let u = Vector::from([4f32, 2.]);
         assert_eq!(u.norm_1(), 6.);
-        assert_eq!(u.norm(), 4.472135955);
+        assert!((u.norm() - 4.472_136).abs() <= f32::EPSILON * 8.);
         assert_eq!(u.norm_inf(), 4.);
 
-        let mut u = Vector::from([-4., -2.]);
+        let u = Vector::from([-4f32, -2.]);
         assert_eq!(u.norm_1(), 6.);
-        assert_eq!(u.norm(), 4.472135955);
+        assert!((u.norm() - 4.472_136).abs() <= f32::EPSILON * 8.);
         assert_eq!(u.norm_inf(), 4.);
     }
+
+    #[test]
+    fn norms_keep_precision() {
+        let u = Vector::from([1f64, 2., 3.]);
+        assert!((u.norm() - 14f64.sqrt()).abs() <= f64::EPSILON * 4.);
+
+        // Squaring these would overflow or underflow.
+        let u = Vector::from([3e200f64, 4e200]);
+        assert!((u.norm() / 5e200 - 1.).abs() <= f64::EPSILON * 4.);
+        let u = Vector::from([3e-200f64, -4e-200]);
+        assert!((u.norm() / 5e-200 - 1.).abs() <= f64::EPSILON * 4.);
+        let u = Vector::from([3e30f32, 4e30]);
+        assert!((u.norm() / 5e30 - 1.).abs() <= f32::EPSILON * 4.);
+
+        let u = Vector::from([-5., -7.]);
+        assert_eq!(7., u.norm_inf());
+        assert_eq!(0., Vector::<f64>::from(vec![]).norm_inf());
+    }
+
+    #[test]
+    fn p_norms() {
+        let u = Vector::from([3f64, -4.]);
+        assert_eq!(7., u.norm_p(1.));
+        assert_eq!(5., u.norm_p(2.));
+        assert_eq!(4., u.norm_p(f64::INFINITY));
+        assert!((u.norm_p(3.) - 91f64.cbrt()).abs() <= f64::EPSILON * 8.);
+        assert_eq!(0., Vector::from([0., 0.]).norm_p(3.));
+
+        let u = Vector::from([3e200f64, 4e200]);
+        assert!((u.norm_p(3.) / (91f64.cbrt() * 1e200) - 1.).abs() <= f64::EPSILON * 8.);
+    }
+
+    #[test]
+    fn norms_of_infinities() {
+        let inf = f64::INFINITY;
+        for u in [
+            Vector::from([inf, inf]),
+            Vector::from([1., -inf]),
+            Vector::from([-inf, 1e300, 0.]),
+        ] {
+            assert_eq!(inf, u.norm());
+            assert_eq!(inf, u.view().norm());
+            assert_eq!(inf, u.norm_p(3.));
+            assert_eq!(inf, u.norm_1());
+        }
+        assert_eq!(f32::INFINITY, Vector::from([f32::INFINITY; 9]).norm());
+        assert_eq!(
+            inf,
+            Vector::from([Complex::new(inf, 0.), Complex::new(0., inf)]).norm()
+        );
+
+        for u in [
+            Vector::from([inf, f64::NAN]),
+            Vector::from([f64::NAN, -inf]),
+        ] {
+            assert!(u.norm().is_nan());
+            assert!(u.norm_p(3.).is_nan());
+            assert!(u.norm_1().is_nan());
+        }
+        assert!(Vector::from([1., f64::NAN]).norm_p(3.).is_nan());
+    }
+
+    #[test]
+    fn norm_inf_propagates_nan() {
+        let mut x = vec![1.; 40];
+        x[33] = f64::NAN;
+        x[7] = f64::INFINITY;
+        let u = Vector::from(x);
+        assert!(u.norm_inf().is_nan());
+        assert!(u.view().norm_inf().is_nan());
+        assert!(u.norm_p(f64::INFINITY).is_nan());
+        assert!(Vector::from([1., f64::NAN]).norm_inf().is_nan());
+        assert!(Vector::from([f32::NAN, 1.]).norm_inf().is_nan());
+        assert!(Vector::from([Complex::new(1., f64::NAN)])
+            .norm_inf()
+            .is_nan());
+    }
+
+    #[test]
+    #[should_panic(expected = "p-norms are only defined for p >= 1")]
+    fn p_norm_below_one_panics() {
+        Vector::from([1., 2.]).norm_p(0.5);
+    }
 }
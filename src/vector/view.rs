@@ -0,0 +1,647 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{
+    Add, AddAssign, Bound, Index, IndexMut, Mul, MulAssign, RangeBounds, Sub, SubAssign,
+};
+
+use crate::error::{check_dimension, LinalgError};
+use crate::scalar::{Field, Scalar};
+use crate::simd;
+
+use super::Vector;
+
+/// Borrowed, possibly strided, window into a vector or a matrix: element `i`
+/// sits `i * stride` elements past the first.
+///
+/// Views of matrix rows, columns and diagonals are built by
+/// [`MatrixView`](crate::MatrixView); vectors and slices give contiguous
+/// ones through [`Vector::view`] and [`VectorView::from_slice`].
+pub struct VectorView<'a, K> {
+    ptr: *const K,
+    len: usize,
+    stride: usize,
+    marker: PhantomData<&'a K>,
+}
+
+/// Mutable counterpart of [`VectorView`].
+pub struct VectorViewMut<'a, K> {
+    ptr: *mut K,
+    len: usize,
+    stride: usize,
+    marker: PhantomData<&'a mut K>,
+}
+
+// The views behave like `&[K]` and `&mut [K]`.
+unsafe impl<K: Sync> Send for VectorView<'_, K> {}
+unsafe impl<K: Sync> Sync for VectorView<'_, K> {}
+unsafe impl<K: Send> Send for VectorViewMut<'_, K> {}
+unsafe impl<K: Sync> Sync for VectorViewMut<'_, K> {}
+
+impl<K> Clone for VectorView<'_, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for VectorView<'_, K> {}
+
+/// Resolves `range` against a dimension of length `len`, panicking if it
+/// does not fit.
+pub(crate) fn resolve_range(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&a) => a,
+        Bound::Excluded(&a) => a + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&b) => b + 1,
+        Bound::Excluded(&b) => b,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end && end <= len,
+        "range {}..{} out of bounds for length {}",
+        start,
+        end,
+        len
+    );
+    (start, end)
+}
+
+/// Number of elements a view of `len` elements `stride` apart spans.
+fn span(len: usize, stride: usize) -> usize {
+    if len == 0 {
+        0
+    } else {
+        (len - 1) * stride + 1
+    }
+}
+
+impl<'a, K> VectorView<'a, K> {
+    /// # Safety
+    ///
+    /// `ptr + i * stride` must be valid for reads for every `i < len`
+    /// during `'a`, and not written through anything else.
+    pub(crate) unsafe fn from_raw_parts(ptr: *const K, len: usize, stride: usize) -> Self {
+        VectorView {
+            ptr,
+            len,
+            stride,
+            marker: PhantomData,
+        }
+    }
+
+    /// Views `len` elements of `data` that are `stride` apart, failing with
+    /// `DimensionMismatch` if `data` is too short.
+    pub fn from_slice(data: &'a [K], len: usize, stride: usize) -> Result<Self, LinalgError> {
+        let needed = span(len, stride);
+        if needed > data.len() {
+            return Err(LinalgError::DimensionMismatch {
+                expected: needed,
+                found: data.len(),
+            });
+        }
+        // SAFETY: every element lies within `data`, borrowed for `'a`.
+        Ok(unsafe { VectorView::from_raw_parts(data.as_ptr(), len, stride) })
+    }
+
+    pub fn size(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Distance in the underlying buffer between consecutive elements.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn get(&self, i: usize) -> Option<&'a K> {
+        // SAFETY: in bounds, see `from_raw_parts`.
+        (i < self.len).then(|| unsafe { &*self.ptr.wrapping_add(i * self.stride) })
+    }
+
+    /// The elements as a slice, when they are contiguous.
+    pub fn as_slice(&self) -> Option<&'a [K]> {
+        // SAFETY: with a unit stride the elements are the `len` values from
+        // `ptr`, see `from_raw_parts`.
+        (self.stride == 1 || self.len <= 1)
+            .then(|| unsafe { std::slice::from_raw_parts(self.ptr, self.len) })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a K> + Clone + 'a {
+        let view = *self;
+        (0..view.len).map(move |i| view.get(i).unwrap())
+    }
+
+    /// The elements in `range`, as a view of the same data.
+    pub fn slice(self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = resolve_range(range, self.len);
+        VectorView {
+            ptr: self.ptr.wrapping_add(start * self.stride),
+            len: end - start,
+            ..self
+        }
+    }
+
+    /// Copies the elements into an owned vector.
+    pub fn to_vector(&self) -> Vector<K>
+    where
+        K: Copy,
+    {
+        Vector::from(self.iter().copied().collect::<Vec<_>>())
+    }
+
+    /// Panics if the sizes differ.
+    pub fn dot(&self, v: VectorView<K>) -> K
+    where
+        K: Scalar,
+    {
+        self.try_dot(v).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_dot(&self, v: VectorView<K>) -> Result<K, LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.len, v.len)?;
+        if let (Some(x), Some(y)) = (self.as_slice(), v.as_slice()) {
+            if let Some(dot) = simd::dot(x, y) {
+                return Ok(dot);
+            }
+        }
+        Ok(self
+            .iter()
+            .zip(v.iter())
+            .fold(K::zero(), |acc, (&a, &b)| acc + a * b))
+    }
+
+    /// Hermitian inner product, see [`Vector::dotc`]. Panics if the sizes
+    /// differ.
+    pub fn dotc(&self, v: VectorView<K>) -> K
+    where
+        K: Scalar,
+    {
+        self.try_dotc(v).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_dotc(&self, v: VectorView<K>) -> Result<K, LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.len, v.len)?;
+        Ok(self
+            .iter()
+            .zip(v.iter())
+            .fold(K::zero(), |acc, (&a, &b)| acc + a.conj() * b))
+    }
+
+    pub fn norm_1(&self) -> K::Real
+    where
+        K: Field,
+    {
+        self.as_slice()
+            .and_then(simd::norm_1)
+            .unwrap_or_else(|| super::norm_1(self.iter()))
+    }
+
+    pub fn norm(&self) -> K::Real
+    where
+        K: Field,
+    {
+        self.as_slice()
+            .and_then(simd::norm)
+            .unwrap_or_else(|| super::norm_2(self.iter()))
+    }
+
+    pub fn norm_inf(&self) -> K::Real
+    where
+        K: Field,
+    {
+        self.as_slice()
+            .and_then(simd::norm_inf)
+            .unwrap_or_else(|| super::norm_inf(self.iter()))
+    }
+
+    pub fn norm_p(&self, p: K::Real) -> K::Real
+    where
+        K: Field,
+    {
+        super::norm_p(self.iter(), p)
+    }
+}
+
+impl<'a, K> VectorViewMut<'a, K> {
+    /// # Safety
+    ///
+    /// `ptr + i * stride` must be distinct and valid for reads and writes
+    /// for every `i < len` during `'a`, and not accessed through anything
+    /// else.
+    pub(crate) unsafe fn from_raw_parts(ptr: *mut K, len: usize, stride: usize) -> Self {
+        VectorViewMut {
+            ptr,
+            len,
+            stride,
+            marker: PhantomData,
+        }
+    }
+
+    /// Views `len` elements of `data` that are `stride` apart, failing with
+    /// `DimensionMismatch` if `data` is too short.
+    ///
+    /// # Panics
+    ///
+    /// If `stride` is zero and `len` is more than one, which would alias
+    /// elements.
+    pub fn from_slice(data: &'a mut [K], len: usize, stride: usize) -> Result<Self, LinalgError> {
+        assert!(
+            stride > 0 || len <= 1,
+            "a mutable view cannot have a zero stride"
+        );
+        let needed = span(len, stride);
+        if needed > data.len() {
+            return Err(LinalgError::DimensionMismatch {
+                expected: needed,
+                found: data.len(),
+            });
+        }
+        // SAFETY: the elements are distinct and lie within `data`, borrowed
+        // mutably for `'a`.
+        Ok(unsafe { VectorViewMut::from_raw_parts(data.as_mut_ptr(), len, stride) })
+    }
+
+    pub fn size(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// A read-only view of the same elements.
+    pub fn as_view(&self) -> VectorView<'_, K> {
+        // SAFETY: borrows `self`, so nothing writes during the new lifetime.
+        unsafe { VectorView::from_raw_parts(self.ptr, self.len, self.stride) }
+    }
+
+    /// A shorter-lived mutable view of the same elements.
+    pub fn reborrow(&mut self) -> VectorViewMut<'_, K> {
+        // SAFETY: borrows `self` mutably.
+        unsafe { VectorViewMut::from_raw_parts(self.ptr, self.len, self.stride) }
+    }
+
+    /// Turns the view into a read-only one for the rest of `'a`.
+    pub fn into_view(self) -> VectorView<'a, K> {
+        // SAFETY: `self` is consumed.
+        unsafe { VectorView::from_raw_parts(self.ptr, self.len, self.stride) }
+    }
+
+    /// The elements in `range`, as a view of the same data.
+    pub fn slice(self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = resolve_range(range, self.len);
+        VectorViewMut {
+            ptr: self.ptr.wrapping_add(start * self.stride),
+            len: end - start,
+            ..self
+        }
+    }
+
+    /// The elements as a mutable slice, when they are contiguous.
+    pub fn as_mut_slice(&mut self) -> Option<&mut [K]> {
+        // SAFETY: as in `VectorView::as_slice`, borrowing `self` mutably.
+        (self.stride == 1 || self.len <= 1)
+            .then(|| unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut K> + '_ {
+        let (ptr, stride) = (self.ptr, self.stride);
+        // SAFETY: the elements are distinct and borrowed through `self`.
+        (0..self.len).map(move |i| unsafe { &mut *ptr.wrapping_add(i * stride) })
+    }
+
+    pub fn fill(&mut self, value: K)
+    where
+        K: Copy,
+    {
+        self.iter_mut().for_each(|e| *e = value);
+    }
+
+    /// Overwrites the elements with those of `v`. Panics if the sizes
+    /// differ.
+    pub fn copy_from(&mut self, v: VectorView<K>)
+    where
+        K: Copy,
+    {
+        self.zip_apply(v, |a, b| *a = b);
+    }
+
+    /// Panics if the sizes differ.
+    pub fn add_mut(&mut self, v: VectorView<K>)
+    where
+        K: Scalar,
+    {
+        self.zip_apply(v, |a, b| *a += b);
+    }
+
+    /// Panics if the sizes differ.
+    pub fn sub_mut(&mut self, v: VectorView<K>)
+    where
+        K: Scalar,
+    {
+        self.zip_apply(v, |a, b| *a -= b);
+    }
+
+    /// `self += a * v`. Panics if the sizes differ.
+    pub fn axpy(&mut self, a: K, v: VectorView<K>)
+    where
+        K: Scalar,
+    {
+        check_dimension(self.len, v.len).unwrap_or_else(|e| panic!("{}", e));
+        if let (Some(x), Some(y)) = (v.as_slice(), self.as_mut_slice()) {
+            if simd::axpy(a, x, y) {
+                return;
+            }
+        }
+        self.zip_apply(v, |y, x| *y += a * x);
+    }
+
+    pub fn try_add_mut(&mut self, v: VectorView<K>) -> Result<(), LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.len, v.len)?;
+        self.add_mut(v);
+        Ok(())
+    }
+
+    pub fn try_sub_mut(&mut self, v: VectorView<K>) -> Result<(), LinalgError>
+    where
+        K: Scalar,
+    {
+        check_dimension(self.len, v.len)?;
+        self.sub_mut(v);
+        Ok(())
+    }
+
+    pub fn scl(&mut self, a: K)
+    where
+        K: Scalar,
+    {
+        self.iter_mut().for_each(|e| *e *= a);
+    }
+
+    fn zip_apply<F>(&mut self, v: VectorView<K>, mut f: F)
+    where
+        K: Copy,
+        F: FnMut(&mut K, K),
+    {
+        check_dimension(self.len, v.len).unwrap_or_else(|e| panic!("{}", e));
+        self.iter_mut().zip(v.iter()).for_each(|(a, &b)| f(a, b));
+    }
+}
+
+impl<K> Index<usize> for VectorView<'_, K> {
+    type Output = K;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        self.get(i)
+            .unwrap_or_else(|| panic!("index {} out of bounds for a view of size {}", i, self.len))
+    }
+}
+
+impl<K> Index<usize> for VectorViewMut<'_, K> {
+    type Output = K;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        assert!(
+            i < self.len,
+            "index {} out of bounds for a view of size {}",
+            i,
+            self.len
+        );
+        // SAFETY: in bounds.
+        unsafe { &*self.ptr.wrapping_add(i * self.stride) }
+    }
+}
+
+impl<K> IndexMut<usize> for VectorViewMut<'_, K> {
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        assert!(
+            i < self.len,
+            "index {} out of bounds for a view of size {}",
+            i,
+            self.len
+        );
+        // SAFETY: in bounds, and borrowed mutably through `self`.
+        unsafe { &mut *self.ptr.wrapping_add(i * self.stride) }
+    }
+}
+
+impl<K: Debug> Debug for VectorView<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Debug> Debug for VectorViewMut<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_view().fmt(f)
+    }
+}
+
+impl<K: PartialEq> PartialEq for VectorView<'_, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: PartialEq> PartialEq<Vector<K>> for VectorView<'_, K> {
+    fn eq(&self, other: &Vector<K>) -> bool {
+        *self == other.view()
+    }
+}
+
+impl<'a, K> From<&'a Vector<K>> for VectorView<'a, K> {
+    fn from(v: &'a Vector<K>) -> Self {
+        v.view()
+    }
+}
+
+impl<'a, K> From<&'a mut Vector<K>> for VectorViewMut<'a, K> {
+    fn from(v: &'a mut Vector<K>) -> Self {
+        v.view_mut()
+    }
+}
+
+impl<K> Vector<K> {
+    /// A contiguous view of the whole vector.
+    pub fn view(&self) -> VectorView<'_, K> {
+        // SAFETY: the elements are those of `self.e`, borrowed.
+        unsafe { VectorView::from_raw_parts(self.e.as_ptr(), self.e.len(), 1) }
+    }
+
+    pub fn view_mut(&mut self) -> VectorViewMut<'_, K> {
+        // SAFETY: the elements are those of `self.e`, borrowed mutably.
+        unsafe { VectorViewMut::from_raw_parts(self.e.as_mut_ptr(), self.e.len(), 1) }
+    }
+}
+
+impl<K: Scalar> Add for VectorView<'_, K> {
+    type Output = Vector<K>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self.to_vector();
+        result += rhs;
+        result
+    }
+}
+
+impl<K: Scalar> Sub for VectorView<'_, K> {
+    type Output = Vector<K>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut result = self.to_vector();
+        result -= rhs;
+        result
+    }
+}
+
+impl<K: Scalar> Mul<K> for VectorView<'_, K> {
+    type Output = Vector<K>;
+
+    fn mul(self, rhs: K) -> Self::Output {
+        Vector::from(self.iter().map(|&e| e * rhs).collect::<Vec<_>>())
+    }
+}
+
+impl<K: Scalar> AddAssign<VectorView<'_, K>> for Vector<K> {
+    fn add_assign(&mut self, rhs: VectorView<K>) {
+        self.view_mut().add_mut(rhs);
+    }
+}
+
+impl<K: Scalar> SubAssign<VectorView<'_, K>> for Vector<K> {
+    fn sub_assign(&mut self, rhs: VectorView<K>) {
+        self.view_mut().sub_mut(rhs);
+    }
+}
+
+impl<K: Scalar> AddAssign<VectorView<'_, K>> for VectorViewMut<'_, K> {
+    fn add_assign(&mut self, rhs: VectorView<K>) {
+        self.add_mut(rhs);
+    }
+}
+
+impl<K: Scalar> SubAssign<VectorView<'_, K>> for VectorViewMut<'_, K> {
+    fn sub_assign(&mut self, rhs: VectorView<K>) {
+        self.sub_mut(rhs);
+    }
+}
+
+impl<K: Scalar> MulAssign<K> for VectorViewMut<'_, K> {
+    fn mul_assign(&mut self, rhs: K) {
+        self.scl(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strided_views() {
+        let data = [1_f64, 2., 3., 4., 5., 6., 7.];
+        let v = VectorView::from_slice(&data, 4, 2).unwrap();
+        assert_eq!(4, v.size());
+        assert_eq!(Vector::from([1., 3., 5., 7.]), v.to_vector());
+        assert_eq!(Vector::from([3., 5.]), v.slice(1..3).to_vector());
+        assert!(v.slice(4..).is_empty());
+        assert_eq!(None, v.get(4));
+        assert_eq!(84., v.dot(v));
+        assert_eq!(16., v.norm_1());
+        assert!((v.norm() - 84_f64.sqrt()).abs() < 1e-14);
+        assert_eq!(7., v.norm_inf());
+        assert_eq!("[1.0, 3.0, 5.0, 7.0]", format!("{:?}", v));
+        assert_eq!(
+            Err(LinalgError::DimensionMismatch {
+                expected: 9,
+                found: 7
+            }),
+            VectorView::from_slice(&data, 5, 2)
+        );
+    }
+
+    #[test]
+    fn mutable_views() {
+        let mut data = [1, 2, 3, 4, 5, 6];
+        let mut v = VectorViewMut::from_slice(&mut data, 3, 2).unwrap();
+        v[1] = 30;
+        v.scl(2);
+        let x = Vector::from([1, 1, 1]);
+        v += x.view();
+        v.axpy(10, x.view());
+        assert_eq!(v.as_view(), Vector::from([13, 71, 21]));
+        v.reborrow().slice(..1).fill(0);
+        assert_eq!([0, 2, 71, 4, 21, 6], data);
+
+        let mut u = Vector::from([1, 2, 3]);
+        u.view_mut()
+            .copy_from(VectorView::from_slice(&data, 3, 2).unwrap());
+        u -= x.view();
+        assert_eq!(Vector::from([-1, 70, 20]), u);
+        assert!(u.view_mut().try_add_mut(Vector::from([1]).view()).is_err());
+    }
+
+    #[test]
+    fn view_arithmetic() {
+        let u = Vector::from([1., 2.]);
+        let v = Vector::from([3., 5.]);
+        assert_eq!(Vector::from([4., 7.]), u.view() + v.view());
+        assert_eq!(Vector::from([-2., -3.]), u.view() - v.view());
+        assert_eq!(Vector::from([2., 4.]), u.view() * 2.);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension mismatch: expected 2, found 3")]
+    fn mismatched_views_panic() {
+        let mut u = Vector::from([1., 2.]);
+        u += Vector::from([1., 2., 3.]).view();
+    }
+
+    #[test]
+    #[should_panic(expected = "range 1..4 out of bounds for length 3")]
+    fn slice_out_of_bounds() {
+        Vector::from([1, 2, 3]).view().slice(1..4);
+    }
+
+    #[test]
+    fn contiguous_and_strided_kernels_agree() {
+        let data: Vec<f64> = (0..40).map(|i| f64::from(i) - 19.5).collect();
+        let contiguous = VectorView::from_slice(&data, 20, 1).unwrap();
+        assert_eq!(Some(&data[..20]), contiguous.as_slice());
+        let strided = VectorView::from_slice(&data, 20, 2).unwrap();
+        assert_eq!(None, strided.as_slice());
+        let copy = strided.to_vector();
+        assert!((copy.view().norm() - strided.norm()).abs() < 1e-12);
+        assert_eq!(copy.view().norm_1(), strided.norm_1());
+        assert_eq!(copy.view().norm_inf(), strided.norm_inf());
+        assert_eq!(copy.dot(&copy), strided.dot(copy.view()));
+
+        let mut y = vec![1.; 40];
+        let mut z = y.clone();
+        VectorViewMut::from_slice(&mut y, 20, 1)
+            .unwrap()
+            .axpy(2., strided);
+        VectorViewMut::from_slice(&mut z, 20, 2)
+            .unwrap()
+            .axpy(2., strided);
+        assert_eq!(y[..20], z.iter().step_by(2).copied().collect::<Vec<_>>());
+    }
+}